@@ -1,5 +1,29 @@
+pub mod audio_extract;
+pub mod av1_bench;
+pub mod backup;
 pub mod batch_compress;
+pub mod clean_old;
+pub mod clean_temp;
+pub mod config;
+pub mod dir_diff;
+pub mod doctor;
+pub mod du;
+pub mod extract;
+pub mod flatten;
+pub mod git_clean;
+pub mod hash;
 pub mod hash_copy;
+pub mod history;
+pub mod join;
+pub mod media_info;
+pub mod prune_archives;
+pub mod rename;
+pub mod repo_mirror;
 pub mod residue_search;
+pub mod schedule;
+pub mod secrets;
+pub mod split;
+pub mod sync;
 pub mod unused_files;
+pub mod video_thumbs;
 pub mod video_transcode;
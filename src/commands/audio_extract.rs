@@ -0,0 +1,248 @@
+//! 音频提取命令模块
+//!
+//! 本模块提供从视频文件中提取音轨并转码为指定音频格式的功能。
+//! 支持 Opus、AAC 和 MP3 三种输出格式。
+//!
+//! # 功能特性
+//!
+//! - 递归扫描目录,最多支持 3 层嵌套
+//! - 支持多种输入视频格式 (mp4, mkv, avi, mov 等)
+//! - 提取第一条音轨,按目标格式转码
+//! - 输出文件与源文件同目录同名,扩展名根据目标格式变化
+//! - 如果目标文件已存在则覆盖
+//! - 先输出到同目录的临时文件,完成后再重命名,中断时不会留下不完整的文件
+
+use crate::utils::filesystem::path_size;
+use crate::utils::i18n::tr;
+use crate::utils::media::{collect_video_files, ensure_ffmpeg, find_ffmpeg};
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// 目标音频格式
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum AudioFormat {
+    /// Opus 格式 (.opus)
+    #[default]
+    Opus,
+    /// AAC 格式 (.m4a)
+    Aac,
+    /// MP3 格式 (.mp3)
+    Mp3,
+}
+
+impl AudioFormat {
+    /// 获取 ffmpeg 音频编码器名称
+    pub fn codec(self) -> &'static str {
+        match self {
+            AudioFormat::Opus => "libopus",
+            AudioFormat::Aac => "aac",
+            AudioFormat::Mp3 => "libmp3lame",
+        }
+    }
+
+    /// 获取输出文件扩展名（不带点）
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Opus => "opus",
+            AudioFormat::Aac => "m4a",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+}
+
+/// 音频提取命令行参数
+#[derive(Args, Debug)]
+#[command(name = "audio_extract")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "从视频文件中提取音频",
+    long_about = "扫描指定目录(最多嵌套三层)下的视频文件,提取第一条音轨并转码为 Opus、AAC 或 MP3 格式。输出文件与源文件同目录同名,扩展名根据目标格式变化。如果目标文件已存在,则覆盖。"
+)]
+pub struct AudioExtractArgs {
+    /// 源目录路径
+    #[arg(
+        short = 's',
+        long,
+        value_name = "SOURCE_DIRECTORY",
+        help = "源目录路径（必须为目录）",
+        long_help = "指定要扫描的源目录，工具会扫描该目录及其子目录（最多三层）中的视频文件。"
+    )]
+    pub source: PathBuf,
+
+    /// 目标音频格式
+    #[arg(
+        short = 'f',
+        long,
+        value_enum,
+        default_value_t = AudioFormat::Opus,
+        help = "目标音频格式",
+        long_help = "指定提取后的音频格式：opus (.opus)、aac (.m4a) 或 mp3 (.mp3)。"
+    )]
+    pub format: AudioFormat,
+
+    /// 音频码率
+    #[arg(
+        short = 'b',
+        long,
+        default_value = "128k",
+        value_name = "BITRATE",
+        help = "音频码率",
+        long_help = "传递给 ffmpeg 的音频码率，例如 96k、128k、192k。默认 128k。"
+    )]
+    pub bitrate: String,
+
+    /// 未安装 ffmpeg 时下载静态构建
+    #[arg(
+        long,
+        help = "未安装 ffmpeg 时自动下载静态构建",
+        long_help = "未检测到 ffmpeg 时，下载适用于当前系统的 ffmpeg 静态构建到应用数据目录并使用，而不是通过 winget 安装。"
+    )]
+    pub download_ffmpeg: bool,
+}
+
+/// 从视频文件中提取音频
+///
+/// 使用 ffmpeg 丢弃视频流，仅保留第一条音轨并转码为指定格式。
+///
+/// # 参数
+///
+/// * `source_path` - 源视频文件路径
+/// * `output_path` - 目标音频文件路径
+/// * `format` - 目标音频格式
+/// * `bitrate` - 音频码率，例如 "128k"
+///
+/// # 返回值
+///
+/// * `Ok(())` - 提取成功
+/// * `Err(anyhow::Error)` - 提取失败，包含详细错误信息
+///
+/// # 技术细节
+///
+/// - `-vn` 丢弃视频流
+/// - `-map 0:a:0` 仅选择第一条音轨
+/// - `-y` 参数自动覆盖已存在的输出文件
+pub async fn extract_audio(
+    source_path: &Path,
+    output_path: &Path,
+    format: AudioFormat,
+    bitrate: &str,
+) -> Result<()> {
+    if !source_path.is_file() {
+        anyhow::bail!(tr!("源文件不存在: {}", source_path.display()));
+    }
+
+    // 先输出到目标目录中的临时文件，完成后再重命名，避免留下不完整的输出文件
+    let temp_file = TempPath::beside(output_path)?;
+
+    let mut cmd = Command::new(find_ffmpeg());
+    cmd.arg("-i")
+        .arg(source_path)
+        .arg("-vn")
+        .arg("-map")
+        .arg("0:a:0")
+        .arg("-c:a")
+        .arg(format.codec())
+        .arg("-b:a")
+        .arg(bitrate)
+        .arg("-y")
+        .arg(temp_file.path())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| tr!("启动 ffmpeg 失败: {}", source_path.display()))?;
+
+    let status = child
+        .wait()
+        .await
+        .with_context(|| tr!("等待 ffmpeg 完成失败: {}", source_path.display()))?;
+
+    if !status.success() {
+        anyhow::bail!(tr!("ffmpeg 提取音频失败: {}", source_path.display()));
+    }
+
+    temp_file.persist(output_path)?;
+
+    stats::add(Counter::Processed, 1);
+    stats::add(Counter::BytesIn, path_size(source_path));
+    stats::add(Counter::BytesOut, path_size(output_path));
+
+    println!("{}", tr!("提取完成: {}", output_path.display()));
+    Ok(())
+}
+
+/// 执行音频提取命令
+///
+/// # 参数
+///
+/// * `args` - 命令行参数,包含源目录、目标格式和码率
+///
+/// # 返回
+///
+/// 执行成功返回 `Ok(())`,失败返回错误信息
+///
+/// # 错误
+///
+/// - 当源目录不存在或无法访问时返回错误
+/// - 当源路径不是目录时返回错误
+/// - 当提取过程失败时返回错误
+pub async fn run(args: AudioExtractArgs) -> Result<()> {
+    // 确保 ffmpeg 可用
+    ensure_ffmpeg(args.download_ffmpeg)?;
+
+    // 规范化源目录路径并检查可访问性
+    let source_dir = args
+        .source
+        .canonicalize()
+        .with_context(|| tr!("无法访问源目录: {}", args.source.display()))?;
+
+    // 确保源路径是目录而非文件
+    if !source_dir.is_dir() {
+        anyhow::bail!(tr!("源路径必须是目录: {}", source_dir.display()));
+    }
+
+    // 打印任务信息
+    println!(
+        "{}",
+        tr!("{} 音频提取工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", source_dir.display()));
+    println!(
+        "{}",
+        tr!(
+            "目标格式: {} ({})",
+            args.format.extension(),
+            args.format.codec()
+        )
+    );
+    println!("{}", tr!("音频码率: {}", args.bitrate));
+    println!();
+
+    // 收集所有视频文件(最多扫描 3 层目录)
+    let video_files = collect_video_files(&source_dir, 3);
+
+    if video_files.is_empty() {
+        println!("{}", tr!("没有找到视频文件"));
+        return Ok(());
+    }
+
+    println!("{}", tr!("找到 {} 个视频文件\n", video_files.len()));
+    stats::add(Counter::Scanned, video_files.len() as u64);
+
+    // 逐个提取音频
+    for (index, video_file) in video_files.iter().enumerate() {
+        println!("{}", tr!("进度: {}/{}", index + 1, video_files.len()));
+        let output_path = video_file.with_extension(args.format.extension());
+        extract_audio(video_file, &output_path, args.format, &args.bitrate).await?;
+        println!();
+    }
+
+    println!("{}", tr!("操作成功完成！"));
+    Ok(())
+}
@@ -0,0 +1,266 @@
+//! AV1 编码器基准测试命令模块
+//!
+//! 本模块使用每个可用的 AV1 编码器进行一次短时间测试编码，
+//! 报告编码速度和输出大小，帮助选择 video-transcode 的 `--encoder` 参数。
+//!
+//! # 功能特性
+//!
+//! - 检测 NVENC、QSV、AMF、SVT-AV1 编码器的可用性
+//! - 默认使用 ffmpeg 生成的测试画面,也可指定真实视频片段
+//! - 使用与 video-transcode 相同的 CRF=25 参数
+//! - 输出耗时、相对实时速度和文件大小
+
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::i18n::tr;
+use crate::utils::media::{Av1Encoder, ensure_ffmpeg, find_ffmpeg, test_encoder};
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// AV1 编码器基准测试命令行参数
+#[derive(Args, Debug)]
+#[command(name = "av1_bench")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "比较各 AV1 编码器的速度和输出大小",
+    long_about = "使用每个可用的 AV1 编码器(NVENC、QSV、AMF、SVT-AV1)进行一次短时间测试编码,报告耗时、相对实时速度和输出文件大小。默认使用 ffmpeg 生成的测试画面,可通过 --input 指定真实视频以获得更有代表性的结果。"
+)]
+pub struct Av1BenchArgs {
+    /// 测试用的输入视频
+    #[arg(
+        short = 'i',
+        long,
+        value_name = "FILE",
+        help = "测试用的输入视频",
+        long_help = "使用该视频的开头片段进行测试。不指定时使用 ffmpeg 生成的 testsrc2 测试画面。"
+    )]
+    pub input: Option<PathBuf>,
+
+    /// 测试时长（秒）
+    #[arg(
+        short = 'd',
+        long,
+        default_value_t = 10,
+        value_name = "SECONDS",
+        help = "测试编码时长（秒）",
+        long_help = "每个编码器编码的视频时长。默认 10 秒。"
+    )]
+    pub duration: u32,
+
+    /// 测试画面分辨率
+    #[arg(
+        long,
+        default_value = "1920x1080",
+        value_name = "WIDTHxHEIGHT",
+        help = "测试画面分辨率",
+        long_help = "生成测试画面时使用的分辨率，指定 --input 时忽略。默认 1920x1080。"
+    )]
+    pub size: String,
+
+    /// 未安装 ffmpeg 时下载静态构建
+    #[arg(
+        long,
+        help = "未安装 ffmpeg 时自动下载静态构建",
+        long_help = "未检测到 ffmpeg 时，下载适用于当前系统的 ffmpeg 静态构建到应用数据目录并使用，而不是通过 winget 安装。"
+    )]
+    pub download_ffmpeg: bool,
+}
+
+/// 单个编码器的测试结果
+struct BenchResult {
+    /// 编码器选项
+    encoder: Av1Encoder,
+    /// ffmpeg 编码器名称
+    ffmpeg_name: String,
+    /// 编码耗时
+    elapsed: Duration,
+    /// 输出文件大小（字节）
+    size: u64,
+}
+
+/// 使用指定编码器进行一次测试编码
+///
+/// # 参数
+///
+/// * `encoder` - ffmpeg 编码器名称
+/// * `args` - 命令行参数（输入源、时长、分辨率）
+/// * `output_path` - 临时输出文件路径
+///
+/// # 返回值
+///
+/// * `Ok(Duration)` - 编码耗时
+/// * `Err(anyhow::Error)` - 编码失败
+async fn bench_encoder(encoder: &str, args: &Av1BenchArgs, output_path: &Path) -> Result<Duration> {
+    let mut cmd = Command::new(find_ffmpeg());
+    cmd.arg("-hide_banner").arg("-loglevel").arg("error");
+
+    match &args.input {
+        Some(input) => {
+            cmd.arg("-i").arg(input);
+        }
+        None => {
+            cmd.arg("-f").arg("lavfi").arg("-i").arg(format!(
+                "testsrc2=duration={}:size={}:rate=30",
+                args.duration, args.size
+            ));
+        }
+    }
+
+    cmd.arg("-t")
+        .arg(args.duration.to_string())
+        .arg("-an")
+        .arg("-threads")
+        .arg("0")
+        .arg("-c:v")
+        .arg(encoder)
+        .arg("-crf")
+        .arg("25")
+        .arg("-y")
+        .arg(output_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit());
+
+    let start = Instant::now();
+    let status = cmd
+        .status()
+        .await
+        .with_context(|| tr!("启动 ffmpeg 失败: {}", encoder))?;
+
+    if !status.success() {
+        anyhow::bail!(tr!("测试编码失败: {}", encoder));
+    }
+
+    Ok(start.elapsed())
+}
+
+/// 执行 AV1 编码器基准测试命令
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回
+///
+/// 执行成功返回 `Ok(())`,失败返回错误信息
+///
+/// # 错误
+///
+/// - 当输入视频不存在时返回错误
+/// - 当没有任何可用的 AV1 编码器时返回错误
+pub async fn run(args: Av1BenchArgs) -> Result<()> {
+    // 确保 ffmpeg 可用
+    ensure_ffmpeg(args.download_ffmpeg)?;
+
+    if let Some(input) = &args.input
+        && !input.is_file()
+    {
+        anyhow::bail!(tr!("输入视频不存在: {}", input.display()));
+    }
+
+    println!(
+        "{}",
+        tr!("{} AV1 编码器基准测试 {}", "=".repeat(15), "=".repeat(15))
+    );
+    match &args.input {
+        Some(input) => println!(
+            "{}",
+            tr!("输入: {} (前 {} 秒)", input.display(), args.duration)
+        ),
+        None => println!(
+            "{}",
+            tr!("输入: testsrc2 {} {} 秒", args.size, args.duration)
+        ),
+    }
+    println!("{}", tr!("编码质量: CRF=25"));
+    println!();
+
+    let mut results = Vec::new();
+
+    for encoder in Av1Encoder::ALL {
+        // 找到该选项下第一个可用的 ffmpeg 编码器
+        let Some(ffmpeg_name) = encoder
+            .ffmpeg_names()
+            .iter()
+            .find(|name| test_encoder(name))
+        else {
+            println!("{}", tr!("{}: 不可用", encoder.name()));
+            continue;
+        };
+
+        println!(
+            "{}",
+            tr!("{}: 正在测试 {} ...", encoder.name(), ffmpeg_name)
+        );
+
+        let output = TempPath::file("av1-bench", "mkv")?;
+        let result = bench_encoder(ffmpeg_name, &args, output.path()).await;
+        let size = std::fs::metadata(output.path())
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        stats::add(Counter::Scanned, 1);
+        match result {
+            Ok(elapsed) => {
+                stats::add(Counter::Processed, 1);
+                stats::add(Counter::BytesOut, size);
+                results.push(BenchResult {
+                    encoder,
+                    ffmpeg_name: ffmpeg_name.to_string(),
+                    elapsed,
+                    size,
+                });
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                println!("{}: {:#}", encoder.name(), e);
+            }
+        }
+    }
+
+    println!();
+    println!("{}", tr!("{} 测试结果 {}", "=".repeat(20), "=".repeat(20)));
+
+    if results.is_empty() {
+        return Err(ScriptError::new(
+            ErrorKind::Dependency,
+            tr!("未找到可用的 AV1 编码器，请检查硬件驱动或安装支持 AV1 的 ffmpeg"),
+        )
+        .into());
+    }
+
+    for result in &results {
+        let seconds = result.elapsed.as_secs_f64();
+        println!(
+            "{}",
+            tr!(
+                "{} {} 耗时: {}s  速度: {}x  大小: {}",
+                format!("{:<6}", result.encoder.name()),
+                format!("{:<12}", result.ffmpeg_name),
+                format!("{:>7.2}", seconds),
+                format!("{:>6.2}", args.duration as f64 / seconds),
+                ByteSize(result.size)
+            )
+        );
+    }
+
+    // 推荐最快的编码器
+    if let Some(fastest) = results.iter().min_by_key(|r| r.elapsed) {
+        println!();
+        println!(
+            "{}",
+            tr!(
+                "最快的编码器: {} (video-transcode --encoder {})",
+                fastest.ffmpeg_name,
+                fastest.encoder.name()
+            )
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,328 @@
+//! # 目录备份工具 (backup)
+//!
+//! 将目录打包为 7z 快照（可选密码加密），以时间戳和内容哈希命名后保存到目标目录，
+//! 并按保留策略（保留最近 N 份 / 最近 N 天）将过期的快照移动到回收站。
+
+use crate::utils::compress::{CompressOptions, compress_7z};
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::filesystem::path_size;
+use crate::utils::hash::calculate_file_hash;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::secrets::resolve_secret;
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use clap::Args;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// 快照文件名中的时间戳格式
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// 快照文件名中保留的哈希长度
+const HASH_LENGTH: usize = 8;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "backup")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "将目录备份为带时间戳和哈希的 7z 快照",
+    long_about = "将源目录压缩为 7z 快照，命名为 <目录名>-<时间戳>-<哈希>.7z 并保存到目标目录。可选密码加密（支持 keyring: 引用），并可按保留策略将过期的快照移动到回收站。"
+)]
+pub struct BackupArgs {
+    /// 要备份的目录
+    #[arg(
+        short = 's',
+        long,
+        value_name = "SOURCE",
+        help = "要备份的目录",
+        long_help = "要备份的源目录，整个目录会被压缩为一个快照。"
+    )]
+    pub source: PathBuf,
+
+    /// 快照保存目录
+    #[arg(
+        short = 't',
+        long,
+        value_name = "TARGET",
+        help = "快照保存目录",
+        long_help = "快照文件保存的目录，不存在时自动创建。保留策略只处理该目录中属于同一源目录的快照。"
+    )]
+    pub target: PathBuf,
+
+    /// 快照密码
+    #[arg(
+        short = 'p',
+        long,
+        value_name = "PASSWORD",
+        help = "快照密码",
+        long_help = "启用后同时加密文件内容和文件名（-mhe=on）。支持 keyring:<名称> 引用 `scripts secrets set` 保存的密码。"
+    )]
+    pub password: Option<String>,
+
+    /// 保留最近 N 份快照
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "保留最近 N 份快照",
+        long_help = "备份完成后只保留最近的 N 份快照，更早的快照移动到回收站。与 --keep-days 同时使用时，满足任一条件的快照都会保留。"
+    )]
+    pub keep: Option<u64>,
+
+    /// 保留最近 N 天的快照
+    #[arg(
+        long,
+        value_name = "DAYS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "保留最近 N 天的快照",
+        long_help = "备份完成后将早于 N 天的快照移动到回收站。与 --keep 同时使用时，满足任一条件的快照都会保留。"
+    )]
+    pub keep_days: Option<u32>,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际备份和清理",
+        long_help = "列出将要创建的快照，以及按保留策略将被移动到回收站的快照后退出，不修改任何文件。"
+    )]
+    pub dry_run: bool,
+
+    /// 跳过删除确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除确认",
+        long_help = "应用保留策略时默认会列出将被删除的快照并要求确认，开启后跳过确认直接执行，适合计划任务。"
+    )]
+    pub yes: bool,
+}
+
+/// 已存在的快照
+#[derive(Debug)]
+struct Snapshot {
+    /// 快照文件路径
+    path: PathBuf,
+    /// 快照创建时间
+    created: NaiveDateTime,
+}
+
+/// 列出目标目录中属于指定源目录的快照
+///
+/// 只匹配 `<名称>-<时间戳>-<哈希>.7z` 格式的文件，按创建时间从新到旧排序。
+///
+/// # 参数
+///
+/// * `target_dir` - 快照保存目录
+/// * `name` - 源目录名称
+///
+/// # 返回值
+///
+/// * `Ok(Vec<Snapshot>)` - 快照列表
+/// * `Err(anyhow::Error)` - 读取目录失败
+fn list_snapshots(target_dir: &Path, name: &str) -> Result<Vec<Snapshot>> {
+    let pattern = Regex::new(&format!(
+        r"^{}-(\d{{8}}-\d{{6}})-[1-9A-HJ-NP-Za-km-z]+\.7z$",
+        regex::escape(name)
+    ))?;
+
+    let mut snapshots: Vec<Snapshot> = std::fs::read_dir(target_dir)
+        .with_context(|| tr!("无法读取目录: {}", target_dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let captures = pattern.captures(&file_name)?;
+            let created = NaiveDateTime::parse_from_str(&captures[1], TIMESTAMP_FORMAT).ok()?;
+            Some(Snapshot {
+                path: entry.path(),
+                created,
+            })
+        })
+        .collect();
+
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created));
+    Ok(snapshots)
+}
+
+/// 根据保留策略筛选过期的快照
+///
+/// 快照满足任一保留条件即保留；未设置任何策略时不删除。
+fn expired_snapshots(
+    snapshots: &[Snapshot],
+    keep: Option<u64>,
+    keep_days: Option<u32>,
+) -> Vec<PathBuf> {
+    if keep.is_none() && keep_days.is_none() {
+        return Vec::new();
+    }
+
+    // 天数过大导致日期溢出时没有截止时间，所有快照都按时间保留
+    let cutoff = keep_days.map(|days| {
+        TimeDelta::try_days(days.into())
+            .and_then(|delta| Local::now().naive_local().checked_sub_signed(delta))
+    });
+
+    snapshots
+        .iter()
+        .enumerate()
+        .filter(|(index, snapshot)| {
+            let kept_by_count = keep.is_some_and(|n| (*index as u64) < n);
+            let kept_by_age =
+                cutoff.is_some_and(|cutoff| cutoff.is_none_or(|cutoff| snapshot.created >= cutoff));
+            !kept_by_count && !kept_by_age
+        })
+        .map(|(_, snapshot)| snapshot.path.clone())
+        .collect()
+}
+
+/// 命令执行函数
+///
+/// 1. 将源目录压缩为临时快照文件
+/// 2. 计算快照哈希并重命名为 `<名称>-<时间戳>-<哈希>.7z`
+/// 3. 按保留策略将过期的快照移动到回收站
+///
+/// 指定 `--dry-run` 时只输出执行计划。
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 备份成功
+/// * `Err(anyhow::Error)` - 备份失败
+pub async fn run(args: BackupArgs) -> Result<()> {
+    let source = args
+        .source
+        .canonicalize()
+        .with_context(|| tr!("无法访问源目录: {}", args.source.display()))?;
+
+    if !source.is_dir() {
+        anyhow::bail!(tr!("源路径必须是目录: {}", source.display()));
+    }
+
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .with_context(|| tr!("无法确定源目录名称"))?;
+
+    // 解析密码中的密钥环引用
+    let password = args.password.as_deref().map(resolve_secret).transpose()?;
+
+    if !args.target.exists() && !args.dry_run {
+        tokio::fs::create_dir_all(&args.target)
+            .await
+            .with_context(|| tr!("创建目录失败: {}", args.target.display()))?;
+    }
+
+    println!(
+        "{}",
+        tr!("{} 目录备份工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", source.display()));
+    println!("{}", tr!("保存目录: {}", args.target.display()));
+    if password.is_some() {
+        println!("{}", tr!("加密模式: 已启用(加密文件内容和文件名)"));
+    }
+    println!();
+
+    let now = Local::now();
+    let timestamp = now.format(TIMESTAMP_FORMAT).to_string();
+
+    // dry-run 模式只输出执行计划，新快照按最新的一份参与保留策略
+    if args.dry_run {
+        let planned = args.target.join(format!("{}-{}.7z", name, timestamp));
+        let mut snapshots = if args.target.exists() {
+            list_snapshots(&args.target, &name)?
+        } else {
+            Vec::new()
+        };
+        snapshots.insert(
+            0,
+            Snapshot {
+                path: planned.clone(),
+                created: now.naive_local(),
+            },
+        );
+        let mut operations = vec![Operation::new(
+            OperationKind::Compress,
+            &source,
+            Some(&planned),
+        )];
+        operations.extend(
+            expired_snapshots(&snapshots, args.keep, args.keep_days)
+                .iter()
+                .map(|path| Operation::new(OperationKind::Trash, path, None)),
+        );
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    // 先压缩到快照旁的临时文件，计算哈希后再重命名
+    let temp = TempPath::beside(&args.target.join(format!("{}-{}.7z", name, timestamp)))?;
+    compress_7z(
+        &source,
+        temp.path(),
+        password.as_deref(),
+        CompressOptions::default(),
+    )
+    .await?;
+
+    let hash = calculate_file_hash(temp.path()).await?;
+    let short_hash: String = hash.chars().take(HASH_LENGTH).collect();
+    let snapshot_path = args
+        .target
+        .join(format!("{}-{}-{}.7z", name, timestamp, short_hash));
+    // 同一秒内备份相同内容会得到相同的文件名，不覆盖已有快照
+    if snapshot_path.exists() {
+        anyhow::bail!(tr!("快照已存在: {}", snapshot_path.display()));
+    }
+    temp.persist(&snapshot_path)?;
+
+    let size = std::fs::metadata(&snapshot_path)
+        .map(|m| m.len())
+        .unwrap_or(0);
+    stats::add(Counter::Processed, 1);
+    stats::add(Counter::BytesIn, path_size(&source));
+    stats::add(Counter::BytesOut, size);
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "快照已保存: {} ({})",
+            snapshot_path.display(),
+            ByteSize(size)
+        )
+    );
+
+    // 应用保留策略
+    let snapshots = list_snapshots(&args.target, &name)?;
+    let expired = expired_snapshots(&snapshots, args.keep, args.keep_days);
+    if expired.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    if !confirm_destructive("移动到回收站", &expired, args.yes)? {
+        return Ok(());
+    }
+
+    for path in expired {
+        match trash::delete(&path) {
+            Ok(_) => {
+                stats::add(Counter::Deleted, 1);
+                println!("{}", tr!("✓ 已将过期快照移动到回收站: {}", path.display()));
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                println!("{}", tr!("✗ 移动到回收站失败: {} - {}", path.display(), e));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -3,13 +3,26 @@
 //! 一个简洁高效的 Rust 命令行工具，用于批量压缩指定目录下的文件和子目录，
 //! 支持密码加密和可选的删除原始文件功能。
 
-use crate::utils::compress::compress_7z;
-use crate::utils::filesystem::get_file_extension;
+use crate::utils::cancel::register_cleanup;
+use crate::utils::compress::{CompressOptions, compress_7z, find_7z, first_volume_path};
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::filesystem::{get_file_extension, path_size};
+use crate::utils::hash::calculate_file_hash;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::secrets::resolve_secret;
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
 use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use chrono::{DateTime, Local};
 use clap::Args;
 use std::path::{Path, PathBuf};
 use trash;
 
+/// `{hash8}` 占位符使用的哈希长度
+const HASH_LENGTH: usize = 8;
+
 /// 命令行参数结构体
 ///
 /// 使用 clap 的 Args API 自动解析命令行参数，
@@ -41,16 +54,55 @@ pub struct BatchCompressArgs {
     ///
     /// 为压缩文件设置密码保护。
     /// 启用后将同时加密文件内容和文件名(使用 -mhe=on 选项)。
+    /// 支持 `keyring:<名称>` 引用系统密钥环中保存的密码。
     /// 如果不指定此参数,则不使用密码加密。
     #[arg(
         short = 'p',
         long,
         value_name = "PASSWORD",
         help = "压缩文件密码",
-        long_help = "启用后同时加密文件内容和文件名（-mhe=on）。不指定则不加密。"
+        long_help = "启用后同时加密文件内容和文件名（-mhe=on）。支持 keyring:<名称> 引用 `scripts secrets set` 保存的密码。不指定则不加密。"
     )]
     pub password: Option<String>,
 
+    /// 压缩级别
+    ///
+    /// 对应 7-Zip 的 `-mx` 参数，0 为仅存储，9 为极限压缩。
+    #[arg(
+        short = 'l',
+        long,
+        value_name = "LEVEL",
+        value_parser = clap::value_parser!(u8).range(0..=9),
+        help = "压缩级别 (0-9)",
+        long_help = "对应 7-Zip 的 -mx 参数：0 仅存储不压缩，1 最快，5 标准（7-Zip 默认），7 最大，9 极限。已压缩的视频、图片使用 0 或 1 可大幅缩短耗时。"
+    )]
+    pub level: Option<u8>,
+
+    /// 分卷大小
+    ///
+    /// 对应 7-Zip 的 `-v` 参数，输出 `<名称>.7z.001`、`<名称>.7z.002` ...
+    #[arg(
+        short = 'v',
+        long,
+        value_name = "SIZE",
+        help = "按指定大小分卷",
+        long_help = "按指定大小将压缩包分为多个分卷，输出 <名称>.7z.001、<名称>.7z.002 ...，例如 4g（FAT32 单文件上限）、700m、100MiB。解压时对第一个分卷执行 extract 即可。"
+    )]
+    pub volume: Option<ByteSize>,
+
+    /// 压缩包命名模板
+    ///
+    /// 生成压缩包文件名（不含 `.7z` 扩展名），默认 `{name}`。
+    #[arg(
+        short = 'n',
+        long,
+        value_name = "TEMPLATE",
+        default_value = "{name}",
+        help = "压缩包命名模板",
+        long_help = "生成压缩包文件名（不含 .7z 扩展名）。可用占位符：{name} 原项目名称、{date} 当前日期 (YYYYMMDD)、{time} 当前时间 (HHMMSS)、{hash8} 压缩包 Blake3 哈希（Base58）的前 8 位。例如 \"{name}-{date}-{hash8}\" 使同一目录的多次备份互不冲突。{hash8} 不能与 --volume 同时使用。"
+    )]
+    pub name_template: String,
+
     /// 压缩完成后删除原始文件
     ///
     /// 启用此选项后，压缩成功后将自动将原始文件移动到回收站。
@@ -62,6 +114,28 @@ pub struct BatchCompressArgs {
         long_help = "启用后，压缩成功将自动将原始文件移动到回收站。默认不启用。"
     )]
     pub delete: bool,
+
+    /// 跳过删除确认
+    ///
+    /// 启用删除时，默认会先列出将被删除的项目并要求确认。
+    /// 启用此选项后直接执行，适合脚本和计划任务。
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除确认",
+        long_help = "启用 --delete 时默认会列出将被删除的项目并要求确认，开启后跳过确认直接执行，适合自动化场景。"
+    )]
+    pub yes: bool,
+
+    /// 仅输出执行计划
+    ///
+    /// 列出将要执行的压缩和删除操作（含大小），但不实际执行。
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际执行",
+        long_help = "列出将要执行的每个压缩和删除操作及其大小，但不调用 7-Zip，也不删除任何文件。"
+    )]
+    pub dry_run: bool,
 }
 
 /// 收集要处理的项目
@@ -91,7 +165,7 @@ pub fn collect_items(work_directory: &Path) -> Result<Vec<PathBuf>> {
 
     // 使用 std::fs::read_dir 读取目录项，只遍历首层
     let items: Vec<PathBuf> = std::fs::read_dir(work_directory)
-        .with_context(|| format!("无法读取目录: {}", work_directory.display()))?
+        .with_context(|| tr!("无法读取目录: {}", work_directory.display()))?
         .filter_map(|entry| entry.ok()) // 忽略读取错误的项
         .map(|entry| entry.path())
         .filter(|path| {
@@ -106,6 +180,11 @@ pub fn collect_items(work_directory: &Path) -> Result<Vec<PathBuf>> {
                 return false;
             }
 
+            // 跳过 7z 分卷（<名称>.7z.001 等）
+            if is_volume_name(file_name) {
+                return false;
+            }
+
             // 跳过特定扩展名的文件（不带点，小写）
             let ext = get_file_extension(path);
             if !ext.is_empty() && skip_extensions.contains(&ext.as_str()) {
@@ -119,12 +198,86 @@ pub fn collect_items(work_directory: &Path) -> Result<Vec<PathBuf>> {
     Ok(items)
 }
 
+/// 判断文件名是否为 7z 分卷，例如 `photos.7z.001`
+fn is_volume_name(file_name: &str) -> bool {
+    match file_name.rsplit_once('.') {
+        Some((stem, number)) => {
+            stem.to_lowercase().ends_with(".7z")
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// 压缩包命名模板
+#[derive(Debug, Clone)]
+pub struct ArchiveNaming {
+    /// 文件名模板，不含 `.7z` 扩展名
+    pub template: String,
+    /// 本次运行的开始时间，所有项目使用同一时间
+    pub now: DateTime<Local>,
+}
+
+impl ArchiveNaming {
+    /// 模板是否包含压缩完成后才能确定的 `{hash8}`
+    fn needs_hash(&self) -> bool {
+        self.template.contains("{hash8}")
+    }
+
+    /// 生成压缩包文件名（含 `.7z` 扩展名）
+    ///
+    /// # 参数
+    ///
+    /// * `name` - 原项目名称
+    /// * `hash` - 压缩包哈希，模板不含 `{hash8}` 时为 `None`
+    fn file_name(&self, name: &str, hash: Option<&str>) -> String {
+        let hash8: String = hash.unwrap_or_default().chars().take(HASH_LENGTH).collect();
+        let stem = self
+            .template
+            .replace("{name}", name)
+            .replace("{date}", &self.now.format("%Y%m%d").to_string())
+            .replace("{time}", &self.now.format("%H%M%S").to_string())
+            .replace("{hash8}", &hash8);
+        format!("{}.7z", stem)
+    }
+}
+
+/// 获取项目对应的压缩包路径，分卷时为第一个分卷的路径
+///
+/// 该路径已存在时视为已压缩，跳过该项目。
+///
+/// # 返回值
+///
+/// * `Some(PathBuf)` - 压缩包路径
+/// * `None` - 模板包含 `{hash8}`，压缩完成前无法确定路径
+fn archive_path(
+    item_path: &Path,
+    work_directory: &Path,
+    options: CompressOptions,
+    naming: &ArchiveNaming,
+) -> Option<PathBuf> {
+    if naming.needs_hash() {
+        return None;
+    }
+    let name = item_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let output_path = work_directory.join(naming.file_name(&name, None));
+    if options.volume_size.is_some() {
+        Some(first_volume_path(&output_path))
+    } else {
+        Some(output_path)
+    }
+}
+
 /// 处理单个项目
 ///
 /// 对单个文件或目录执行完整的压缩和删除流程:
-/// 1. 生成同名的 .7z 压缩文件路径
+/// 1. 按命名模板生成 .7z 压缩文件路径
 /// 2. 检查压缩文件是否已存在,存在则跳过
-/// 3. 使用 7-Zip 压缩项目
+/// 3. 使用 7-Zip 压缩项目(模板包含 `{hash8}` 时先压缩到临时文件,按哈希重命名)
 /// 4. 压缩成功后删除原始项目
 ///
 /// # 参数
@@ -132,6 +285,9 @@ pub fn collect_items(work_directory: &Path) -> Result<Vec<PathBuf>> {
 /// * `item_path` - 要处理的文件或目录路径
 /// * `work_directory` - 工作目录路径(用于存放压缩文件)
 /// * `password` - 可选的压缩文件密码
+/// * `delete` - 压缩完成后是否将原始项目移动到回收站
+/// * `options` - 压缩级别和分卷选项
+/// * `naming` - 压缩包命名模板
 ///
 /// # 返回值
 ///
@@ -142,52 +298,79 @@ pub async fn process_item(
     work_directory: &Path,
     password: Option<&str>,
     delete: bool,
+    options: CompressOptions,
+    naming: &ArchiveNaming,
 ) -> Result<()> {
     // 提取项目名称用于显示和生成输出文件名
     let item_name = item_path
         .file_name()
         .and_then(|n| n.to_str())
-        .context("无效的项目名称")?;
+        .with_context(|| tr!("无效的项目名称"))?;
 
-    println!("处理: {}", item_name);
+    println!("{}", tr!("处理: {}", item_name));
 
-    // 生成输出路径，压缩文件与原始项目同名，扩展名为 .7z
-    let output_path = work_directory.join(format!("{}.7z", item_name));
+    let archive_name = match archive_path(item_path, work_directory, options, naming) {
+        Some(archive) => {
+            let archive_name = archive.file_name().unwrap().to_string_lossy().to_string();
 
-    // 检查压缩文件是否已存在，避免重复处理
-    if output_path.exists() {
-        println!(
-            "压缩文件已存在: {}",
-            output_path.file_name().unwrap().to_string_lossy()
-        );
-        return Ok(());
-    }
+            // 检查压缩文件（分卷时为第一个分卷）是否已存在，避免重复处理
+            if archive.exists() {
+                println!("{}", tr!("压缩文件已存在: {}", archive_name));
+                return Ok(());
+            }
+
+            // 使用 7-Zip 压缩项目，中断时删除未完成的压缩文件
+            // （分卷时删除第一个分卷，下次运行不会误认为已压缩）
+            let output_path = work_directory.join(naming.file_name(item_name, None));
+            let cleanup = register_cleanup(&archive);
+            compress_7z(item_path, &output_path, password, options).await?;
+            drop(cleanup);
+            archive_name
+        }
+        None => {
+            // 文件名包含哈希：先压缩到临时文件，计算哈希后再重命名
+            let temp = TempPath::beside(&work_directory.join(format!("{}.7z", item_name)))?;
+            compress_7z(item_path, temp.path(), password, options).await?;
+            let hash = calculate_file_hash(temp.path()).await?;
+            let archive_name = naming.file_name(item_name, Some(&hash));
+            let archive = work_directory.join(&archive_name);
 
-    // 使用 7-Zip 压缩项目
-    compress_7z(item_path, &output_path, password).await;
+            // 同名即内容相同，丢弃临时文件，保留原始项目
+            if archive.exists() {
+                println!("{}", tr!("相同内容的压缩文件已存在: {}", archive_name));
+                return Ok(());
+            }
+            temp.persist(&archive)?;
+            archive_name
+        }
+    };
 
     // 根据是否使用密码显示不同的提示信息
     if password.is_some() {
         println!(
-            "压缩完成(已加密): {} -> {}",
-            item_name,
-            output_path.file_name().unwrap().to_string_lossy()
+            "{}",
+            tr!("压缩完成(已加密): {} -> {}", item_name, archive_name)
         );
     } else {
-        println!(
-            "压缩完成: {} -> {}",
-            item_name,
-            output_path.file_name().unwrap().to_string_lossy()
-        );
+        println!("{}", tr!("压缩完成: {} -> {}", item_name, archive_name));
     }
 
+    // 分卷时写入字节数只计入第一个分卷
+    stats::add(Counter::Processed, 1);
+    stats::add(Counter::BytesIn, path_size(item_path));
+    stats::add(
+        Counter::BytesOut,
+        path_size(work_directory.join(&archive_name)),
+    );
+
     // 如果启用了删除选项，将原始项目移动到回收站
     if delete {
         trash::delete(item_path)
-            .with_context(|| format!("无法将原始项目移动到回收站: {}", item_path.display()))?;
-        println!("已将原始项目移动到回收站: {}", item_name);
+            .with_context(|| tr!("无法将原始项目移动到回收站: {}", item_path.display()))?;
+        stats::add(Counter::Deleted, 1);
+        println!("{}", tr!("已将原始项目移动到回收站: {}", item_name));
     } else {
-        println!("保留原始项目: {}", item_name);
+        println!("{}", tr!("保留原始项目: {}", item_name));
     }
 
     Ok(())
@@ -215,51 +398,127 @@ pub async fn run(args: BatchCompressArgs) -> anyhow::Result<()> {
     let work_directory = args
         .source
         .canonicalize()
-        .with_context(|| format!("无法访问源目录: {}", args.source.display()))?;
+        .with_context(|| tr!("无法访问源目录: {}", args.source.display()))?;
+
+    // 解析密码中的密钥环引用
+    let password = args.password.as_deref().map(resolve_secret).transpose()?;
 
     // 显示程序标题和源目录信息
-    println!("{} 批量压缩工具 {}", "=".repeat(15), "=".repeat(15));
-    println!("源目录: {}", work_directory.display());
+    println!(
+        "{}",
+        tr!("{} 批量压缩工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", work_directory.display()));
 
     // 显示密码设置状态
-    if args.password.is_some() {
-        println!("加密模式: 已启用(加密文件内容和文件名)");
+    if password.is_some() {
+        println!("{}", tr!("加密模式: 已启用(加密文件内容和文件名)"));
     } else {
-        println!("加密模式: 未启用");
+        println!("{}", tr!("加密模式: 未启用"));
+    }
+
+    let options = CompressOptions {
+        level: args.level,
+        volume_size: args.volume.map(|size| size.as_u64()),
+    };
+    let naming = ArchiveNaming {
+        template: args.name_template.clone(),
+        now: Local::now(),
+    };
+    if naming.template.contains(['/', '\\']) {
+        anyhow::bail!(tr!("命名模板不能包含路径分隔符: {}", naming.template));
+    }
+    if naming.needs_hash() && options.volume_size.is_some() {
+        anyhow::bail!(tr!("{hash8} 不能与 --volume 同时使用"));
+    }
+    if naming.template != "{name}" {
+        println!("{}", tr!("命名模板: {}.7z", naming.template));
+    }
+    if let Some(level) = options.level {
+        println!("{}", tr!("压缩级别: {}", level));
+    }
+    if let Some(volume) = args.volume {
+        println!("{}", tr!("分卷大小: {}", volume));
     }
 
     // 显示删除选项状态
     if args.delete {
-        println!("删除原始文件: 已启用");
+        println!("{}", tr!("删除原始文件: 已启用"));
     } else {
-        println!("删除原始文件: 未启用");
+        println!("{}", tr!("删除原始文件: 未启用"));
     }
     println!();
 
     // 收集要处理的项目（应用过滤规则）
     let items = collect_items(&work_directory)?;
+    stats::add(Counter::Scanned, items.len() as u64);
 
     // 如果没有找到项目，直接返回
     if items.is_empty() {
-        println!("没有找到要处理的项目");
+        println!("{}", tr!("没有找到要处理的项目"));
+        return Ok(());
+    }
+
+    println!("{}", tr!("找到 {} 个项目要处理\n", items.len()));
+
+    // 压缩文件已存在的项目会被跳过,不会被压缩或删除
+    let pending: Vec<(PathBuf, PathBuf)> = items
+        .iter()
+        .filter_map(
+            |item| match archive_path(item, &work_directory, options, &naming) {
+                Some(output_path) => (!output_path.exists()).then(|| (item.clone(), output_path)),
+                // 文件名包含哈希时无法预先判断，计划中使用默认名称
+                None => Some((
+                    item.clone(),
+                    work_directory.join(format!("{}.7z", item.file_name()?.to_string_lossy())),
+                )),
+            },
+        )
+        .collect();
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        let mut operations = Vec::new();
+        for (item, output_path) in &pending {
+            operations.push(Operation::new(
+                OperationKind::Compress,
+                item,
+                Some(output_path),
+            ));
+            if args.delete {
+                operations.push(Operation::new(OperationKind::Trash, item, None));
+            }
+        }
+        print_plan(&operations);
         return Ok(());
     }
 
-    println!("找到 {} 个项目要处理\n", items.len());
+    // 确认和处理之前检查 7-Zip，缺少时以退出码 7 退出
+    find_7z()?;
+
+    // 启用删除时,确认将被删除的项目
+    if args.delete {
+        let to_delete: Vec<PathBuf> = pending.iter().map(|(item, _)| item.clone()).collect();
+        if !confirm_destructive("压缩后移动到回收站", &to_delete, args.yes)? {
+            return Ok(());
+        }
+    }
 
     // 逐个处理项目，遇到失败直接返回错误
     for item in items {
         process_item(
             &item,
             &work_directory,
-            args.password.as_deref(),
+            password.as_deref(),
             args.delete,
+            options,
+            &naming,
         )
         .await
-        .with_context(|| format!("处理 {} 失败", item.display()))?;
+        .with_context(|| tr!("处理 {} 失败", item.display()))?;
     }
 
     // 显示完成信息
-    println!("操作成功完成！");
+    println!("{}", tr!("操作成功完成！"));
     Ok(())
 }
@@ -0,0 +1,217 @@
+//! # 旧文件清理工具 (clean_old)
+//!
+//! 递归扫描目录，将超过指定天数或超出保留数量的旧文件移动到回收站，
+//! 适合清理下载目录、构建缓存和日志目录。
+//!
+//! ## 清理规则
+//!
+//! - `--days N`: 修改时间早于 N 天前的文件视为过期
+//! - `--keep N`: 按修改时间保留最新的 N 个文件，其余视为过期
+//! - 同时设置时，只有两个条件都不满足保留要求的文件才会被清理
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::filesystem::build_globset;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "clean-old")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "将旧文件移动到回收站",
+    long_about = "递归扫描目录，将修改时间早于 --days 天前的文件，或按修改时间排在最新 --keep 个之外的文件移动到回收站。同时设置两者时，只清理两个条件都不满足保留要求的文件。可使用 --include/--exclude 按 glob 模式筛选文件。"
+)]
+pub struct CleanOldArgs {
+    /// 要清理的目录
+    #[arg(
+        short = 'd',
+        long,
+        value_name = "DIR",
+        help = "要清理的目录",
+        long_help = "要清理的目录，会递归扫描其中的所有文件。"
+    )]
+    pub dir: PathBuf,
+
+    /// 保留最近 N 天内修改的文件
+    #[arg(
+        long,
+        value_name = "N",
+        help = "清理 N 天前修改的文件",
+        long_help = "修改时间早于 N 天前的文件会被清理。"
+    )]
+    pub days: Option<u64>,
+
+    /// 保留最新的 N 个文件
+    #[arg(
+        long,
+        value_name = "N",
+        help = "只保留最新的 N 个文件",
+        long_help = "按修改时间排序，只保留最新的 N 个匹配文件，其余文件会被清理。"
+    )]
+    pub keep: Option<usize>,
+
+    /// 包含的文件模式
+    #[arg(
+        short = 'i',
+        long,
+        value_name = "GLOB",
+        value_delimiter = ',',
+        help = "仅处理匹配的文件（glob，逗号分隔）",
+        long_help = "仅处理相对路径匹配任一 glob 模式的文件，例如 \"*.log,*.tmp\"。`*` 可跨目录匹配。"
+    )]
+    pub include: Vec<String>,
+
+    /// 排除的文件模式
+    #[arg(
+        short = 'e',
+        long,
+        value_name = "GLOB",
+        value_delimiter = ',',
+        help = "排除匹配的文件（glob，逗号分隔）",
+        long_help = "跳过相对路径匹配任一 glob 模式的文件，例如 \"important/**\"。排除优先于包含。"
+    )]
+    pub exclude: Vec<String>,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际删除",
+        long_help = "列出将被移动到回收站的文件及其大小，但不实际删除。"
+    )]
+    pub dry_run: bool,
+
+    /// 跳过删除确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除确认",
+        long_help = "默认会列出将被删除的文件并要求确认，开启后跳过确认直接执行，适合自动化场景。"
+    )]
+    pub yes: bool,
+}
+
+/// 命令执行函数
+///
+/// 1. 递归收集匹配 include/exclude 规则的文件及其修改时间
+/// 2. 按修改时间从新到旧排序，根据 `--days` 和 `--keep` 筛选过期文件
+/// 3. 输出计划或确认后移动到回收站
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 清理完成
+/// * `Err(anyhow::Error)` - 参数无效或目录无法访问
+pub async fn run(args: CleanOldArgs) -> Result<()> {
+    if args.days.is_none() && args.keep.is_none() {
+        anyhow::bail!(tr!("必须至少指定 --days 或 --keep 之一"));
+    }
+
+    let dir = args
+        .dir
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", args.dir.display()))?;
+
+    if !dir.is_dir() {
+        anyhow::bail!(tr!("路径必须是目录: {}", dir.display()));
+    }
+
+    let include = build_globset(&args.include)?;
+    let exclude = build_globset(&args.exclude)?;
+
+    println!(
+        "{}",
+        tr!("{} 旧文件清理工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("目录: {}", dir.display()));
+    if let Some(days) = args.days {
+        println!("{}", tr!("保留天数: {}", days));
+    }
+    if let Some(keep) = args.keep {
+        println!("{}", tr!("保留数量: {}", keep));
+    }
+    println!();
+
+    // 收集匹配的文件及其修改时间
+    let mut files: Vec<(PathBuf, SystemTime)> = WalkDir::new(&dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let relative = e.path().strip_prefix(&dir).unwrap_or(e.path());
+            let included = include.as_ref().is_none_or(|set| set.is_match(relative));
+            let excluded = exclude.as_ref().is_some_and(|set| set.is_match(relative));
+            included && !excluded
+        })
+        .filter_map(|e| {
+            let modified = e.metadata().ok()?.modified().ok()?;
+            Some((e.into_path(), modified))
+        })
+        .collect();
+
+    stats::add(Counter::Scanned, files.len() as u64);
+
+    // 按修改时间从新到旧排序
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    // 天数过大导致时间溢出时没有截止时间，所有文件都视为未过期
+    let cutoff = args.days.map(|days| {
+        days.checked_mul(24 * 60 * 60)
+            .and_then(|secs| SystemTime::now().checked_sub(Duration::from_secs(secs)))
+    });
+
+    let expired: Vec<PathBuf> = files
+        .into_iter()
+        .enumerate()
+        .filter(|(index, (_, modified))| {
+            let kept_by_count = args.keep.is_some_and(|n| *index < n);
+            let kept_by_age =
+                cutoff.is_some_and(|cutoff| cutoff.is_none_or(|cutoff| *modified >= cutoff));
+            !kept_by_count && !kept_by_age
+        })
+        .map(|(_, (path, _))| path)
+        .collect();
+
+    if expired.is_empty() {
+        println!("{}", tr!("没有需要清理的文件"));
+        return Ok(());
+    }
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        let operations: Vec<Operation> = expired
+            .iter()
+            .map(|path| Operation::new(OperationKind::Trash, path, None))
+            .collect();
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    if !confirm_destructive("移动到回收站", &expired, args.yes)? {
+        return Ok(());
+    }
+
+    for path in expired {
+        match trash::delete(&path) {
+            Ok(_) => {
+                stats::add(Counter::Deleted, 1);
+                println!("{}", tr!("✓ 已将文件移动到回收站: {}", path.display()));
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                println!("{}", tr!("✗ 移动到回收站失败: {} - {}", path.display(), e));
+            }
+        }
+    }
+
+    Ok(())
+}
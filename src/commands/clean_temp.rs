@@ -0,0 +1,251 @@
+//! # 临时文件清理工具 (clean_temp)
+//!
+//! 删除之前运行异常退出（例如进程被强制结束或断电）时残留的临时文件和临时目录。
+//!
+//! ## 清理范围
+//!
+//! - `<系统临时目录>/scripts` 下的所有条目
+//! - 旧版本直接创建在系统临时目录中的 `scripts-mirror-*`、`ffmpeg-download-*` 目录
+//!   和以 UUID 命名的 `.webm`/`.mp4` 转码临时文件
+//! - 命令行中给出的目录（递归）下由 [`TempPath::beside`] 创建、以 `.scripts-<UUID>-` 开头的
+//!   未完成输出文件
+//!
+//! [`TempPath::beside`]: crate::utils::tempfiles::TempPath::beside
+//!
+//! 只清理修改时间早于 `--older-than` 小时前的条目，避免删除正在运行的命令的临时文件。
+//! 临时文件不会移动到回收站，而是直接永久删除。
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::{TEMP_PREFIX, temp_root};
+use anyhow::Result;
+use bytesize::ByteSize;
+use clap::Args;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "clean-temp")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "清理异常退出残留的临时文件",
+    long_about = "删除系统临时目录中由本程序创建、因异常退出而残留的临时文件和临时目录（镜像仓库、ffmpeg 下载目录、转码临时文件等），以及指定目录中残留的 `.scripts-<UUID>-*` 未完成输出文件。只清理修改时间早于 --older-than 小时前的条目。临时文件直接永久删除，不移动到回收站。"
+)]
+pub struct CleanTempArgs {
+    /// 额外扫描的输出目录
+    #[arg(
+        value_name = "DIR",
+        help = "额外扫描的输出目录",
+        long_help = "递归扫描这些目录，清理写入输出文件时在目标文件旁创建、因异常退出而残留的 `.scripts-<UUID>-*` 临时文件，例如 video-transcode、backup、sync 的输出目录。不指定时只清理系统临时目录。"
+    )]
+    pub dirs: Vec<PathBuf>,
+
+    /// 只清理 N 小时前的条目
+    #[arg(
+        long,
+        value_name = "HOURS",
+        default_value_t = 24,
+        help = "只清理 N 小时前修改的条目",
+        long_help = "只清理修改时间早于 N 小时前的临时文件和目录，避免删除正在运行的命令的临时文件。设为 0 时清理所有条目。默认 24。"
+    )]
+    pub older_than: u64,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际删除",
+        long_help = "列出将被删除的临时文件和目录及其大小，但不实际删除。"
+    )]
+    pub dry_run: bool,
+
+    /// 跳过删除确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除确认",
+        long_help = "默认会列出将被删除的条目并要求确认，开启后跳过确认直接执行，适合计划任务。"
+    )]
+    pub yes: bool,
+}
+
+/// 判断系统临时目录中的条目是否为旧版本遗留的临时文件
+fn is_legacy_temp(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    if name.starts_with("scripts-mirror-") || name.starts_with("ffmpeg-download-") {
+        return path.is_dir();
+    }
+
+    // 转码临时文件：<UUIDv7>.webm 或 <UUIDv7>.mp4
+    match name.rsplit_once('.') {
+        Some((stem, "webm" | "mp4")) => {
+            path.is_file() && Uuid::parse_str(stem).is_ok_and(|id| id.get_version_num() == 7)
+        }
+        _ => false,
+    }
+}
+
+/// 判断文件名是否为 [`TempPath::beside`] 创建的临时文件：`.scripts-<UUIDv7>-<目标文件名>`
+///
+/// [`TempPath::beside`]: crate::utils::tempfiles::TempPath::beside
+fn is_beside_temp(name: &str) -> bool {
+    let Some(rest) = name
+        .strip_prefix('.')
+        .and_then(|rest| rest.strip_prefix(TEMP_PREFIX))
+    else {
+        return false;
+    };
+    match (rest.get(..36), rest.get(36..)) {
+        (Some(id), Some(tail)) => {
+            tail.starts_with('-') && Uuid::parse_str(id).is_ok_and(|id| id.get_version_num() == 7)
+        }
+        _ => false,
+    }
+}
+
+/// 收集候选的临时条目
+///
+/// # 参数
+///
+/// * `dirs` - 额外递归扫描 `.scripts-<UUID>-*` 临时文件的目录
+fn collect_candidates(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(temp_root())
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .collect();
+
+    candidates.extend(
+        std::fs::read_dir(env::temp_dir())
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| is_legacy_temp(path)),
+    );
+
+    for dir in dirs {
+        candidates.extend(
+            WalkDir::new(dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .filter(|e| e.file_name().to_str().is_some_and(is_beside_temp))
+                .map(|e| e.into_path()),
+        );
+    }
+
+    candidates.sort();
+    candidates
+}
+
+/// 删除文件或目录
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// 命令执行函数
+///
+/// 1. 收集临时目录和指定目录中本程序创建的条目
+/// 2. 按修改时间筛选出过期的条目
+/// 3. 输出计划或确认后永久删除
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 清理完成
+/// * `Err(anyhow::Error)` - 指定的目录不存在或读取确认输入失败
+pub async fn run(args: CleanTempArgs) -> Result<()> {
+    for dir in &args.dirs {
+        if !dir.is_dir() {
+            anyhow::bail!(tr!("目录不存在: {}", dir.display()));
+        }
+    }
+
+    println!(
+        "{}",
+        tr!("{} 临时文件清理工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("临时目录: {}", temp_root().display()));
+    for dir in &args.dirs {
+        println!("{}", tr!("扫描目录: {}", dir.display()));
+    }
+    println!("{}", tr!("清理条件: {} 小时前修改", args.older_than));
+    println!();
+
+    // 小时数过大导致时间溢出时没有截止时间，不清理任何文件
+    let cutoff = args
+        .older_than
+        .checked_mul(60 * 60)
+        .and_then(|secs| SystemTime::now().checked_sub(Duration::from_secs(secs)));
+    let candidates = collect_candidates(&args.dirs);
+    stats::add(Counter::Scanned, candidates.len() as u64);
+    let stale: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter(|path| {
+            std::fs::symlink_metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| cutoff.is_some_and(|cutoff| modified <= cutoff))
+        })
+        .collect();
+
+    if stale.is_empty() {
+        println!("{}", tr!("没有需要清理的临时文件"));
+        return Ok(());
+    }
+
+    let operations: Vec<Operation> = stale
+        .iter()
+        .map(|path| Operation::new(OperationKind::Delete, path, None))
+        .collect();
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    if !confirm_destructive("永久删除", &stale, args.yes)? {
+        return Ok(());
+    }
+
+    let mut freed = 0;
+    let mut failed = 0;
+    for op in &operations {
+        match remove_path(&op.source) {
+            Ok(()) => {
+                freed += op.size;
+                stats::add(Counter::Deleted, 1);
+                println!("{}", tr!("✓ 已删除: {}", op.source.display()));
+            }
+            Err(e) => {
+                failed += 1;
+                stats::add(Counter::Failed, 1);
+                println!("{}", tr!("✗ 删除失败: {} - {}", op.source.display(), e));
+            }
+        }
+    }
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("删除: {}", operations.len() - failed));
+    println!("{}", tr!("失败: {}", failed));
+    println!("{}", tr!("释放空间: {}", ByteSize(freed)));
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! # 配置文件管理工具 (config)
+//!
+//! 查看或编辑 `~/.config/scripts/config.toml`，该文件为各子命令提供默认参数。
+
+use crate::utils::config::{CONFIG_TEMPLATE, config_path};
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use std::env;
+use std::process::Command;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "config")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "查看或编辑配置文件",
+    long_about = "管理 ~/.config/scripts/config.toml。配置文件中每个 [子命令] 表的键为参数长名称，值作为该参数的默认值，命令行参数优先。"
+)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+/// 配置文件操作
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// 显示配置文件路径和内容
+    Show,
+    /// 使用编辑器打开配置文件（不存在时根据模板创建）
+    Edit,
+}
+
+/// 获取编辑器命令
+///
+/// 依次读取 `VISUAL`、`EDITOR` 环境变量，未设置时 Windows 使用 notepad，其他平台使用 vi。
+fn editor() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// 命令执行函数
+pub async fn run(args: ConfigArgs) -> Result<()> {
+    let path = config_path()?;
+
+    match args.action {
+        ConfigAction::Show => {
+            println!("{}", tr!("配置文件: {}", path.display()));
+            println!();
+            if path.exists() {
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| tr!("读取配置文件失败: {}", path.display()))?;
+                println!("{}", content);
+            } else {
+                println!("{}", tr!("配置文件不存在，使用 `scripts config edit` 创建"));
+            }
+        }
+        ConfigAction::Edit => {
+            if !path.exists() {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| tr!("创建目录失败: {}", parent.display()))?;
+                }
+                std::fs::write(&path, CONFIG_TEMPLATE)
+                    .with_context(|| tr!("创建配置文件失败: {}", path.display()))?;
+                println!("{}", tr!("已创建配置文件: {}", path.display()));
+            }
+
+            let editor = editor();
+            let status = Command::new(&editor)
+                .arg(&path)
+                .status()
+                .with_context(|| tr!("启动编辑器失败: {}", editor))?;
+
+            if !status.success() {
+                anyhow::bail!(tr!("编辑器异常退出: {}", editor));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! # 目录差异对比工具 (dir_diff)
+//!
+//! 比较两个目录树（或目录与校验和清单），报告新增、删除和内容变化的文件及其大小。
+//!
+//! ## 对比规则
+//!
+//! - 两侧均为目录时，大小不同即视为变化；大小相同时再比较 Blake3 哈希
+//! - 任一侧为清单文件时，按哈希比较（目录一侧的文件按清单中哈希的编码计算哈希）
+
+use crate::utils::hash::{HashEncoding, calculate_file_hash_as, read_manifest};
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "dir-diff")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "比较两个目录树的差异",
+    long_about = "比较两个目录树，报告新增、删除和内容变化的文件及其大小。任一侧可以是校验和清单文件（每行 `<哈希>  <相对路径>`），此时按 Blake3 哈希比较。"
+)]
+pub struct DirDiffArgs {
+    /// 旧目录或清单文件
+    #[arg(
+        value_name = "A",
+        help = "旧目录或清单文件",
+        long_help = "作为对比基准的目录或校验和清单文件。"
+    )]
+    pub a: PathBuf,
+
+    /// 新目录或清单文件
+    #[arg(
+        value_name = "B",
+        help = "新目录或清单文件",
+        long_help = "与基准对比的目录或校验和清单文件。"
+    )]
+    pub b: PathBuf,
+
+    /// 以 JSON 格式输出
+    #[arg(
+        long,
+        help = "以 JSON 格式输出",
+        long_help = "以 JSON 对象形式输出 added、removed、changed 三个列表，适合由其他工具处理。"
+    )]
+    pub json: bool,
+}
+
+/// 对比的一侧：目录或清单
+enum Side {
+    /// 目录，记录相对路径到文件大小的映射
+    Dir {
+        root: PathBuf,
+        files: BTreeMap<String, u64>,
+    },
+    /// 清单，记录相对路径到哈希的映射
+    Manifest { hashes: BTreeMap<String, String> },
+}
+
+impl Side {
+    /// 根据路径类型加载目录或清单
+    fn load(path: &Path) -> Result<Self> {
+        if path.is_dir() {
+            let files = WalkDir::new(path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .filter_map(|e| {
+                    let relative = e.path().strip_prefix(path).ok()?;
+                    let relative = relative.to_string_lossy().replace('\\', "/");
+                    let size = e.metadata().map(|m| m.len()).unwrap_or(0);
+                    Some((relative, size))
+                })
+                .collect();
+            Ok(Side::Dir {
+                root: path.to_path_buf(),
+                files,
+            })
+        } else if path.is_file() {
+            Ok(Side::Manifest {
+                hashes: read_manifest(path)?,
+            })
+        } else {
+            anyhow::bail!(tr!("路径不存在: {}", path.display()))
+        }
+    }
+
+    /// 所有文件的相对路径
+    fn paths(&self) -> BTreeSet<&str> {
+        match self {
+            Side::Dir { files, .. } => files.keys().map(String::as_str).collect(),
+            Side::Manifest { hashes } => hashes.keys().map(String::as_str).collect(),
+        }
+    }
+
+    /// 文件大小（清单中没有大小信息）
+    fn size(&self, path: &str) -> Option<u64> {
+        match self {
+            Side::Dir { files, .. } => files.get(path).copied(),
+            Side::Manifest { .. } => None,
+        }
+    }
+
+    /// 清单中记录的哈希编码（目录没有固定编码）
+    fn encoding(&self, path: &str) -> Option<HashEncoding> {
+        match self {
+            Side::Dir { .. } => None,
+            Side::Manifest { hashes } => hashes.get(path).map(|hash| HashEncoding::detect(hash)),
+        }
+    }
+
+    /// 文件哈希，目录一侧按 `encoding` 编码
+    async fn hash(&self, path: &str, encoding: HashEncoding) -> Result<String> {
+        match self {
+            Side::Dir { root, .. } => calculate_file_hash_as(root.join(path), encoding).await,
+            Side::Manifest { hashes } => hashes
+                .get(path)
+                .cloned()
+                .with_context(|| tr!("清单中不存在: {}", path)),
+        }
+    }
+}
+
+/// 新增或删除的文件
+#[derive(Debug, Serialize)]
+pub struct FileEntry {
+    /// 相对路径
+    pub path: String,
+    /// 文件大小（字节数），来自清单时为空
+    pub size: Option<u64>,
+}
+
+/// 内容变化的文件
+#[derive(Debug, Serialize)]
+pub struct ChangedEntry {
+    /// 相对路径
+    pub path: String,
+    /// 旧文件大小（字节数）
+    pub old_size: Option<u64>,
+    /// 新文件大小（字节数）
+    pub new_size: Option<u64>,
+}
+
+/// 对比结果
+#[derive(Debug, Default, Serialize)]
+pub struct DiffReport {
+    /// 仅存在于 B 的文件
+    pub added: Vec<FileEntry>,
+    /// 仅存在于 A 的文件
+    pub removed: Vec<FileEntry>,
+    /// 两侧都存在但内容不同的文件
+    pub changed: Vec<ChangedEntry>,
+}
+
+/// 判断两侧的同名文件内容是否不同
+async fn is_changed(a: &Side, b: &Side, path: &str) -> Result<bool> {
+    if let (Some(old_size), Some(new_size)) = (a.size(path), b.size(path))
+        && old_size != new_size
+    {
+        return Ok(true);
+    }
+    // 与清单比较时使用清单中哈希的编码，十六进制清单（如 b3sum 输出）也能正确比较
+    let encoding = a
+        .encoding(path)
+        .or_else(|| b.encoding(path))
+        .unwrap_or_default();
+    Ok(a.hash(path, encoding).await? != b.hash(path, encoding).await?)
+}
+
+/// 对比两侧并生成报告
+async fn diff(a: &Side, b: &Side) -> Result<DiffReport> {
+    let a_paths = a.paths();
+    let b_paths = b.paths();
+    let mut report = DiffReport::default();
+    stats::add(Counter::Scanned, a_paths.union(&b_paths).count() as u64);
+
+    for path in b_paths.difference(&a_paths) {
+        report.added.push(FileEntry {
+            path: path.to_string(),
+            size: b.size(path),
+        });
+    }
+
+    for path in a_paths.difference(&b_paths) {
+        report.removed.push(FileEntry {
+            path: path.to_string(),
+            size: a.size(path),
+        });
+    }
+
+    for path in a_paths.intersection(&b_paths) {
+        let changed = is_changed(a, b, path).await;
+        if changed.is_err() {
+            stats::add(Counter::Failed, 1);
+        }
+        let changed = changed.with_context(|| tr!("比较 {} 失败", path))?;
+        stats::add(Counter::Processed, 1);
+        if changed {
+            report.changed.push(ChangedEntry {
+                path: path.to_string(),
+                old_size: a.size(path),
+                new_size: b.size(path),
+            });
+        }
+    }
+
+    Ok(report)
+}
+
+/// 格式化可选的文件大小
+fn format_size(size: Option<u64>) -> String {
+    size.map(|s| ByteSize(s).to_string())
+        .unwrap_or("-".to_string())
+}
+
+/// 命令执行函数
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 对比完成
+/// * `Err(anyhow::Error)` - 路径不存在、清单格式错误或读取文件失败
+pub async fn run(args: DirDiffArgs) -> Result<()> {
+    let a = Side::load(&args.a)?;
+    let b = Side::load(&args.b)?;
+    let report = diff(&a, &b).await?;
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).with_context(|| tr!("序列化 JSON 失败"))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!("{} 目录差异对比 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("A: {}", args.a.display());
+    println!("B: {}", args.b.display());
+    println!();
+
+    for entry in &report.added {
+        println!("+ {} ({})", entry.path, format_size(entry.size));
+    }
+    for entry in &report.removed {
+        println!("- {} ({})", entry.path, format_size(entry.size));
+    }
+    for entry in &report.changed {
+        println!(
+            "~ {} ({} -> {})",
+            entry.path,
+            format_size(entry.old_size),
+            format_size(entry.new_size)
+        );
+    }
+    println!();
+
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("新增: {}", report.added.len()));
+    println!("{}", tr!("删除: {}", report.removed.len()));
+    println!("{}", tr!("变化: {}", report.changed.len()));
+
+    Ok(())
+}
@@ -0,0 +1,264 @@
+//! # 环境检查工具 (doctor)
+//!
+//! 检查各子命令调用的外部工具（7-Zip、ffmpeg/ffprobe、AV1 编码器、git 等）是否可用，
+//! 输出版本和安装提示。指定子命令时只检查该命令需要的工具，缺少必需工具时返回
+//! [`ErrorKind::Dependency`] 错误（退出码 7），便于在脚本和计划任务中提前检查环境。
+
+use crate::utils::compress::locate_7z;
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::i18n::{tr, translate};
+use crate::utils::media::{Av1Encoder, find_ffmpeg, find_ffprobe, test_encoder};
+use anyhow::Result;
+use clap::{Args, CommandFactory};
+use std::path::PathBuf;
+use std::process::{Command as StdCommand, Stdio};
+
+/// 使用 AV1 编码器的子命令
+const AV1_COMMANDS: [&str; 2] = ["av1-bench", "video-transcode"];
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "doctor")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "检查外部工具是否可用",
+    long_about = "检查各子命令依赖的外部工具（7-Zip、ffmpeg、ffprobe、AV1 编码器、git，以及下载 ffmpeg 使用的 curl/tar 和 Windows 下的 winget），输出版本和安装提示。指定子命令时只检查该命令需要的工具，缺少必需的工具时以退出码 7 退出。"
+)]
+pub struct DoctorArgs {
+    /// 要检查的子命令
+    #[arg(
+        value_name = "COMMAND",
+        help = "只检查指定子命令需要的工具",
+        long_help = "子命令名称，例如 video-transcode。指定后只检查该命令需要的工具，缺少任一工具时返回非零退出码；不指定时检查所有工具，只输出报告。"
+    )]
+    pub command: Option<String>,
+}
+
+/// 外部工具
+struct Tool {
+    /// 工具名称
+    name: &'static str,
+    /// 依赖该工具的子命令，为空时表示可选工具
+    commands: &'static [&'static str],
+    /// 输出版本信息的参数
+    version_args: &'static [&'static str],
+    /// 安装提示
+    hint: &'static str,
+}
+
+/// 所有外部工具
+const TOOLS: [Tool; 7] = [
+    Tool {
+        name: "7z",
+        commands: &["backup", "batch-compress", "extract"],
+        version_args: &[],
+        hint: "从 https://www.7-zip.org/ 下载安装，或执行 winget install 7zip.7zip",
+    },
+    Tool {
+        name: "ffmpeg",
+        commands: &[
+            "audio-extract",
+            "av1-bench",
+            "video-thumbs",
+            "video-transcode",
+        ],
+        version_args: &["-version"],
+        hint: "执行 winget install ffmpeg，或在相关命令中加上 --download-ffmpeg 下载静态构建",
+    },
+    Tool {
+        name: "ffprobe",
+        commands: &["media-info", "video-thumbs"],
+        version_args: &["-version"],
+        hint: "随 ffmpeg 一起安装",
+    },
+    Tool {
+        name: "git",
+        commands: &["git-clean", "repo-mirror"],
+        version_args: &["--version"],
+        hint: "从 https://git-scm.com/ 下载安装，或执行 winget install Git.Git",
+    },
+    Tool {
+        name: "curl",
+        commands: &[],
+        version_args: &["--version"],
+        hint: "--download-ffmpeg 需要，Windows 10 及以上系统自带",
+    },
+    Tool {
+        name: "tar",
+        commands: &[],
+        version_args: &["--version"],
+        hint: "--download-ffmpeg 需要，Windows 10 及以上系统自带",
+    },
+    Tool {
+        name: "winget",
+        commands: &[],
+        version_args: &["--version"],
+        hint: "未安装 ffmpeg 时用于自动安装，可从 Microsoft Store 安装“应用安装程序”",
+    },
+];
+
+/// 获取工具的可执行文件
+///
+/// 7-Zip 只在常见安装位置查找，与实际调用时一致；ffmpeg/ffprobe 优先使用自动下载的版本。
+fn tool_program(name: &str) -> Option<PathBuf> {
+    match name {
+        "7z" => locate_7z(),
+        "ffmpeg" => Some(find_ffmpeg()),
+        "ffprobe" => Some(find_ffprobe()),
+        _ => Some(PathBuf::from(name)),
+    }
+}
+
+/// 运行工具获取版本信息（输出的第一个非空行）
+///
+/// # 返回值
+///
+/// * `Some(String)` - 版本信息
+/// * `None` - 工具不存在或无法运行
+fn detect_version(tool: &Tool) -> Option<String> {
+    let output = StdCommand::new(tool_program(tool.name)?)
+        .args(tool.version_args)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+
+    let text = if output.stdout.is_empty() {
+        String::from_utf8_lossy(&output.stderr).to_string()
+    } else {
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// 检查指定名称是否为有效的子命令
+fn is_known_command(name: &str) -> bool {
+    crate::Cli::command().find_subcommand(name).is_some()
+}
+
+/// 命令执行函数
+///
+/// 1. 根据子命令筛选需要检查的工具
+/// 2. 逐个运行工具获取版本，输出检查结果和安装提示
+/// 3. 需要时测试各 AV1 编码器是否可用
+/// 4. 指定子命令且缺少必需工具时返回错误
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 检查完成（指定子命令时表示所需工具均可用）
+/// * `Err(anyhow::Error)` - 子命令不存在或缺少必需的工具
+pub async fn run(args: DoctorArgs) -> Result<()> {
+    if let Some(command) = &args.command
+        && !is_known_command(command)
+    {
+        anyhow::bail!(tr!("未知的子命令: {}", command));
+    }
+
+    println!(
+        "{}",
+        tr!("{} 环境检查工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    if let Some(command) = &args.command {
+        println!("{}", tr!("检查命令: {}", command));
+    }
+    println!();
+
+    let tools: Vec<&Tool> = TOOLS
+        .iter()
+        // winget 只在 Windows 下使用
+        .filter(|tool| tool.name != "winget" || cfg!(windows))
+        .filter(|tool| match &args.command {
+            Some(command) => tool.commands.contains(&command.as_str()),
+            None => true,
+        })
+        .collect();
+    let check_av1 = args
+        .command
+        .as_deref()
+        .is_none_or(|command| AV1_COMMANDS.contains(&command));
+
+    if tools.is_empty() {
+        println!("{}", tr!("该命令不依赖外部工具"));
+        return Ok(());
+    }
+
+    let mut found = 0;
+    let mut missing_required = Vec::new();
+    let mut ffmpeg_found = false;
+    for tool in &tools {
+        let required = if tool.commands.is_empty() {
+            tr!("可选")
+        } else {
+            tool.commands.join(", ")
+        };
+        match detect_version(tool) {
+            Some(version) => {
+                found += 1;
+                ffmpeg_found |= tool.name == "ffmpeg";
+                println!("✓ {}: {} ({})", tool.name, version, required);
+            }
+            None => {
+                println!("{}", tr!("✗ {}: 未找到 ({})", tool.name, required));
+                println!("{}", tr!("  安装: {}", translate(tool.hint)));
+                if !tool.commands.is_empty() {
+                    missing_required.push(tool.name);
+                }
+            }
+        }
+    }
+
+    // AV1 编码器依赖 ffmpeg 构建和硬件驱动，需要实际编码测试
+    if check_av1 && ffmpeg_found {
+        println!();
+        println!("{}", tr!("AV1 编码器 ({}):", AV1_COMMANDS.join(", ")));
+        let mut available = 0;
+        for encoder in Av1Encoder::ALL {
+            match encoder
+                .ffmpeg_names()
+                .iter()
+                .find(|name| test_encoder(name))
+            {
+                Some(name) => {
+                    available += 1;
+                    println!("  ✓ {} ({})", encoder.name(), name);
+                }
+                None => println!("  ✗ {}", encoder.name()),
+            }
+        }
+        if available == 0 {
+            println!(
+                "{}",
+                tr!(
+                    "  安装: 更新显卡驱动，或使用包含 libsvtav1 的 ffmpeg 构建（--download-ffmpeg）"
+                )
+            );
+            missing_required.push(translate("AV1 编码器"));
+        }
+    }
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("可用: {}", found));
+    println!("{}", tr!("缺失: {}", tools.len() - found));
+
+    if let Some(command) = &args.command
+        && !missing_required.is_empty()
+    {
+        return Err(ScriptError::new(
+            ErrorKind::Dependency,
+            tr!(
+                "{} 缺少必需的外部工具: {}",
+                command,
+                missing_required.join(", ")
+            ),
+        )
+        .into());
+    }
+    Ok(())
+}
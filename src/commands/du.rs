@@ -0,0 +1,294 @@
+//! # 磁盘占用分析工具 (du)
+//!
+//! 并行计算目录下各子项的大小，按大小降序输出树形结构，
+//! 用于快速找出占用空间最多的目录和文件。
+//!
+//! ## 功能特性
+//!
+//! - 首层子项并行计算
+//! - 每层只显示最大的前 N 项
+//! - 支持按最小大小过滤
+//! - 不跟随符号链接，权限不足时自动跳过并在结尾报告跳过的条目数量
+
+use crate::utils::filesystem::measure_dir_size;
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "du")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "分析目录的磁盘占用",
+    long_about = "并行计算目录下各子项的大小，按大小降序输出树形结构。每层只显示最大的前 N 项，可通过 --min-size 过滤掉较小的项。"
+)]
+pub struct DuArgs {
+    /// 要分析的目录
+    #[arg(
+        short = 'd',
+        long,
+        default_value = ".",
+        value_name = "DIR",
+        help = "要分析的目录",
+        long_help = "要分析磁盘占用的目录。默认当前目录 (.)。"
+    )]
+    pub dir: PathBuf,
+
+    /// 每层显示的最大项数
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = 10,
+        value_name = "N",
+        help = "每层显示的最大项数",
+        long_help = "每层按大小降序只显示前 N 项，其余项合并为一行汇总。默认 10。"
+    )]
+    pub top: usize,
+
+    /// 树的展开深度
+    #[arg(
+        short = 'l',
+        long,
+        default_value_t = 1,
+        value_name = "DEPTH",
+        help = "树的展开深度",
+        long_help = "展开显示的目录层数，1 表示只显示直接子项。默认 1。"
+    )]
+    pub depth: usize,
+
+    /// 最小显示大小
+    #[arg(
+        short = 'm',
+        long,
+        value_name = "SIZE",
+        help = "仅显示不小于该大小的项",
+        long_help = "仅显示大小不小于该值的项，例如 100MB、1GiB。"
+    )]
+    pub min_size: Option<ByteSize>,
+}
+
+/// 树节点
+#[derive(Debug)]
+struct Node {
+    /// 路径
+    path: PathBuf,
+    /// 是否为目录
+    is_dir: bool,
+    /// 总大小（字节数）
+    size: u64,
+    /// 无法访问而跳过的条目数量（含子节点）
+    skipped: usize,
+    /// 子节点（按大小降序，仅在展开深度内填充）
+    children: Vec<Node>,
+}
+
+/// 读取目录的直接子项（不跟随符号链接）
+///
+/// # 返回值
+///
+/// * `Ok(Vec<(PathBuf, bool)>)` - 子项路径及是否为目录，无法读取的子项被忽略
+/// * `Err(std::io::Error)` - 目录无法读取，例如权限不足
+fn read_children(path: &Path) -> std::io::Result<Vec<(PathBuf, bool)>> {
+    Ok(std::fs::read_dir(path)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let file_type = e.file_type().ok()?;
+            if file_type.is_symlink() {
+                return None;
+            }
+            Some((e.path(), file_type.is_dir()))
+        })
+        .collect())
+}
+
+/// 递归构建树节点
+///
+/// 在展开深度内逐层读取子项，超出深度的目录直接计算总大小。
+/// 无法访问的文件和目录计入 `skipped`，不中断统计。
+fn build_node(path: PathBuf, is_dir: bool, depth: usize) -> Node {
+    if !is_dir {
+        let (size, skipped) = match path.metadata() {
+            Ok(metadata) => (metadata.len(), 0),
+            Err(_) => (0, 1),
+        };
+        return Node {
+            path,
+            is_dir,
+            size,
+            skipped,
+            children: Vec::new(),
+        };
+    }
+
+    if depth == 0 {
+        let size = measure_dir_size(&path);
+        return Node {
+            path,
+            is_dir,
+            size: size.bytes,
+            skipped: size.skipped,
+            children: Vec::new(),
+        };
+    }
+
+    let Ok(entries) = read_children(&path) else {
+        return Node {
+            path,
+            is_dir,
+            size: 0,
+            skipped: 1,
+            children: Vec::new(),
+        };
+    };
+    let mut children: Vec<Node> = entries
+        .into_iter()
+        .map(|(child, child_is_dir)| build_node(child, child_is_dir, depth - 1))
+        .collect();
+    children.sort_by_key(|node| std::cmp::Reverse(node.size));
+
+    Node {
+        path,
+        is_dir,
+        size: children.iter().map(|node| node.size).sum(),
+        skipped: children.iter().map(|node| node.skipped).sum(),
+        children,
+    }
+}
+
+/// 获取节点的显示名称（目录以 `/` 结尾）
+fn display_name(node: &Node) -> String {
+    let name = node
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| node.path.display().to_string());
+    if node.is_dir {
+        format!("{}/", name)
+    } else {
+        name
+    }
+}
+
+/// 以树形结构打印子节点
+fn print_children(node: &Node, prefix: &str, top: usize, min_size: u64) {
+    let visible: Vec<&Node> = node
+        .children
+        .iter()
+        .filter(|child| child.size >= min_size)
+        .take(top)
+        .collect();
+    let hidden: Vec<&Node> = node
+        .children
+        .iter()
+        .filter(|child| !visible.iter().any(|v| std::ptr::eq(*v, *child)))
+        .collect();
+
+    for (index, child) in visible.iter().enumerate() {
+        let is_last = index == visible.len() - 1 && hidden.is_empty();
+        let branch = if is_last { "└── " } else { "├── " };
+        println!(
+            "{}{}{:>10}  {}",
+            prefix,
+            branch,
+            ByteSize(child.size).to_string(),
+            display_name(child)
+        );
+        let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+        print_children(child, &child_prefix, top, min_size);
+    }
+
+    if !hidden.is_empty() {
+        let hidden_size: u64 = hidden.iter().map(|node| node.size).sum();
+        println!(
+            "{}",
+            tr!(
+                "{}└── {}  (其余 {} 项)",
+                prefix,
+                format!("{:>10}", ByteSize(hidden_size).to_string()),
+                hidden.len()
+            )
+        );
+    }
+}
+
+/// 命令执行函数
+///
+/// 1. 读取目录的直接子项
+/// 2. 为每个子项启动阻塞任务并行计算大小
+/// 3. 按大小降序输出树形结构
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 分析完成
+/// * `Err(anyhow::Error)` - 目录不存在或无法访问
+pub async fn run(args: DuArgs) -> Result<()> {
+    let dir = args
+        .dir
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", args.dir.display()))?;
+
+    if !dir.is_dir() {
+        anyhow::bail!(tr!("路径必须是目录: {}", dir.display()));
+    }
+
+    let depth = args.depth.max(1);
+
+    // 首层子项并行计算
+    let handles: Vec<_> = read_children(&dir)
+        .with_context(|| tr!("无法读取目录: {}", dir.display()))?
+        .into_iter()
+        .map(|(child, is_dir)| {
+            tokio::task::spawn_blocking(move || build_node(child, is_dir, depth - 1))
+        })
+        .collect();
+
+    let mut children = Vec::with_capacity(handles.len());
+    for handle in handles {
+        children.push(handle.await.with_context(|| tr!("计算目录大小失败"))?);
+    }
+    children.sort_by_key(|node| std::cmp::Reverse(node.size));
+
+    let root = Node {
+        size: children.iter().map(|node| node.size).sum(),
+        skipped: children.iter().map(|node| node.skipped).sum(),
+        path: dir,
+        is_dir: true,
+        children,
+    };
+    stats::add(Counter::Scanned, root.children.len() as u64);
+    stats::add(Counter::Processed, root.children.len() as u64);
+    stats::add(Counter::Failed, root.skipped as u64);
+
+    println!(
+        "{:>10}  {}",
+        ByteSize(root.size).to_string(),
+        root.path.display()
+    );
+    print_children(
+        &root,
+        "",
+        args.top,
+        args.min_size.map(|s| s.as_u64()).unwrap_or(0),
+    );
+
+    if root.skipped > 0 {
+        println!();
+        println!(
+            "{}",
+            tr!(
+                "{} 项因权限不足等原因无法访问，以上大小为部分统计",
+                root.skipped
+            )
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! # 解压工具 (extract)
+//!
+//! 使用 7-Zip 解压一个或多个压缩包，支持密码（包括 `keyring:` 引用）和目标目录选择，
+//! 是 batch_compress 的反向操作。
+
+use crate::utils::compress::extract_7z;
+use crate::utils::filesystem::path_size;
+use crate::utils::i18n::tr;
+use crate::utils::secrets::resolve_secret;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "extract")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "使用 7-Zip 解压压缩包",
+    long_about = "使用 7-Zip 解压一个或多个压缩包（.7z、.zip、.rar 等）。默认解压到压缩包所在目录下与压缩包同名的子目录，也可通过 --output 指定目标目录。支持密码及 keyring:<名称> 引用。"
+)]
+pub struct ExtractArgs {
+    /// 要解压的压缩包
+    #[arg(
+        value_name = "ARCHIVE",
+        required = true,
+        help = "要解压的压缩包",
+        long_help = "一个或多个压缩包路径。"
+    )]
+    pub archives: Vec<PathBuf>,
+
+    /// 目标目录
+    #[arg(
+        short = 'o',
+        long,
+        value_name = "DIR",
+        help = "解压目标目录",
+        long_help = "所有压缩包都解压到该目录。不指定时，每个压缩包解压到其所在目录下与压缩包同名（去掉扩展名）的子目录。"
+    )]
+    pub output: Option<PathBuf>,
+
+    /// 解压密码
+    #[arg(
+        short = 'p',
+        long,
+        value_name = "PASSWORD",
+        help = "解压密码",
+        long_help = "加密压缩包的密码，支持 keyring:<名称> 引用 `scripts secrets set` 保存的密码。不指定时，加密压缩包会由 7-Zip 交互式询问密码。"
+    )]
+    pub password: Option<String>,
+}
+
+/// 获取压缩包的默认解压目录：所在目录下与压缩包同名（去掉扩展名）的子目录
+fn default_output_dir(archive: &Path) -> PathBuf {
+    let stem = archive
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    archive
+        .parent()
+        .map(|parent| parent.join(&stem))
+        .unwrap_or_else(|| PathBuf::from(&stem))
+}
+
+/// 命令执行函数
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 全部解压成功
+/// * `Err(anyhow::Error)` - 压缩包不存在、密码解析失败或解压失败
+pub async fn run(args: ExtractArgs) -> Result<()> {
+    // 解析密码中的密钥环引用
+    let password = args.password.as_deref().map(resolve_secret).transpose()?;
+
+    println!("{}", tr!("{} 解压工具 {}", "=".repeat(15), "=".repeat(15)));
+    println!("{}", tr!("压缩包数量: {}", args.archives.len()));
+    println!();
+    stats::add(Counter::Scanned, args.archives.len() as u64);
+
+    for archive in &args.archives {
+        if !archive.is_file() {
+            anyhow::bail!(tr!("压缩包不存在: {}", archive.display()));
+        }
+
+        let output_dir = args
+            .output
+            .clone()
+            .unwrap_or_else(|| default_output_dir(archive));
+
+        println!(
+            "{}",
+            tr!("解压: {} -> {}", archive.display(), output_dir.display())
+        );
+        // 输出目录可能已有内容，按解压前后的大小差计算写入量
+        let existing = path_size(&output_dir);
+        let result = extract_7z(archive, &output_dir, password.as_deref()).await;
+        if result.is_err() {
+            stats::add(Counter::Failed, 1);
+        }
+        result.with_context(|| tr!("处理 {} 失败", archive.display()))?;
+        stats::add(Counter::Processed, 1);
+        stats::add(Counter::BytesIn, path_size(archive));
+        stats::add(
+            Counter::BytesOut,
+            path_size(&output_dir).saturating_sub(existing),
+        );
+        println!();
+    }
+
+    println!("{}", tr!("操作成功完成！"));
+    Ok(())
+}
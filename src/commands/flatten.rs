@@ -0,0 +1,231 @@
+//! # 目录扁平化工具 (flatten)
+//!
+//! 将嵌套子目录中的所有文件移动到同一个目标目录，用于整理下载或解压后层级混乱的目录。
+//!
+//! ## 冲突处理
+//!
+//! - 同名且内容相同（Blake3 哈希一致）的文件视为重复，跳过不移动
+//! - 同名但内容不同的文件追加 `-1`、`-2` 等后缀
+//! - 与目标目录中的子目录等非普通文件同名时直接追加后缀，不做哈希比较
+//! - 名称比较不区分大小写，以兼容 Windows 和 macOS 的文件系统
+
+use crate::utils::filesystem::move_file;
+use crate::utils::hash::calculate_file_hash;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "flatten")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "将嵌套目录中的文件移动到同一目录",
+    long_about = "将源目录下所有子目录中的文件移动到目标目录（默认为源目录本身）。同名文件通过 Blake3 哈希比较：内容相同则跳过，内容不同则追加 -1、-2 等后缀。"
+)]
+pub struct FlattenArgs {
+    /// 源目录
+    #[arg(
+        short = 's',
+        long,
+        default_value = ".",
+        value_name = "SOURCE",
+        help = "源目录路径",
+        long_help = "要扁平化的目录，会递归处理其中所有子目录的文件。默认当前目录 (.)。"
+    )]
+    pub source: PathBuf,
+
+    /// 目标目录
+    #[arg(
+        short = 't',
+        long,
+        value_name = "TARGET",
+        help = "目标目录路径",
+        long_help = "所有文件移动到的目录，不存在时自动创建。默认为源目录本身。"
+    )]
+    pub target: Option<PathBuf>,
+
+    /// 移动后删除空目录
+    #[arg(
+        long,
+        help = "移动完成后删除空的子目录",
+        long_help = "移动完成后自底向上删除源目录中已经为空的子目录。"
+    )]
+    pub remove_empty: bool,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际执行",
+        long_help = "计算冲突并列出将要执行的移动操作及其大小，但不移动任何文件。"
+    )]
+    pub dry_run: bool,
+}
+
+/// 生成带数字后缀的文件名，例如 `photo.jpg` -> `photo-1.jpg`
+fn suffixed_name(name: &str, n: usize) -> String {
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    }
+}
+
+/// 自底向上删除空的子目录（不删除根目录本身）
+fn remove_empty_dirs(root: &Path) {
+    let dirs: Vec<PathBuf> = WalkDir::new(root)
+        .min_depth(1)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+        .map(|e| e.into_path())
+        .collect();
+
+    for dir in dirs {
+        // 非空目录删除失败是预期行为，忽略错误
+        if std::fs::remove_dir(&dir).is_ok() {
+            println!("{}", tr!("已删除空目录: {}", dir.display()));
+        }
+    }
+}
+
+/// 命令执行函数
+///
+/// 1. 记录目标目录中已有的文件名
+/// 2. 按路径顺序遍历源目录的子目录文件，根据哈希比较决定跳过、直接移动或追加后缀
+/// 3. 执行移动（或输出计划），可选删除空目录
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 执行成功
+/// * `Err(anyhow::Error)` - 目录无法访问、计算哈希或移动失败
+pub async fn run(args: FlattenArgs) -> Result<()> {
+    let source = args
+        .source
+        .canonicalize()
+        .with_context(|| tr!("无法访问源目录: {}", args.source.display()))?;
+    let target = match &args.target {
+        Some(target) => {
+            if !args.dry_run {
+                tokio::fs::create_dir_all(target)
+                    .await
+                    .with_context(|| tr!("创建目录失败: {}", target.display()))?;
+            }
+            target.canonicalize().unwrap_or(target.clone())
+        }
+        None => source.clone(),
+    };
+
+    println!(
+        "{}",
+        tr!("{} 目录扁平化工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", source.display()));
+    println!("{}", tr!("目标目录: {}", target.display()));
+    println!();
+
+    // 已占用的名称(小写) -> 当前持有该名称内容的文件路径
+    let mut assigned: HashMap<String, PathBuf> = HashMap::new();
+    // 目标目录中被目录等非普通文件占用的名称(小写)，不参与哈希比较
+    let mut occupied: HashSet<String> = HashSet::new();
+    if target.is_dir() {
+        for entry in std::fs::read_dir(&target)
+            .with_context(|| tr!("无法读取目录: {}", target.display()))?
+            .filter_map(|e| e.ok())
+        {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if entry.file_type().is_ok_and(|t| t.is_file()) {
+                assigned.insert(name, entry.path());
+            } else {
+                occupied.insert(name);
+            }
+        }
+    }
+
+    // 收集子目录中的文件(跳过目标目录本身和已位于目标目录的文件)
+    let mut files: Vec<PathBuf> = WalkDir::new(&source)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| e.path() != target)
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|path| path.parent() != Some(target.as_path()))
+        .collect();
+    files.sort();
+    stats::add(Counter::Scanned, files.len() as u64);
+
+    let mut moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut duplicates = 0;
+
+    for file in files {
+        let name = file
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .with_context(|| tr!("无效的文件名"))?;
+
+        let is_taken = |candidate: &str| {
+            let key = candidate.to_lowercase();
+            assigned.contains_key(&key) || occupied.contains(&key)
+        };
+        let mut final_name = name.clone();
+        if is_taken(&name) {
+            if let Some(existing) = assigned.get(&name.to_lowercase()) {
+                let file_hash = calculate_file_hash(&file).await?;
+                if calculate_file_hash(existing).await? == file_hash {
+                    println!("{}", tr!("跳过(内容相同): {}", file.display()));
+                    duplicates += 1;
+                    continue;
+                }
+            }
+            final_name = (1..)
+                .map(|n| suffixed_name(&name, n))
+                .find(|candidate| !is_taken(candidate))
+                .unwrap();
+        }
+
+        assigned.insert(final_name.to_lowercase(), file.clone());
+        moves.push((file, target.join(final_name)));
+    }
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        let operations: Vec<Operation> = moves
+            .iter()
+            .map(|(from, to)| Operation::new(OperationKind::Move, from, Some(to)))
+            .collect();
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    for (from, to) in &moves {
+        move_file(from, to).await?;
+        stats::add(Counter::Processed, 1);
+        println!("{}", tr!("移动: {} -> {}", from.display(), to.display()));
+    }
+
+    if args.remove_empty {
+        remove_empty_dirs(&source);
+    }
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("移动文件: {}", moves.len()));
+    println!("{}", tr!("跳过重复: {}", duplicates));
+
+    Ok(())
+}
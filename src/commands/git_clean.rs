@@ -0,0 +1,265 @@
+//! # Git 仓库整理工具 (git_clean)
+//!
+//! 查找目录下的所有 git 仓库，报告每个仓库的磁盘占用，
+//! 并可选执行 `git gc` 和删除已合并到主分支的本地分支。
+//!
+//! ## 功能特性
+//!
+//! - 递归查找仓库，找到仓库后不再进入其子目录
+//! - 主分支默认自动检测 `main` 或 `master`
+//! - 只使用 `git branch -d` 删除已合并的分支，不删除当前分支和主分支
+//! - 单个仓库失败时继续处理其他仓库
+
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::filesystem::calculate_dir_size;
+use crate::utils::git::{find_repositories, run_git};
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "git-clean")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "整理目录下的 git 仓库",
+    long_about = "查找目录下的所有 git 仓库，报告工作区和 .git 目录的大小以及已合并到主分支的本地分支。使用 --gc 执行 git gc，使用 --prune-merged 删除已合并的分支。不带操作选项时只输出报告。需要系统安装 git。"
+)]
+pub struct GitCleanArgs {
+    /// 要扫描的目录
+    #[arg(
+        short = 'd',
+        long,
+        default_value = ".",
+        value_name = "DIR",
+        help = "要扫描的目录",
+        long_help = "递归查找该目录下的 git 仓库。默认当前目录 (.)。"
+    )]
+    pub dir: PathBuf,
+
+    /// 执行 git gc
+    #[arg(
+        long,
+        help = "对每个仓库执行 git gc",
+        long_help = "对每个仓库执行 git gc 压缩对象、清理不可达对象，并报告 .git 目录释放的空间。"
+    )]
+    pub gc: bool,
+
+    /// 删除已合并的分支
+    #[arg(
+        long,
+        help = "删除已合并到主分支的本地分支",
+        long_help = "使用 git branch -d 删除已合并到主分支的本地分支，不会删除当前分支和主分支本身。"
+    )]
+    pub prune_merged: bool,
+
+    /// 主分支名称
+    #[arg(
+        short = 'b',
+        long,
+        value_name = "BRANCH",
+        help = "主分支名称",
+        long_help = "判断分支是否已合并时使用的主分支。默认依次尝试 main 和 master，仓库中都不存在时跳过分支清理。"
+    )]
+    pub base: Option<String>,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际执行",
+        long_help = "列出将要删除的分支和将要执行 git gc 的仓库，但不做任何修改。"
+    )]
+    pub dry_run: bool,
+}
+
+/// 单个仓库的处理结果
+#[derive(Debug, Default)]
+struct RepoReport {
+    /// 删除的分支数量
+    deleted_branches: usize,
+    /// git gc 释放的空间（字节数）
+    freed: u64,
+}
+
+/// 检测仓库的主分支
+///
+/// 指定了 `base` 时检查该分支是否存在，否则依次尝试 `main` 和 `master`。
+async fn detect_base(repo: &Path, base: Option<&str>) -> Option<String> {
+    let candidates = match base {
+        Some(base) => vec![base],
+        None => vec!["main", "master"],
+    };
+    for candidate in candidates {
+        let refname = format!("refs/heads/{}", candidate);
+        if run_git(&["rev-parse", "--verify", "--quiet", &refname], Some(repo))
+            .await
+            .is_ok()
+        {
+            return Some(candidate.to_string());
+        }
+    }
+    None
+}
+
+/// 列出已合并到主分支的本地分支（不含主分支和当前分支）
+async fn merged_branches(repo: &Path, base: &str) -> Result<Vec<String>> {
+    let current = run_git(&["branch", "--show-current"], Some(repo)).await?;
+    let current = current.trim();
+    let output = run_git(
+        &[
+            "for-each-ref",
+            "--merged",
+            base,
+            "--format=%(refname:short)",
+            "refs/heads/",
+        ],
+        Some(repo),
+    )
+    .await?;
+
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && *name != base && *name != current)
+        .map(str::to_string)
+        .collect())
+}
+
+/// 报告并整理单个仓库
+async fn clean_repo(repo: &Path, args: &GitCleanArgs) -> Result<RepoReport> {
+    let git_dir = repo.join(".git");
+    let git_size = calculate_dir_size(&git_dir);
+    let total_size = calculate_dir_size(repo);
+    let mut report = RepoReport::default();
+
+    println!("{}", tr!("仓库: {}", repo.display()));
+    println!(
+        "{}",
+        tr!(
+            "  工作区: {}  .git: {}",
+            ByteSize(total_size.saturating_sub(git_size)),
+            ByteSize(git_size)
+        )
+    );
+
+    match detect_base(repo, args.base.as_deref()).await {
+        Some(base) => {
+            let merged = merged_branches(repo, &base).await?;
+            if merged.is_empty() {
+                println!("{}", tr!("  已合并到 {} 的分支: 无", base));
+            } else {
+                println!(
+                    "{}",
+                    tr!("  已合并到 {} 的分支: {}", base, merged.join(", "))
+                );
+            }
+
+            if args.prune_merged {
+                for branch in &merged {
+                    if args.dry_run {
+                        println!("{}", tr!("  [计划] 删除分支: {}", branch));
+                        continue;
+                    }
+                    run_git(&["branch", "-d", branch], Some(repo))
+                        .await
+                        .with_context(|| tr!("删除分支失败: {}", branch))?;
+                    println!("{}", tr!("  已删除分支: {}", branch));
+                    report.deleted_branches += 1;
+                }
+            }
+        }
+        None => println!("{}", tr!("  未找到主分支，跳过分支检查")),
+    }
+
+    if args.gc {
+        if args.dry_run {
+            println!("{}", tr!("  [计划] git gc"));
+        } else {
+            run_git(&["gc", "--quiet"], Some(repo)).await?;
+            let after = calculate_dir_size(&git_dir);
+            report.freed = git_size.saturating_sub(after);
+            println!(
+                "{}",
+                tr!(
+                    "  git gc: {} -> {}（释放 {}）",
+                    ByteSize(git_size),
+                    ByteSize(after),
+                    ByteSize(report.freed)
+                )
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+/// 命令执行函数
+///
+/// 1. 查找目录下的所有 git 仓库
+/// 2. 逐个报告磁盘占用和已合并分支，按选项删除分支、执行 git gc
+/// 3. 输出统计结果
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 所有仓库处理成功
+/// * `Err(anyhow::Error)` - 目录无法访问或有仓库处理失败
+pub async fn run(args: GitCleanArgs) -> Result<()> {
+    let root = args
+        .dir
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", args.dir.display()))?;
+
+    println!(
+        "{}",
+        tr!("{} Git 仓库整理工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("扫描目录: {}", root.display()));
+    println!();
+
+    let repos = find_repositories(&root);
+    stats::add(Counter::Scanned, repos.len() as u64);
+    let mut deleted_branches = 0;
+    let mut freed = 0;
+    let mut failed = 0;
+
+    for repo in &repos {
+        match clean_repo(repo, &args).await {
+            Ok(report) => {
+                stats::add(Counter::Processed, 1);
+                stats::add(Counter::Deleted, report.deleted_branches as u64);
+                deleted_branches += report.deleted_branches;
+                freed += report.freed;
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                failed += 1;
+                println!("{}", tr!("  ✗ 处理失败: {}", format!("{:#}", e)));
+            }
+        }
+        println!();
+    }
+
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("仓库数量: {}", repos.len()));
+    if args.prune_merged && !args.dry_run {
+        println!("{}", tr!("删除分支: {}", deleted_branches));
+    }
+    if args.gc && !args.dry_run {
+        println!("{}", tr!("释放空间: {}", ByteSize(freed)));
+    }
+    println!("{}", tr!("失败: {}", failed));
+
+    if failed > 0 {
+        return Err(
+            ScriptError::new(ErrorKind::PartialFailure, tr!("{} 个仓库处理失败", failed)).into(),
+        );
+    }
+    Ok(())
+}
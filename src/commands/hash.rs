@@ -0,0 +1,308 @@
+//! # 文件哈希工具 (hash)
+//!
+//! 计算文件或目录中文件的 Blake3 哈希（默认 Base58 编码，与 hash-copy 的文件名一致，
+//! 也可使用与 `b3sum` 兼容的十六进制编码），
+//! 输出 `sha256sum` 风格的校验和清单，并可根据清单校验文件。
+//!
+//! ## 清单格式
+//!
+//! 每行 `<哈希>  <路径>`（两个空格分隔），与 split、dir-diff 使用的清单格式相同。
+//! 路径使用 `/` 分隔，写入清单文件时相对于清单文件所在的目录（不在该目录下的文件使用绝对路径），
+//! 输出到终端时为命令行中给出的路径（目录中的文件拼接在目录之后），即相对于当前目录。
+//! `--check` 时相对路径相对于清单文件所在的目录解析，因此清单可以原样校验。
+
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::filesystem::path_size;
+use crate::utils::hash::{HashEncoding, calculate_file_hash_as, read_manifest};
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "hash")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "计算文件哈希或校验清单",
+    long_about = "计算文件的 Blake3 哈希（默认 Base58 编码，可选十六进制），按 `<哈希>  <路径>` 格式输出到终端或清单文件。指定目录时计算目录中的文件，使用 --recursive 包含子目录。使用 --check 根据已有清单校验文件。"
+)]
+pub struct HashArgs {
+    /// 要计算哈希的文件或目录
+    #[arg(
+        value_name = "PATH",
+        required_unless_present = "check",
+        help = "要计算哈希的文件或目录",
+        long_help = "一个或多个文件或目录。写入清单文件时路径相对于清单文件所在的目录，输出到终端时路径相对于当前目录，与 --check 解析路径的方式一致。"
+    )]
+    pub paths: Vec<PathBuf>,
+
+    /// 递归处理子目录
+    #[arg(
+        short = 'r',
+        long,
+        help = "递归处理子目录",
+        long_help = "指定目录时包含所有子目录中的文件，默认只处理目录的直接子文件。"
+    )]
+    pub recursive: bool,
+
+    /// 哈希编码方式
+    #[arg(
+        short = 'e',
+        long,
+        value_enum,
+        default_value_t = HashEncoding::Base58,
+        help = "哈希编码方式",
+        long_help = "base58 与 hash-copy 文件名及其他命令的清单一致；hex 为小写十六进制，与 b3sum 的输出一致。--check 时根据清单内容自动识别。"
+    )]
+    pub encoding: HashEncoding,
+
+    /// 清单输出文件
+    #[arg(
+        short = 'o',
+        long,
+        value_name = "FILE",
+        conflicts_with = "check",
+        help = "将结果写入清单文件",
+        long_help = "将 `<哈希>  <路径>` 格式的结果写入文件而不是输出到终端。路径相对于清单文件所在的目录，例如 `hash -r photos -o photos.b3` 写入 `photos/a.jpg`，`-o photos/SUMS` 写入 `a.jpg`。清单文件位于被扫描的目录中时会自动排除自身。"
+    )]
+    pub output: Option<PathBuf>,
+
+    /// 复制到剪贴板
+    #[arg(
+        long,
+        conflicts_with = "check",
+        help = "将哈希复制到剪贴板",
+        long_help = "计算完成后将结果复制到系统剪贴板：只有一个文件时复制哈希值本身，多个文件时复制完整的清单内容。Linux 下剪贴板内容可能在程序退出后失效，需要运行剪贴板管理器。"
+    )]
+    pub copy: bool,
+
+    /// 要校验的清单文件
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "paths",
+        help = "根据清单文件校验文件",
+        long_help = "读取 `<哈希>  <路径>` 格式的清单并逐个校验文件，相对路径相对于清单文件所在的目录。存在缺失或不匹配的文件时返回错误。"
+    )]
+    pub check: Option<PathBuf>,
+}
+
+/// 将路径转换为清单中使用的 `/` 分隔格式
+fn manifest_name(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// 获取文件在清单中的路径
+///
+/// # 参数
+///
+/// * `path` - 文件路径
+/// * `base` - 清单文件所在目录（已规范化），输出到终端时为 `None`
+///
+/// # 返回值
+///
+/// * `String` - 指定 `base` 时为相对于 `base` 的路径，文件不在 `base` 下时为绝对路径；
+///   否则为去掉开头 `./` 的原路径
+fn entry_name(path: &Path, base: Option<&Path>) -> String {
+    let Some(base) = base else {
+        return manifest_name(path.strip_prefix(".").unwrap_or(path));
+    };
+    // Windows 下 canonicalize 返回 `\\?\C:\...` 形式的路径，去掉该前缀以便 --check 解析
+    match dunce::canonicalize(path) {
+        Ok(absolute) => manifest_name(absolute.strip_prefix(base).unwrap_or(&absolute)),
+        Err(_) => manifest_name(path),
+    }
+}
+
+/// 收集要计算哈希的文件：(清单中的路径, 实际路径)
+///
+/// # 参数
+///
+/// * `paths` - 命令行中给出的文件或目录
+/// * `recursive` - 是否递归处理子目录
+/// * `base` - 清单文件所在目录，清单中的路径相对于该目录；输出到终端时为 `None`
+fn collect_entries(
+    paths: &[PathBuf],
+    recursive: bool,
+    base: Option<&Path>,
+) -> Result<Vec<(String, PathBuf)>> {
+    let mut entries = Vec::new();
+
+    for path in paths {
+        if path.is_file() {
+            entries.push((entry_name(path, base), path.clone()));
+            continue;
+        }
+        if !path.is_dir() {
+            anyhow::bail!(tr!("路径不存在: {}", path.display()));
+        }
+
+        let max_depth = if recursive { usize::MAX } else { 1 };
+        let mut files: Vec<(String, PathBuf)> = WalkDir::new(path)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| (entry_name(e.path(), base), e.into_path()))
+            .collect();
+        files.sort();
+        entries.extend(files);
+    }
+
+    Ok(entries)
+}
+
+/// 将文本复制到系统剪贴板
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .with_context(|| tr!("复制到剪贴板失败"))
+}
+
+/// 根据清单校验文件
+async fn check_manifest(manifest: &Path) -> Result<()> {
+    let entries = read_manifest(manifest)?;
+    let base = manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    println!(
+        "{}",
+        tr!("{} 文件哈希工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("清单: {}", manifest.display()));
+    println!();
+
+    stats::add(Counter::Scanned, entries.len() as u64);
+    let mut failed = 0;
+    for (name, expected) in &entries {
+        let path = base.join(name);
+        if !path.is_file() {
+            println!("{}", tr!("✗ 缺失: {}", name));
+            stats::add(Counter::Failed, 1);
+            failed += 1;
+            continue;
+        }
+        let encoding = HashEncoding::detect(expected);
+        // 单个文件读取失败时记为失败并继续校验其余文件
+        match calculate_file_hash_as(&path, encoding).await {
+            Ok(actual) if &actual == expected => {
+                stats::add(Counter::Processed, 1);
+                stats::add(Counter::BytesIn, path_size(&path));
+                println!("✓ {}", name);
+            }
+            Ok(_) => {
+                println!("{}", tr!("✗ 不匹配: {}", name));
+                stats::add(Counter::Failed, 1);
+                stats::add(Counter::BytesIn, path_size(&path));
+                failed += 1;
+            }
+            Err(e) => {
+                println!("{}", tr!("✗ 读取失败: {}: {}", name, format!("{:#}", e)));
+                stats::add(Counter::Failed, 1);
+                failed += 1;
+            }
+        }
+    }
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("通过: {}", entries.len() - failed));
+    println!("{}", tr!("失败: {}", failed));
+
+    if failed > 0 {
+        return Err(
+            ScriptError::new(ErrorKind::Verification, tr!("{} 个文件校验失败", failed)).into(),
+        );
+    }
+    Ok(())
+}
+
+/// 命令执行函数
+///
+/// - 指定 `--check` 时根据清单校验文件
+/// - 否则计算文件哈希，输出到终端或写入清单文件，可选复制到剪贴板
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 计算或校验成功
+/// * `Err(anyhow::Error)` - 路径不存在、读写失败或校验失败
+pub async fn run(args: HashArgs) -> Result<()> {
+    if let Some(manifest) = &args.check {
+        return check_manifest(manifest).await;
+    }
+
+    // 清单中的路径相对于清单文件所在的目录，与 --check 解析路径的方式一致
+    let base = match &args.output {
+        Some(output) => {
+            let parent = output
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .unwrap_or(Path::new("."));
+            Some(
+                dunce::canonicalize(parent)
+                    .with_context(|| tr!("无法访问目录: {}", parent.display()))?,
+            )
+        }
+        None => None,
+    };
+    let mut entries = collect_entries(&args.paths, args.recursive, base.as_deref())?;
+
+    // 清单文件位于被扫描的目录中时排除自身
+    if let Some(output) = args.output.as_ref().and_then(|o| o.canonicalize().ok()) {
+        entries.retain(|(_, path)| path.canonicalize().ok().as_ref() != Some(&output));
+    }
+
+    stats::add(Counter::Scanned, entries.len() as u64);
+    let mut lines = String::new();
+    let mut last_hash = String::new();
+    for (name, path) in &entries {
+        let result = calculate_file_hash_as(path, args.encoding).await;
+        if result.is_err() {
+            stats::add(Counter::Failed, 1);
+        }
+        last_hash = result?;
+        stats::add(Counter::Processed, 1);
+        stats::add(Counter::BytesIn, path_size(path));
+        let line = format!("{}  {}", last_hash, name);
+        if args.output.is_none() {
+            println!("{}", line);
+        }
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+
+    if args.copy {
+        // 只有一个文件时复制哈希本身，便于直接粘贴
+        let text = if entries.len() == 1 {
+            &last_hash
+        } else {
+            &lines
+        };
+        copy_to_clipboard(text)?;
+        eprintln!("{}", tr!("已复制到剪贴板"));
+    }
+
+    if let Some(output) = &args.output {
+        let size = lines.len() as u64;
+        tokio::fs::write(output, lines)
+            .await
+            .with_context(|| tr!("写入清单失败: {}", output.display()))?;
+        stats::add(Counter::BytesOut, size);
+        println!(
+            "{}",
+            tr!(
+                "已写入清单: {} ({} 个文件)",
+                output.display(),
+                entries.len()
+            )
+        );
+    }
+
+    Ok(())
+}
@@ -3,8 +3,14 @@
 //! 一个简洁高效的 Rust 命令行工具，用于将源目录中的文件复制到目标目录，
 //! 并使用 Blake3 哈希值重命名以避免重复。
 
-use crate::utils::filesystem::get_file_extension;
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::error::Failures;
+use crate::utils::filesystem::{get_file_extension, long_path};
 use crate::utils::hash::calculate_file_hash;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
 use anyhow::{Context, Result};
 use clap::Args;
 use std::path::{Path, PathBuf};
@@ -77,6 +83,81 @@ pub struct HashCopyArgs {
         long_help = "开启后在复制成功后删除源文件（相当于移动）。默认关闭，仅复制不删除源文件。"
     )]
     pub move_after_copy: bool,
+
+    /// 跳过移动确认
+    ///
+    /// 启用移动模式时，默认会先列出将被移动到回收站的源文件并要求确认。
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过移动确认",
+        long_help = "启用 --move-after-copy 时默认会列出可能被移动到回收站的源文件并要求确认，开启后跳过确认直接执行，适合自动化场景。"
+    )]
+    pub yes: bool,
+
+    /// 仅输出执行计划
+    ///
+    /// 计算哈希并列出将要执行的复制和删除操作（含大小），但不实际执行。
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际执行",
+        long_help = "计算每个文件的哈希并列出将要执行的复制和删除操作及其大小，但不复制、不删除，也不创建目标目录。"
+    )]
+    pub dry_run: bool,
+
+    /// 出错后继续处理
+    ///
+    /// 单个文件失败时记录错误并继续，结束时汇总所有失败的文件。
+    #[arg(
+        short = 'k',
+        long,
+        help = "单个文件失败时继续处理其他文件",
+        long_help = "默认遇到第一个失败的文件即停止。开启后记录失败并继续处理剩余文件，结束时列出所有失败的文件及错误，并以非零退出码退出。"
+    )]
+    pub keep_going: bool,
+
+    /// 跟随符号链接
+    ///
+    /// 默认跳过符号链接；启用后按链接指向的文件和目录处理。
+    #[arg(
+        short = 'L',
+        long,
+        help = "跟随符号链接",
+        long_help = "默认跳过源目录中的符号链接并在开始前报告跳过的数量。开启后处理链接指向的文件，并进入链接指向的目录（循环链接会被忽略）。"
+    )]
+    pub follow_symlinks: bool,
+}
+
+/// 计算文件在目标目录中基于哈希的路径
+///
+/// 文件名为 Base58 编码的 Blake3 哈希，保留原始扩展名（小写）。
+///
+/// # 参数
+///
+/// * `file_path` - 源文件路径
+/// * `target_dir` - 目标目录路径
+///
+/// # 返回值
+///
+/// * `Ok(PathBuf)` - 目标文件路径
+/// * `Err(anyhow::Error)` - 计算哈希失败
+pub async fn hashed_target_path(file_path: &Path, target_dir: &Path) -> Result<PathBuf> {
+    // 计算文件哈希
+    let hash = calculate_file_hash(long_path(file_path))
+        .await
+        .with_context(|| tr!("计算文件哈希失败"))?;
+
+    // 获取文件扩展名（不带点，小写）
+    let ext = get_file_extension(file_path);
+
+    // 生成目标文件名
+    let target_filename = if ext.is_empty() {
+        hash
+    } else {
+        format!("{}.{}", hash, ext)
+    };
+
+    Ok(target_dir.join(target_filename))
 }
 
 /// 处理单个文件
@@ -105,46 +186,42 @@ pub async fn process_file(
     let file_name = file_path
         .file_name()
         .and_then(|n| n.to_str())
-        .context("无效的文件名")?;
-
-    println!("处理: {}", file_name);
+        .with_context(|| tr!("无效的文件名"))?;
 
-    // 计算文件哈希
-    let hash = calculate_file_hash(file_path)
-        .await
-        .context("计算文件哈希失败")?;
-
-    // 获取文件扩展名（不带点，小写）
-    let ext = get_file_extension(file_path);
+    println!("{}", tr!("处理: {}", file_name));
 
-    // 生成目标文件名
-    let target_filename = if ext.is_empty() {
-        hash
-    } else {
-        format!("{}.{}", hash, ext)
-    };
-
-    let target_path = target_dir.join(&target_filename);
+    let target_path = hashed_target_path(file_path, target_dir).await?;
+    let target_filename = target_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
 
     // 检查目标文件是否已存在
     if target_path.exists() {
-        println!("目标已存在: {}", target_filename);
+        println!("{}", tr!("目标已存在: {}", target_filename));
         return Ok(());
     }
 
-    // 复制文件
-    tokio::fs::copy(file_path, &target_path)
+    // 先复制到目标旁的临时文件再重命名，中断时不会在哈希文件名下留下不完整的文件
+    // （深层目录中的长路径或 aux.js 等保留名称需要扩展长度路径）
+    let temp = TempPath::beside(&target_path)?;
+    let copied = tokio::fs::copy(long_path(file_path), long_path(temp.path()))
         .await
-        .with_context(|| format!("复制文件到 {} 失败", target_path.display()))?;
+        .with_context(|| tr!("复制文件到 {} 失败", target_path.display()))?;
+    temp.persist(&target_path)?;
+    stats::add(Counter::Processed, 1);
+    stats::add(Counter::BytesIn, copied);
+    stats::add(Counter::BytesOut, copied);
 
-    println!("复制完成: {} -> {}", file_name, target_filename);
+    println!("{}", tr!("复制完成: {} -> {}", file_name, target_filename));
 
     // 如果启用了移动模式，复制成功后删除源文件
     if move_after_copy {
         trash::delete(file_path)
-            .with_context(|| format!("无法将源文件移动到回收站: {}", file_path.display()))?;
+            .with_context(|| tr!("无法将源文件移动到回收站: {}", file_path.display()))?;
+        stats::add(Counter::Deleted, 1);
 
-        println!("已将源文件移动到回收站: {}", file_name);
+        println!("{}", tr!("已将源文件移动到回收站: {}", file_name));
     }
 
     Ok(())
@@ -169,25 +246,28 @@ pub async fn process_file(
 pub async fn run(args: HashCopyArgs) -> anyhow::Result<()> {
     // 验证源目录和目标目录不能相同
     if args.source == args.target {
-        anyhow::bail!("源目录和目标目录不能相同");
+        anyhow::bail!(tr!("源目录和目标目录不能相同"));
     }
 
     // 验证源目录是否存在
     if !args.source.exists() {
-        anyhow::bail!("源目录不存在: {}", args.source.display());
+        anyhow::bail!(tr!("源目录不存在: {}", args.source.display()));
     }
 
     // 显示程序信息
-    println!("{} 哈希复制工具 {}", "=".repeat(15), "=".repeat(15));
-    println!("源目录: {}", args.source.display());
-    println!("目标目录: {}", args.target.display());
+    println!(
+        "{}",
+        tr!("{} 哈希复制工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", args.source.display()));
+    println!("{}", tr!("目标目录: {}", args.target.display()));
     println!();
 
-    // 确保目标目录存在
-    if !args.target.exists() {
+    // 确保目标目录存在(dry-run 模式不创建)
+    if !args.dry_run && !args.target.exists() {
         tokio::fs::create_dir_all(&args.target)
             .await
-            .with_context(|| format!("创建目录失败: {}", args.target.display()))?;
+            .with_context(|| tr!("创建目录失败: {}", args.target.display()))?;
     }
 
     // 解析文件扩展名参数（不带点）
@@ -199,14 +279,16 @@ pub async fn run(args: HashCopyArgs) -> anyhow::Result<()> {
         .collect();
 
     if allowed_extensions.is_empty() {
-        anyhow::bail!("扩展名列表不能为空");
+        anyhow::bail!(tr!("扩展名列表不能为空"));
     }
 
-    println!("文件扩展名: {}", allowed_extensions.join(", "));
+    println!("{}", tr!("文件扩展名: {}", allowed_extensions.join(", ")));
     println!();
 
     // 使用函数式编程风格收集符合条件的文件
+    let mut skipped_links = 0;
     let files_to_process: Vec<walkdir::DirEntry> = WalkDir::new(&args.source)
+        .follow_links(args.follow_symlinks)
         .into_iter()
         .filter_entry(|e| {
             let name = e.file_name().to_string_lossy();
@@ -214,7 +296,14 @@ pub async fn run(args: HashCopyArgs) -> anyhow::Result<()> {
             !name.starts_with('.')
         })
         .filter_map(Result::ok) // 忽略遍历错误
-        .filter(|entry| entry.file_type().is_file()) // 只要文件
+        .filter(|entry| {
+            // 不跟随链接时，符号链接的类型是链接本身，计数后跳过
+            if entry.file_type().is_symlink() {
+                skipped_links += 1;
+                return false;
+            }
+            entry.file_type().is_file() // 只要文件
+        })
         .filter_map(|entry| {
             // 检查文件扩展名（不带点，小写）
             let ext = get_file_extension(entry.path());
@@ -227,13 +316,69 @@ pub async fn run(args: HashCopyArgs) -> anyhow::Result<()> {
         })
         .collect();
 
-    // 处理收集到的文件，遇到失败直接返回错误
+    stats::add(Counter::Scanned, files_to_process.len() as u64);
+
+    if skipped_links > 0 {
+        println!(
+            "{}",
+            tr!(
+                "跳过符号链接: {} 个（使用 --follow-symlinks 处理链接指向的文件）",
+                skipped_links
+            )
+        );
+        println!();
+    }
+
+    // dry-run 模式只计算哈希并输出执行计划
+    if args.dry_run {
+        let mut operations = Vec::new();
+        for entry in &files_to_process {
+            let target_path = hashed_target_path(entry.path(), &args.target)
+                .await
+                .with_context(|| tr!("处理 {} 失败", entry.path().display()))?;
+            // 目标已存在的文件会被跳过
+            if target_path.exists() {
+                continue;
+            }
+            operations.push(Operation::new(
+                OperationKind::Copy,
+                entry.path(),
+                Some(&target_path),
+            ));
+            if args.move_after_copy {
+                operations.push(Operation::new(OperationKind::Trash, entry.path(), None));
+            }
+        }
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    // 移动模式下确认源文件的删除(目标已存在的文件会被跳过,不会删除)
+    if args.move_after_copy {
+        let sources: Vec<PathBuf> = files_to_process
+            .iter()
+            .map(|entry| entry.path().to_path_buf())
+            .collect();
+        if !confirm_destructive("复制后移动到回收站", &sources, args.yes)? {
+            return Ok(());
+        }
+    }
+
+    // 处理收集到的文件，默认遇到失败直接返回错误
+    let mut failures = Failures::default();
     for entry in files_to_process {
-        process_file(entry.path(), &args.target, args.move_after_copy)
+        if let Err(e) = process_file(entry.path(), &args.target, args.move_after_copy)
             .await
-            .with_context(|| format!("处理 {} 失败", entry.path().display()))?;
+            .with_context(|| tr!("处理 {} 失败", entry.path().display()))
+        {
+            if !args.keep_going {
+                return Err(e);
+            }
+            failures.record(entry.path(), e);
+        }
     }
+    failures.finish()?;
 
-    println!("操作成功完成！");
+    println!("{}", tr!("操作成功完成！"));
     Ok(())
 }
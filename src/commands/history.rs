@@ -0,0 +1,186 @@
+//! # 执行历史工具 (history)
+//!
+//! 查询每次命令调用的执行记录（子命令、参数、开始时间、耗时、退出码、运行统计），
+//! 便于事后确认批量删除、移动等操作何时以什么参数执行过。
+//! 记录由主函数写入，密码等敏感参数已替换为 `***`。
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::history::{HistoryEntry, history_path, read_history};
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use chrono::{DateTime, Local};
+use clap::Args;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "history")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "查询命令执行历史",
+    long_about = "列出最近的命令执行记录，包括开始时间、耗时、退出状态和参数（敏感参数已脱敏）。可按子命令或失败状态筛选，或以 JSON Lines 格式输出。history 命令本身不会被记录。"
+)]
+pub struct HistoryArgs {
+    /// 显示的记录数量
+    #[arg(
+        short = 'n',
+        long,
+        value_name = "N",
+        default_value_t = 20,
+        help = "显示最近 N 条记录",
+        long_help = "按时间倒序显示最近 N 条匹配的记录，默认 20。设为 0 时显示所有记录。"
+    )]
+    pub limit: usize,
+
+    /// 按子命令筛选
+    #[arg(
+        short = 'c',
+        long,
+        value_name = "COMMAND",
+        help = "只显示指定子命令的记录",
+        long_help = "只显示指定子命令的记录，例如 batch-compress。"
+    )]
+    pub command: Option<String>,
+
+    /// 只显示失败的记录
+    #[arg(
+        long,
+        help = "只显示失败或被中断的记录",
+        long_help = "只显示退出码不为 0 的记录，包括出错和按 Ctrl+C 中断的调用。"
+    )]
+    pub failed: bool,
+
+    /// 以 JSON Lines 格式输出
+    #[arg(
+        long,
+        conflicts_with = "clear",
+        help = "以 JSON Lines 格式输出",
+        long_help = "每行输出一条 JSON 记录，字段为 command、args、started、duration、exit_code、error 和 stats（运行统计），便于脚本处理。"
+    )]
+    pub json: bool,
+
+    /// 清空历史记录
+    #[arg(
+        long,
+        help = "清空历史记录",
+        long_help = "删除历史文件中的所有记录，执行前要求确认。"
+    )]
+    pub clear: bool,
+
+    /// 跳过确认
+    #[arg(
+        short = 'y',
+        long,
+        requires = "clear",
+        help = "清空时跳过确认",
+        long_help = "配合 --clear 使用，跳过确认直接删除历史文件。"
+    )]
+    pub yes: bool,
+}
+
+/// 输出一条记录
+fn print_entry(entry: &HistoryEntry) {
+    let started = DateTime::parse_from_rfc3339(&entry.started)
+        .map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| entry.started.clone());
+    let status = match entry.exit_code {
+        0 => "✓".to_string(),
+        code => format!("✗ {}", code),
+    };
+    println!(
+        "{}  {:>8.1}s  {}  scripts {}",
+        started,
+        entry.duration,
+        status,
+        entry.args.join(" ")
+    );
+    let stats = &entry.stats;
+    if !stats.is_empty() {
+        println!(
+            "{}",
+            tr!(
+                "  扫描 {}，处理 {}，删除 {}，失败 {}，读取 {}，写入 {}",
+                stats.scanned,
+                stats.processed,
+                stats.deleted,
+                stats.failed,
+                ByteSize(stats.bytes_in),
+                ByteSize(stats.bytes_out)
+            )
+        );
+    }
+    if let Some(error) = &entry.error {
+        println!("  {}", error);
+    }
+}
+
+/// 命令执行函数
+///
+/// - 指定 `--clear` 时确认后删除历史文件
+/// - 否则按条件筛选记录，按时间倒序输出最近的记录
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 查询或清空成功
+/// * `Err(anyhow::Error)` - 读取或删除历史文件失败
+pub async fn run(args: HistoryArgs) -> Result<()> {
+    let path = history_path();
+
+    if args.clear {
+        if !path.exists() {
+            println!("{}", tr!("没有历史记录"));
+            return Ok(());
+        }
+        if !confirm_destructive("永久删除", std::slice::from_ref(&path), args.yes)? {
+            return Ok(());
+        }
+        std::fs::remove_file(&path).with_context(|| tr!("删除历史文件失败: {}", path.display()))?;
+        println!("{}", tr!("已清空历史记录"));
+        return Ok(());
+    }
+
+    let mut entries: Vec<HistoryEntry> = read_history()?
+        .into_iter()
+        .filter(|entry| args.command.as_ref().is_none_or(|c| &entry.command == c))
+        .filter(|entry| !args.failed || entry.exit_code != 0)
+        .collect();
+
+    // 只保留最近的记录，按时间倒序输出
+    entries.reverse();
+    if args.limit > 0 {
+        entries.truncate(args.limit);
+    }
+
+    if args.json {
+        for entry in &entries {
+            println!("{}", serde_json::to_string(entry)?);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!("{} 执行历史工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("历史文件: {}", path.display()));
+    println!();
+
+    if entries.is_empty() {
+        println!("{}", tr!("没有匹配的记录"));
+        return Ok(());
+    }
+
+    for entry in &entries {
+        print_entry(entry);
+    }
+
+    Ok(())
+}
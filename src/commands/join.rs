@@ -0,0 +1,196 @@
+//! # 文件合并工具 (join)
+//!
+//! 根据 `scripts split` 生成的 `<文件名>.manifest` 清单，
+//! 校验每个分块的 Blake3 哈希后按顺序合并，并校验合并结果与原文件一致。
+
+use crate::commands::split::manifest_path;
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::hash::{calculate_file_hash, encode_hash, read_manifest};
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "join")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "校验并合并分割后的文件",
+    long_about = "读取 <文件名>.manifest 清单，校验每个分块的 Blake3 哈希后按顺序合并为原文件，并校验合并结果的哈希。合并结果先写入输出文件旁的临时文件，校验通过后才重命名为输出文件。"
+)]
+pub struct JoinArgs {
+    /// 原文件路径
+    #[arg(
+        value_name = "PREFIX",
+        help = "原文件路径（分块的公共前缀）",
+        long_help = "分割前的文件路径，例如 ./disk.iso，对应的分块为 ./disk.iso.001 等，清单为 ./disk.iso.manifest。"
+    )]
+    pub prefix: PathBuf,
+
+    /// 输出文件
+    #[arg(
+        short = 'o',
+        long,
+        value_name = "FILE",
+        help = "合并后的输出文件",
+        long_help = "合并结果的保存路径，默认为原文件路径。输出文件已存在时报错。"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+/// 将分块追加到输出文件，同时计算整体哈希
+async fn append_chunk(
+    chunk_path: &Path,
+    output: &mut tokio::fs::File,
+    hasher: &mut blake3::Hasher,
+) -> Result<u64> {
+    let mut chunk = tokio::fs::File::open(chunk_path)
+        .await
+        .with_context(|| tr!("打开分块失败: {}", chunk_path.display()))?;
+    let mut buffer = vec![0; 65536];
+    let mut total = 0;
+
+    loop {
+        let n = chunk
+            .read(&mut buffer)
+            .await
+            .with_context(|| tr!("读取分块失败: {}", chunk_path.display()))?;
+        if n == 0 {
+            break;
+        }
+        output.write_all(&buffer[..n]).await?;
+        hasher.update(&buffer[..n]);
+        total += n as u64;
+    }
+
+    Ok(total)
+}
+
+/// 校验所有分块并合并
+///
+/// 先写入输出文件旁的临时文件，合并结果校验通过后再重命名为输出文件。
+async fn join_chunks(
+    chunks: &[(PathBuf, String)],
+    output_path: &Path,
+    expected_hash: &str,
+) -> Result<()> {
+    for (chunk_path, hash) in chunks {
+        if &calculate_file_hash(chunk_path).await? != hash {
+            stats::add(Counter::Failed, 1);
+            return Err(ScriptError::new(
+                ErrorKind::Verification,
+                tr!("分块校验失败: {}", chunk_path.display()),
+            )
+            .into());
+        }
+        println!("{}", tr!("✓ 分块校验通过: {}", chunk_path.display()));
+    }
+
+    let temp = TempPath::beside(output_path)?;
+    let mut output = tokio::fs::File::create(temp.path())
+        .await
+        .with_context(|| tr!("创建输出文件失败: {}", output_path.display()))?;
+    let mut hasher = blake3::Hasher::new();
+    let mut total = 0;
+    for (chunk_path, _) in chunks {
+        let size = append_chunk(chunk_path, &mut output, &mut hasher).await?;
+        stats::add(Counter::Processed, 1);
+        stats::add(Counter::BytesIn, size);
+        total += size;
+    }
+    output.flush().await?;
+    drop(output);
+
+    if encode_hash(&hasher.finalize()) != expected_hash {
+        stats::add(Counter::Failed, 1);
+        return Err(ScriptError::new(
+            ErrorKind::Verification,
+            tr!("合并结果校验失败: {}", output_path.display()),
+        )
+        .into());
+    }
+    temp.persist(output_path)?;
+    stats::add(Counter::BytesOut, total);
+
+    println!();
+    println!(
+        "{}",
+        tr!("合并完成: {} ({})", output_path.display(), ByteSize(total))
+    );
+    Ok(())
+}
+
+/// 命令执行函数
+///
+/// 1. 读取清单，区分分块条目和原文件条目
+/// 2. 校验每个分块的哈希
+/// 3. 按分块序号合并，并校验合并结果的哈希
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 合并并校验成功
+/// * `Err(anyhow::Error)` - 清单无效、分块缺失或校验失败
+pub async fn run(args: JoinArgs) -> Result<()> {
+    let file_name = args
+        .prefix
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .with_context(|| tr!("无效的文件名"))?;
+    let dir = args
+        .prefix
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+
+    let manifest_file = manifest_path(&args.prefix);
+    let mut entries = read_manifest(&manifest_file)?;
+    let expected_hash = entries
+        .remove(&file_name)
+        .with_context(|| tr!("清单中缺少原文件哈希: {}", manifest_file.display()))?;
+
+    // 按分块序号排序（超过 999 个分块时序号位数会增加，不能按名称排序）
+    let mut numbered: Vec<(u64, String, String)> = entries
+        .into_iter()
+        .map(|(name, hash)| {
+            let number = name
+                .rsplit_once('.')
+                .and_then(|(_, n)| n.parse::<u64>().ok())
+                .with_context(|| tr!("无效的分块名称: {}", name))?;
+            Ok((number, name, hash))
+        })
+        .collect::<Result<_>>()?;
+    numbered.sort_by_key(|(number, _, _)| *number);
+    let chunks: Vec<(PathBuf, String)> = numbered
+        .into_iter()
+        .map(|(_, name, hash)| (dir.join(name), hash))
+        .collect();
+    if chunks.is_empty() {
+        anyhow::bail!(tr!("清单中没有分块: {}", manifest_file.display()));
+    }
+
+    let output_path = args.output.unwrap_or(args.prefix.clone());
+    if output_path.exists() {
+        anyhow::bail!(tr!("输出文件已存在: {}", output_path.display()));
+    }
+
+    println!(
+        "{}",
+        tr!("{} 文件合并工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("清单: {}", manifest_file.display()));
+    println!("{}", tr!("分块数: {}", chunks.len()));
+    println!();
+    stats::add(Counter::Scanned, chunks.len() as u64);
+
+    // 任一步骤失败或中断时临时文件会被删除，不会留下不完整的输出文件
+    join_chunks(&chunks, &output_path, &expected_hash).await
+}
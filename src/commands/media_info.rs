@@ -0,0 +1,216 @@
+//! 媒体信息报告命令模块
+//!
+//! 本模块使用 ffprobe 读取目录中媒体文件的编码、分辨率、时长、码率和容器信息，
+//! 以表格或 JSON 形式输出，可按编码过滤以找出需要转码的文件。
+//!
+//! # 功能特性
+//!
+//! - 递归扫描目录,最多支持 3 层嵌套
+//! - 同时支持视频和音频文件
+//! - 支持按视频或音频编码过滤 (例如 `--codec h264`)
+//! - 支持 JSON 输出,便于其他工具处理
+
+use crate::utils::i18n::{tr, translate};
+use crate::utils::media::{MediaInfo, collect_media_files, ensure_ffmpeg, probe_media};
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// 媒体信息命令行参数
+#[derive(Args, Debug)]
+#[command(name = "media_info")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "生成媒体文件信息报告",
+    long_about = "扫描指定目录(最多嵌套三层)下的视频和音频文件,使用 ffprobe 读取编码、分辨率、时长、码率和容器格式,以表格或 JSON 形式输出。可通过 --codec 过滤出指定编码的文件,用于查找需要转码的候选文件。"
+)]
+pub struct MediaInfoArgs {
+    /// 要扫描的目录
+    #[arg(
+        value_name = "DIR",
+        help = "要扫描的目录",
+        long_help = "要扫描的目录，工具会扫描该目录及其子目录（最多三层）中的视频和音频文件。"
+    )]
+    pub dir: PathBuf,
+
+    /// 按编码过滤
+    #[arg(
+        short = 'c',
+        long,
+        value_name = "CODEC",
+        help = "仅显示指定编码的文件",
+        long_help = "仅显示视频或音频编码与之匹配的文件（大小写不敏感），例如 h264、hevc、aac。"
+    )]
+    pub codec: Option<String>,
+
+    /// 以 JSON 格式输出
+    #[arg(
+        long,
+        help = "以 JSON 格式输出",
+        long_help = "以 JSON 数组形式输出报告，适合由其他工具处理。"
+    )]
+    pub json: bool,
+
+    /// 未安装 ffmpeg 时下载静态构建
+    #[arg(
+        long,
+        help = "未安装 ffmpeg 时自动下载静态构建",
+        long_help = "未检测到 ffmpeg 时，下载适用于当前系统的 ffmpeg 静态构建到应用数据目录并使用，而不是通过 winget 安装。"
+    )]
+    pub download_ffmpeg: bool,
+}
+
+/// 将秒数格式化为 `HH:MM:SS`
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total / 3600,
+        (total % 3600) / 60,
+        total % 60
+    )
+}
+
+/// 判断媒体信息是否匹配编码过滤条件
+fn matches_codec(info: &MediaInfo, codec: &str) -> bool {
+    [&info.video_codec, &info.audio_codec]
+        .into_iter()
+        .flatten()
+        .any(|c| c.eq_ignore_ascii_case(codec))
+}
+
+/// 表格列宽（显示宽度）：视频编码、音频编码、分辨率、时长、码率、容器
+const COLUMN_WIDTHS: [usize; 6] = [10, 10, 11, 10, 12, 20];
+
+/// 按显示宽度右侧补齐空格（中文字符按 2 个宽度计算）
+fn pad(text: &str, width: usize) -> String {
+    let display_width: usize = text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum();
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(width.saturating_sub(display_width))
+    )
+}
+
+/// 打印一行表格
+fn print_row(cells: [&str; 6], path: &str) {
+    let line: String = cells
+        .iter()
+        .zip(COLUMN_WIDTHS)
+        .map(|(cell, width)| pad(cell, width))
+        .collect();
+    println!("{}{}", line, path);
+}
+
+/// 以表格行形式打印单个文件的信息
+fn print_info(info: &MediaInfo, base_dir: &Path) {
+    let display_path = info.path.strip_prefix(base_dir).unwrap_or(&info.path);
+    let resolution = match (info.width, info.height) {
+        (Some(w), Some(h)) => format!("{}x{}", w, h),
+        _ => "-".to_string(),
+    };
+    let duration = info
+        .duration
+        .map(format_duration)
+        .unwrap_or("-".to_string());
+    let bit_rate = info
+        .bit_rate
+        .map(|b| format!("{} kb/s", b / 1000))
+        .unwrap_or("-".to_string());
+
+    print_row(
+        [
+            info.video_codec.as_deref().unwrap_or("-"),
+            info.audio_codec.as_deref().unwrap_or("-"),
+            &resolution,
+            &duration,
+            &bit_rate,
+            &info.container,
+        ],
+        &display_path.display().to_string(),
+    );
+}
+
+/// 执行媒体信息命令
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回
+///
+/// 执行成功返回 `Ok(())`,失败返回错误信息
+///
+/// # 错误
+///
+/// - 当目录不存在或无法访问时返回错误
+/// - 当 JSON 序列化失败时返回错误
+pub async fn run(args: MediaInfoArgs) -> Result<()> {
+    // 确保 ffmpeg (包含 ffprobe) 可用
+    ensure_ffmpeg(args.download_ffmpeg)?;
+
+    let dir = args
+        .dir
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", args.dir.display()))?;
+
+    if !dir.is_dir() {
+        anyhow::bail!(tr!("路径必须是目录: {}", dir.display()));
+    }
+
+    let media_files = collect_media_files(&dir, 3);
+
+    // 逐个读取媒体信息,读取失败的文件输出到 stderr 并跳过
+    stats::add(Counter::Scanned, media_files.len() as u64);
+    let mut infos = Vec::new();
+    for file in &media_files {
+        match probe_media(file) {
+            Ok(info) => {
+                stats::add(Counter::Processed, 1);
+                infos.push(info);
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                eprintln!("{}", tr!("跳过 {}: {}", file.display(), format!("{:#}", e)));
+            }
+        }
+    }
+
+    if let Some(codec) = &args.codec {
+        infos.retain(|info| matches_codec(info, codec));
+    }
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&infos).with_context(|| tr!("序列化 JSON 失败"))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!("{} 媒体信息报告 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("目录: {}", dir.display()));
+    if let Some(codec) = &args.codec {
+        println!("{}", tr!("编码过滤: {}", codec));
+    }
+    println!();
+
+    print_row(
+        ["视频编码", "音频编码", "分辨率", "时长", "码率", "容器"].map(translate),
+        translate("文件"),
+    );
+    for info in &infos {
+        print_info(info, &dir);
+    }
+    println!();
+
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("扫描文件: {}", media_files.len()));
+    println!("{}", tr!("匹配文件: {}", infos.len()));
+
+    Ok(())
+}
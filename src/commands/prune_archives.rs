@@ -0,0 +1,314 @@
+//! # 压缩包清理工具 (prune_archives)
+//!
+//! 按保留策略清理目录中过期的 7z 压缩包，配合 batch-compress 的命名模板和 backup
+//! 组成完整的定期备份流程。
+//!
+//! ## 分组规则
+//!
+//! 文件名（去掉 `.7z` 或 `.7z.NNN`）中第一个 `-YYYYMMDD` 日期之前的部分作为基础名称，
+//! 基础名称相同的压缩包为同一组，例如：
+//!
+//! - `docs-20260101-3xK9pQ2a.7z`（batch-compress `{name}-{date}-{hash8}`）
+//! - `docs-20260101-120000-3xK9pQ2a.7z`（backup 快照）
+//!
+//! 都属于 `docs` 组。文件名中的日期（和时间）作为创建时间；不含日期的压缩包单独成组，
+//! 使用修改时间。同一压缩包的所有分卷作为一个整体保留或清理。
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use clap::Args;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "prune-archives")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "按保留策略清理过期的压缩包",
+    long_about = "扫描目录中的 .7z 压缩包（包括分卷），按文件名中日期之前的基础名称分组，每组只保留最新的 --keep 个或最近 --keep-days 天内的压缩包，其余移动到回收站。同时设置两者时，满足任一条件的压缩包都会保留。"
+)]
+pub struct PruneArchivesArgs {
+    /// 压缩包所在目录
+    #[arg(
+        short = 'd',
+        long,
+        value_name = "DIR",
+        help = "压缩包所在目录",
+        long_help = "压缩包所在的目录，只扫描该目录的直接子文件，不递归子目录。"
+    )]
+    pub dir: PathBuf,
+
+    /// 每组保留最新的 N 个压缩包
+    #[arg(
+        long,
+        value_name = "N",
+        help = "每组保留最新的 N 个压缩包",
+        long_help = "按创建时间排序，每组只保留最新的 N 个压缩包，其余移动到回收站。与 --keep-days 同时使用时，满足任一条件的压缩包都会保留。"
+    )]
+    pub keep: Option<usize>,
+
+    /// 保留最近 N 天的压缩包
+    #[arg(
+        long,
+        value_name = "DAYS",
+        help = "保留最近 N 天的压缩包",
+        long_help = "将创建时间早于 N 天前的压缩包移动到回收站。与 --keep 同时使用时，满足任一条件的压缩包都会保留。"
+    )]
+    pub keep_days: Option<u32>,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际删除",
+        long_help = "列出每组的保留情况和将被移动到回收站的文件及其大小，但不实际删除。"
+    )]
+    pub dry_run: bool,
+
+    /// 跳过删除确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除确认",
+        long_help = "默认会列出将被删除的压缩包并要求确认，开启后跳过确认直接执行，适合计划任务。"
+    )]
+    pub yes: bool,
+}
+
+/// 一个压缩包（分卷压缩包包含多个文件）
+#[derive(Debug)]
+struct Archive {
+    /// 基础名称，用于分组
+    base: String,
+    /// 创建时间
+    created: NaiveDateTime,
+    /// 压缩包文件，分卷时为所有分卷
+    files: Vec<PathBuf>,
+}
+
+/// 获取压缩包名称（去掉 `.7z` 或 `.7z.NNN`），不是 7z 压缩包时返回 `None`
+fn archive_stem(file_name: &str) -> Option<&str> {
+    if let Some(stem) = file_name.strip_suffix(".7z") {
+        return Some(stem);
+    }
+    let (stem, volume) = file_name.rsplit_once('.')?;
+    if volume.len() >= 3 && volume.bytes().all(|b| b.is_ascii_digit()) {
+        stem.strip_suffix(".7z")
+    } else {
+        None
+    }
+}
+
+/// 收集目录中的压缩包
+///
+/// # 参数
+///
+/// * `dir` - 压缩包所在目录
+///
+/// # 返回值
+///
+/// * `Ok(Vec<Archive>)` - 压缩包列表
+/// * `Err(anyhow::Error)` - 读取目录失败
+fn collect_archives(dir: &Path) -> Result<Vec<Archive>> {
+    // <基础名称>-<YYYYMMDD>[-<HHMMSS>][-<其他>]
+    let pattern = Regex::new(r"^(.+?)-(\d{8})(?:-(\d{6}))?(?:-.*)?$")?;
+
+    let mut archives: BTreeMap<String, Archive> = BTreeMap::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| tr!("无法读取目录: {}", dir.display()))?
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(stem) = archive_stem(&file_name) else {
+            continue;
+        };
+
+        let modified = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .map(|time| DateTime::<Local>::from(time).naive_local())
+            .unwrap_or_default();
+
+        // 文件名中的日期优先，无法解析时使用修改时间
+        let named = pattern.captures(stem).and_then(|captures| {
+            let date = NaiveDate::parse_from_str(&captures[2], "%Y%m%d").ok()?;
+            let time = match captures.get(3) {
+                Some(time) => NaiveTime::parse_from_str(time.as_str(), "%H%M%S").ok()?,
+                None => NaiveTime::MIN,
+            };
+            Some((captures[1].to_string(), date.and_time(time)))
+        });
+        let (base, created) = named.unwrap_or_else(|| (stem.to_string(), modified));
+
+        archives
+            .entry(stem.to_string())
+            .or_insert_with(|| Archive {
+                base,
+                created,
+                files: Vec::new(),
+            })
+            .files
+            .push(path);
+    }
+
+    Ok(archives
+        .into_values()
+        .map(|mut archive| {
+            archive.files.sort();
+            archive
+        })
+        .collect())
+}
+
+/// 命令执行函数
+///
+/// 1. 收集目录中的压缩包并按基础名称分组
+/// 2. 每组按创建时间从新到旧排序，根据 `--keep` 和 `--keep-days` 筛选过期的压缩包
+/// 3. 输出计划或确认后移动到回收站
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 清理完成
+/// * `Err(anyhow::Error)` - 参数无效或目录无法访问
+pub async fn run(args: PruneArchivesArgs) -> Result<()> {
+    if args.keep.is_none() && args.keep_days.is_none() {
+        anyhow::bail!(tr!("必须至少指定 --keep 或 --keep-days 之一"));
+    }
+
+    let dir = args
+        .dir
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", args.dir.display()))?;
+
+    if !dir.is_dir() {
+        anyhow::bail!(tr!("路径必须是目录: {}", dir.display()));
+    }
+
+    println!(
+        "{}",
+        tr!("{} 压缩包清理工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("目录: {}", dir.display()));
+    if let Some(keep) = args.keep {
+        println!("{}", tr!("每组保留数量: {}", keep));
+    }
+    if let Some(days) = args.keep_days {
+        println!("{}", tr!("保留天数: {}", days));
+    }
+    println!();
+
+    let archives = collect_archives(&dir)?;
+    stats::add(Counter::Scanned, archives.len() as u64);
+
+    let mut groups: BTreeMap<String, Vec<Archive>> = BTreeMap::new();
+    for archive in archives {
+        groups
+            .entry(archive.base.clone())
+            .or_default()
+            .push(archive);
+    }
+
+    if groups.is_empty() {
+        println!("{}", tr!("目录中没有 7z 压缩包"));
+        return Ok(());
+    }
+
+    // 天数过大导致日期溢出时没有截止时间，所有压缩包都按时间保留
+    let cutoff = args.keep_days.map(|days| {
+        TimeDelta::try_days(days.into())
+            .and_then(|delta| Local::now().naive_local().checked_sub_signed(delta))
+    });
+
+    let mut expired: Vec<PathBuf> = Vec::new();
+    for (base, archives) in &mut groups {
+        // 按创建时间从新到旧排序
+        archives.sort_by_key(|archive| std::cmp::Reverse(archive.created));
+
+        let mut pruned = 0;
+        for (index, archive) in archives.iter().enumerate() {
+            let kept_by_count = args.keep.is_some_and(|n| index < n);
+            let kept_by_age =
+                cutoff.is_some_and(|cutoff| cutoff.is_none_or(|cutoff| archive.created >= cutoff));
+            if !kept_by_count && !kept_by_age {
+                pruned += 1;
+                expired.extend(archive.files.iter().cloned());
+            }
+        }
+        println!(
+            "{}",
+            tr!(
+                "{}: {} 个压缩包，保留 {} 个，清理 {} 个",
+                base,
+                archives.len(),
+                archives.len() - pruned,
+                pruned
+            )
+        );
+    }
+    println!();
+
+    if expired.is_empty() {
+        println!("{}", tr!("没有需要清理的压缩包"));
+        return Ok(());
+    }
+
+    let operations: Vec<Operation> = expired
+        .iter()
+        .map(|path| Operation::new(OperationKind::Trash, path, None))
+        .collect();
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    if !confirm_destructive("移动到回收站", &expired, args.yes)? {
+        return Ok(());
+    }
+
+    let mut freed = 0;
+    let mut failed = 0;
+    for op in &operations {
+        match trash::delete(&op.source) {
+            Ok(_) => {
+                freed += op.size;
+                stats::add(Counter::Deleted, 1);
+                println!(
+                    "{}",
+                    tr!("✓ 已将压缩包移动到回收站: {}", op.source.display())
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                stats::add(Counter::Failed, 1);
+                println!(
+                    "{}",
+                    tr!("✗ 移动到回收站失败: {} - {}", op.source.display(), e)
+                );
+            }
+        }
+    }
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("清理: {}", operations.len() - failed));
+    println!("{}", tr!("失败: {}", failed));
+    println!("{}", tr!("释放空间: {}", ByteSize(freed)));
+
+    Ok(())
+}
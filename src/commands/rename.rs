@@ -0,0 +1,651 @@
+//! # 批量重命名工具 (rename)
+//!
+//! 按模板、正则替换和大小写规则批量重命名目录中的文件，
+//! 执行前输出重命名预览表，并自动处理名称冲突。
+//!
+//! ## 处理顺序
+//!
+//! 1. 按模板生成文件名主干（不含扩展名）
+//! 2. 对主干执行正则查找替换
+//! 3. 按大小写规则转换文件名（含扩展名）
+//! 4. 与已有文件或其他新名称冲突时按冲突策略处理
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::filesystem::build_globset;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use clap::{Args, ValueEnum};
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// 大小写规则
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum CaseMode {
+    /// 保持原样
+    #[default]
+    Keep,
+    /// 全部小写
+    Lower,
+    /// 全部大写
+    Upper,
+}
+
+/// 名称冲突处理策略
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum ConflictMode {
+    /// 在文件名后追加 -1、-2 等后缀
+    #[default]
+    Suffix,
+    /// 跳过冲突的文件
+    Skip,
+}
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "rename")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "按模板和规则批量重命名文件",
+    long_about = "批量重命名目录中的文件（不递归）。支持模板（{name}、{ext}、{date}、{counter}）、正则查找替换和大小写转换，执行前输出重命名预览表并要求确认。与已有文件或其他新名称冲突时追加后缀或跳过。"
+)]
+pub struct RenameArgs {
+    /// 要处理的目录
+    #[arg(
+        short = 'd',
+        long,
+        default_value = ".",
+        value_name = "DIR",
+        help = "要处理的目录",
+        long_help = "只处理该目录的直接子文件（不递归）。默认当前目录 (.)。"
+    )]
+    pub dir: PathBuf,
+
+    /// 文件名模板
+    #[arg(
+        short = 't',
+        long,
+        value_name = "TEMPLATE",
+        help = "文件名模板",
+        long_help = "生成新文件名主干（不含扩展名，扩展名自动保留）。可用占位符：{name} 原文件名主干、{ext} 扩展名、{date} 修改日期 (YYYYMMDD)、{counter} 序号。例如 \"{date}-{counter}-{name}\"。"
+    )]
+    pub template: Option<String>,
+
+    /// 正则查找模式
+    #[arg(
+        short = 'f',
+        long,
+        value_name = "REGEX",
+        requires = "replace",
+        help = "正则查找模式",
+        long_help = "在文件名主干中查找的正则表达式，需与 --replace 一起使用。"
+    )]
+    pub find: Option<String>,
+
+    /// 正则替换内容
+    #[arg(
+        short = 'r',
+        long,
+        value_name = "REPLACEMENT",
+        requires = "find",
+        help = "正则替换内容",
+        long_help = "替换 --find 匹配到的内容，支持 $1、${name} 等捕获组引用。"
+    )]
+    pub replace: Option<String>,
+
+    /// 大小写规则
+    #[arg(
+        short = 'c',
+        long,
+        value_enum,
+        default_value_t = CaseMode::Keep,
+        help = "大小写规则",
+        long_help = "转换整个文件名（含扩展名）的大小写：keep 保持原样、lower 全部小写、upper 全部大写。"
+    )]
+    pub case: CaseMode,
+
+    /// 仅处理匹配的文件
+    #[arg(
+        short = 'i',
+        long,
+        value_name = "GLOB",
+        value_delimiter = ',',
+        help = "仅处理匹配的文件（glob，逗号分隔）",
+        long_help = "仅处理文件名匹配任一 glob 模式的文件，例如 \"*.jpg,*.png\"。"
+    )]
+    pub include: Vec<String>,
+
+    /// 序号起始值
+    #[arg(
+        long,
+        default_value_t = 1,
+        value_name = "N",
+        help = "{counter} 的起始值",
+        long_help = "模板中 {counter} 的起始值，文件按原文件名排序后依次编号。默认 1。"
+    )]
+    pub start: usize,
+
+    /// 序号宽度
+    #[arg(
+        long,
+        default_value_t = 3,
+        value_name = "WIDTH",
+        help = "{counter} 的最小宽度",
+        long_help = "模板中 {counter} 的最小位数，不足时补零。默认 3。"
+    )]
+    pub width: usize,
+
+    /// 冲突处理策略
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ConflictMode::Suffix,
+        help = "名称冲突处理策略",
+        long_help = "新名称与已有文件或其他新名称冲突时：suffix 追加 -1、-2 等后缀，skip 跳过该文件。"
+    )]
+    pub on_conflict: ConflictMode,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际重命名",
+        long_help = "列出每个文件的原路径、新路径和大小后退出，不修改任何文件。"
+    )]
+    pub dry_run: bool,
+
+    /// 跳过确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过确认",
+        long_help = "默认会在预览后要求确认，开启后跳过确认直接执行，适合自动化场景。"
+    )]
+    pub yes: bool,
+}
+
+/// 单个文件的重命名计划
+#[derive(Debug)]
+struct RenamePlan {
+    /// 源文件路径
+    source: PathBuf,
+    /// 新文件名
+    new_name: String,
+}
+
+/// 将文件名拆分为主干和扩展名（扩展名不带点）
+fn split_name(file_name: &str) -> (&str, &str) {
+    match file_name.rfind('.') {
+        Some(index) if index > 0 => (&file_name[..index], &file_name[index + 1..]),
+        _ => (file_name, ""),
+    }
+}
+
+/// 拼接主干和扩展名
+fn join_name(stem: &str, ext: &str) -> String {
+    if ext.is_empty() {
+        stem.to_string()
+    } else {
+        format!("{}.{}", stem, ext)
+    }
+}
+
+/// 按模板、正则和大小写规则生成新文件名
+fn build_name(
+    path: &Path,
+    counter: usize,
+    args: &RenameArgs,
+    find: Option<&Regex>,
+) -> Result<String> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| tr!("无效的文件名"))?;
+    let (stem, ext) = split_name(file_name);
+
+    let mut new_stem = match &args.template {
+        Some(template) => {
+            let modified: DateTime<Local> = path
+                .metadata()
+                .and_then(|m| m.modified())
+                .with_context(|| tr!("读取修改时间失败: {}", path.display()))?
+                .into();
+            template
+                .replace("{name}", stem)
+                .replace("{ext}", ext)
+                .replace("{date}", &modified.format("%Y%m%d").to_string())
+                .replace(
+                    "{counter}",
+                    &format!("{:0width$}", counter, width = args.width),
+                )
+        }
+        None => stem.to_string(),
+    };
+
+    if let (Some(find), Some(replace)) = (find, &args.replace) {
+        new_stem = find.replace_all(&new_stem, replace.as_str()).to_string();
+    }
+
+    let new_name = join_name(&new_stem, ext);
+    Ok(match args.case {
+        CaseMode::Keep => new_name,
+        CaseMode::Lower => new_name.to_lowercase(),
+        CaseMode::Upper => new_name.to_uppercase(),
+    })
+}
+
+/// 检查新文件名是否可以作为同一目录下的文件名
+///
+/// 不能为空、`.` 或 `..`，也不能包含路径分隔符，否则文件会被移出目录或无法重命名。
+fn validate_name(new_name: &str) -> bool {
+    !new_name.is_empty() && new_name != "." && new_name != ".." && !new_name.contains(['/', '\\'])
+}
+
+/// 在已占用的名称中为新名称追加数字后缀直到不冲突
+///
+/// 名称比较不区分大小写，以兼容 Windows 和 macOS 的文件系统。
+fn resolve_conflict(new_name: &str, occupied: &HashSet<String>) -> String {
+    let (stem, ext) = split_name(new_name);
+    (1..)
+        .map(|n| join_name(&format!("{}-{}", stem, n), ext))
+        .find(|candidate| !occupied.contains(&candidate.to_lowercase()))
+        .unwrap()
+}
+
+/// 获取路径中的文件名
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// 为每个文件分配不冲突的新名称
+///
+/// 不移动的文件（新名称与原名相同，或按 skip 策略跳过）保留原名称，其他文件不能占用。
+/// 跳过的文件原名称已分配给前面的文件时，重新分配所有名称。
+///
+/// # 参数
+///
+/// * `candidates` - (源文件路径, 期望的新文件名)，按处理顺序排列
+/// * `occupied` - 不参与重命名的已有名称（小写）
+/// * `mode` - 冲突处理策略
+///
+/// # 返回值
+///
+/// * `(Vec<RenamePlan>, Vec<(String, String)>)` - 重命名计划，以及跳过的 (原文件名, 新文件名)
+fn assign_names(
+    candidates: &[(PathBuf, String)],
+    occupied: &HashSet<String>,
+    mode: ConflictMode,
+) -> (Vec<RenamePlan>, Vec<(String, String)>) {
+    let mut kept: HashSet<&Path> = candidates
+        .iter()
+        .filter(|(source, new_name)| file_name_of(source) == *new_name)
+        .map(|(source, _)| source.as_path())
+        .collect();
+
+    loop {
+        let mut taken = occupied.clone();
+        taken.extend(
+            kept.iter()
+                .map(|source| file_name_of(source).to_lowercase()),
+        );
+
+        let mut plans = Vec::new();
+        let mut skipped = Vec::new();
+        let mut reassign = false;
+        for (source, desired) in candidates {
+            let old_name = file_name_of(source);
+            if kept.contains(source.as_path()) {
+                if old_name != *desired {
+                    skipped.push((old_name, desired.clone()));
+                }
+                continue;
+            }
+
+            let mut new_name = desired.clone();
+            if taken.contains(&new_name.to_lowercase()) {
+                match mode {
+                    ConflictMode::Suffix => new_name = resolve_conflict(&new_name, &taken),
+                    ConflictMode::Skip => {
+                        kept.insert(source);
+                        skipped.push((old_name.clone(), new_name));
+                        if !taken.insert(old_name.to_lowercase()) {
+                            reassign = true;
+                            break;
+                        }
+                        continue;
+                    }
+                }
+            }
+            taken.insert(new_name.to_lowercase());
+
+            if new_name != old_name {
+                plans.push(RenamePlan {
+                    source: source.clone(),
+                    new_name,
+                });
+            }
+        }
+
+        if !reassign {
+            return (plans, skipped);
+        }
+    }
+}
+
+/// 按相反顺序撤销已完成的重命名
+///
+/// 撤销失败时只输出警告，文件保留在当前名称下。
+async fn rollback(done: &[(PathBuf, PathBuf)]) {
+    for (from, to) in done.iter().rev() {
+        match tokio::fs::rename(to, from).await {
+            Ok(_) => println!("{}", tr!("已撤销: {} -> {}", to.display(), from.display())),
+            Err(e) => println!(
+                "{}",
+                tr!("✗ 撤销失败: {} -> {} - {}", to.display(), from.display(), e)
+            ),
+        }
+    }
+}
+
+/// 依次执行重命名，任一步失败时撤销之前的所有重命名
+///
+/// 目标路径已存在时拒绝覆盖（仅大小写不同的同一文件除外）。
+/// # 参数
+///
+/// * `steps` - 按顺序执行的 (原路径, 新路径)
+///
+/// # 返回值
+///
+/// * `Ok(())` - 全部重命名成功
+/// * `Err(anyhow::Error)` - 某一步失败，已完成的重命名已撤销
+async fn rename_all(steps: &[(PathBuf, PathBuf)]) -> Result<()> {
+    let mut done: Vec<(PathBuf, PathBuf)> = Vec::with_capacity(steps.len());
+    for (from, to) in steps {
+        let case_only = file_name_of(from).to_lowercase() == file_name_of(to).to_lowercase();
+        if !case_only && tokio::fs::symlink_metadata(to).await.is_ok() {
+            stats::add(Counter::Failed, 1);
+            rollback(&done).await;
+            anyhow::bail!(tr!("目标已存在，拒绝覆盖: {}", to.display()));
+        }
+        if let Err(e) = tokio::fs::rename(from, to).await {
+            stats::add(Counter::Failed, 1);
+            rollback(&done).await;
+            return Err(e)
+                .with_context(|| tr!("重命名失败: {} -> {}", from.display(), to.display()));
+        }
+        done.push((from.clone(), to.clone()));
+    }
+    Ok(())
+}
+
+/// 输出重命名预览表
+fn print_preview(plans: &[RenamePlan]) {
+    let width = plans
+        .iter()
+        .filter_map(|plan| plan.source.file_name())
+        .map(|name| name.to_string_lossy().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for plan in plans {
+        let old_name = plan
+            .source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        println!(
+            "  {:<width$}  ->  {}",
+            old_name,
+            plan.new_name,
+            width = width
+        );
+    }
+}
+
+/// 命令执行函数
+///
+/// 1. 收集目录中的文件并按文件名排序
+/// 2. 为每个文件生成新名称并处理冲突
+/// 3. 检查所有新名称，任一无效时不修改任何文件
+/// 4. 输出预览表，确认后重命名；新名称是其他待重命名文件的原名称（链式或循环重命名）时
+///    先改为临时名称，避免互相覆盖；任一步失败时撤销已完成的重命名
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 执行成功
+/// * `Err(anyhow::Error)` - 参数无效、目录无法访问或重命名失败
+pub async fn run(args: RenameArgs) -> Result<()> {
+    if args.template.is_none() && args.find.is_none() && matches!(args.case, CaseMode::Keep) {
+        anyhow::bail!(tr!(
+            "必须至少指定 --template、--find/--replace 或 --case 之一"
+        ));
+    }
+
+    let dir = args
+        .dir
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", args.dir.display()))?;
+
+    let find = args
+        .find
+        .as_deref()
+        .map(Regex::new)
+        .transpose()
+        .with_context(|| tr!("无效的正则表达式"))?;
+    let include = build_globset(&args.include)?;
+
+    // 收集目录中的所有名称和要处理的文件
+    let mut all_names = HashSet::new();
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(&dir)
+        .with_context(|| tr!("无法读取目录: {}", dir.display()))?
+        .filter_map(|e| e.ok())
+    {
+        let name = entry.file_name().to_string_lossy().to_string();
+        all_names.insert(name.to_lowercase());
+
+        let is_file = entry.file_type().map(|t| t.is_file()).unwrap_or(false);
+        let included = include.as_ref().is_none_or(|set| set.is_match(&name));
+        if is_file && !name.starts_with('.') && included {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+
+    // 不参与重命名的名称视为已占用
+    let mut occupied: HashSet<String> = all_names;
+    for file in &files {
+        if let Some(name) = file.file_name() {
+            occupied.remove(&name.to_string_lossy().to_lowercase());
+        }
+    }
+
+    let mut candidates = Vec::with_capacity(files.len());
+    for (index, file) in files.iter().enumerate() {
+        let new_name = build_name(file, args.start + index, &args, find.as_ref())?;
+        if !validate_name(&new_name) {
+            anyhow::bail!(tr!(
+                "无效的新文件名: {} -> \"{}\"（不能为空或包含路径分隔符）",
+                file_name_of(file),
+                new_name
+            ));
+        }
+        candidates.push((file.clone(), new_name));
+    }
+
+    let (plans, skipped) = assign_names(&candidates, &occupied, args.on_conflict);
+    for (old_name, new_name) in &skipped {
+        println!("{}", tr!("跳过(名称冲突): {} -> {}", old_name, new_name));
+    }
+
+    stats::add(Counter::Scanned, files.len() as u64);
+
+    if plans.is_empty() {
+        println!("{}", tr!("没有需要重命名的文件"));
+        return Ok(());
+    }
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        let operations: Vec<Operation> = plans
+            .iter()
+            .map(|plan| {
+                let target = dir.join(&plan.new_name);
+                Operation::new(OperationKind::Move, &plan.source, Some(&target))
+            })
+            .collect();
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!("{} 重命名预览 {}", "=".repeat(20), "=".repeat(20))
+    );
+    print_preview(&plans);
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "共 {} 个文件将被重命名，跳过 {} 个",
+            plans.len(),
+            skipped.len()
+        )
+    );
+    println!();
+
+    let sources: Vec<PathBuf> = plans.iter().map(|plan| plan.source.clone()).collect();
+    if !confirm_destructive("重命名", &sources, args.yes)? {
+        return Ok(());
+    }
+
+    // 新名称是其他待重命名文件的原名称时需要经过临时名称，其余直接重命名
+    let source_names: HashSet<String> = plans
+        .iter()
+        .filter_map(|plan| plan.source.file_name())
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .collect();
+    let mut direct = Vec::new();
+    let mut staged = Vec::new();
+    for plan in &plans {
+        let target = dir.join(&plan.new_name);
+        let own_name = plan
+            .source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let new_name = plan.new_name.to_lowercase();
+        if new_name != own_name && source_names.contains(&new_name) {
+            let temp_path = dir.join(format!(".{}.rename-tmp", Uuid::now_v7()));
+            staged.push((plan.source.clone(), temp_path, target));
+        } else {
+            direct.push((plan.source.clone(), target));
+        }
+    }
+
+    // 先将链式重命名的文件移到临时名称，腾出原名称后再执行其余重命名
+    let mut steps: Vec<(PathBuf, PathBuf)> = staged
+        .iter()
+        .map(|(source, temp_path, _)| (source.clone(), temp_path.clone()))
+        .collect();
+    steps.extend(direct);
+    steps.extend(
+        staged
+            .into_iter()
+            .map(|(_, temp_path, target)| (temp_path, target)),
+    );
+    rename_all(&steps).await?;
+    stats::add(Counter::Processed, plans.len() as u64);
+
+    for plan in &plans {
+        println!(
+            "{}",
+            tr!(
+                "重命名: {} -> {}",
+                plan.source.display(),
+                dir.join(&plan.new_name).display()
+            )
+        );
+    }
+    println!();
+    println!("{}", tr!("操作成功完成！"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 创建测试用的临时目录
+    fn test_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("scripts-rename-test-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn skip_keeps_name_of_unchanged_file() {
+        // a.txt -> b.txt 与不变的 b.txt 冲突，应跳过而不是占用 b.txt
+        let candidates = vec![
+            (PathBuf::from("a.txt"), "b.txt".to_string()),
+            (PathBuf::from("b.txt"), "b.txt".to_string()),
+        ];
+        let (plans, skipped) = assign_names(&candidates, &HashSet::new(), ConflictMode::Skip);
+        assert!(plans.is_empty());
+        assert_eq!(skipped, vec![("a.txt".to_string(), "b.txt".to_string())]);
+    }
+
+    #[test]
+    fn skip_reassigns_name_of_skipped_file() {
+        // b.txt -> c.txt 与已有的 c.txt 冲突被跳过，a.txt 不能再占用 b.txt
+        let candidates = vec![
+            (PathBuf::from("a.txt"), "b.txt".to_string()),
+            (PathBuf::from("b.txt"), "c.txt".to_string()),
+        ];
+        let occupied = HashSet::from(["c.txt".to_string()]);
+        let (plans, skipped) = assign_names(&candidates, &occupied, ConflictMode::Skip);
+        assert!(plans.is_empty());
+        assert_eq!(skipped.len(), 2);
+    }
+
+    #[test]
+    fn suffix_avoids_unchanged_file() {
+        let candidates = vec![
+            (PathBuf::from("a.txt"), "b.txt".to_string()),
+            (PathBuf::from("b.txt"), "b.txt".to_string()),
+        ];
+        let (plans, _) = assign_names(&candidates, &HashSet::new(), ConflictMode::Suffix);
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].new_name, "b-1.txt");
+    }
+
+    #[tokio::test]
+    async fn rename_all_refuses_to_overwrite() {
+        let dir = test_dir();
+        std::fs::write(dir.join("a.txt"), "AAA").unwrap();
+        std::fs::write(dir.join("b.txt"), "BBB").unwrap();
+        std::fs::write(dir.join("c.txt"), "CCC").unwrap();
+
+        // 第一步成功后第二步失败，第一步应被撤销
+        let steps = vec![
+            (dir.join("c.txt"), dir.join("d.txt")),
+            (dir.join("a.txt"), dir.join("b.txt")),
+        ];
+        assert!(rename_all(&steps).await.is_err());
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "AAA");
+        assert_eq!(std::fs::read_to_string(dir.join("b.txt")).unwrap(), "BBB");
+        assert_eq!(std::fs::read_to_string(dir.join("c.txt")).unwrap(), "CCC");
+        assert!(!dir.join("d.txt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,311 @@
+//! # 仓库镜像工具 (repo_mirror)
+//!
+//! 将一个 git 仓库的分支和标签（或所有引用）镜像到另一个仓库，
+//! 用于在不同托管平台之间迁移或同步仓库。
+//!
+//! ## 批量镜像
+//!
+//! 使用 `--file` 从 JSON 或 TOML 文件读取仓库列表，例如：
+//!
+//! ```toml
+//! [[repos]]
+//! from = "https://github.com/org/app.git"
+//! to = "https://gitlab.example.com/org/app.git"
+//! mirror = true
+//!
+//! [repos.to_auth]
+//! username = "oauth2"
+//! token = "keyring:gitlab"
+//! ```
+//!
+//! `from_auth` / `to_auth` 中的 `token` 支持 `keyring:<名称>` 引用。
+
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::git::{Credentials, MirrorAuth, MirrorOptions, mirror_repository};
+use crate::utils::i18n::tr;
+use crate::utils::secrets::resolve_secret;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "repo-mirror")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "将 git 仓库镜像到另一个仓库",
+    long_about = "从源仓库拉取所有分支和标签并推送到目标仓库。使用 --mirror 时通过 git clone --mirror 镜像 refs/* 下的所有引用（包括 notes 等），同时使用 --force 时通过 git push --mirror 强制更新并删除目标中多余的引用，目标仓库拒绝推送时回退为逐个推送分支。使用 --force 覆盖目标仓库中分叉的引用。需要系统安装 git，认证使用 git 自身的凭据配置。使用 --file 从 JSON/TOML 文件批量镜像多个仓库，可为每个仓库指定访问令牌。"
+)]
+pub struct RepoMirrorArgs {
+    /// 源仓库地址
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "源仓库地址",
+        required_unless_present = "file",
+        conflicts_with = "file",
+        long_help = "源仓库的地址，可以是 HTTPS、SSH 地址或本地路径。"
+    )]
+    pub from: Option<String>,
+
+    /// 目标仓库地址
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "目标仓库地址",
+        required_unless_present = "file",
+        conflicts_with = "file",
+        long_help = "目标仓库的地址，可以是 HTTPS、SSH 地址或本地路径。目标仓库需要已经存在。"
+    )]
+    pub to: Option<String>,
+
+    /// 仓库列表文件
+    #[arg(
+        short = 'f',
+        long,
+        value_name = "FILE",
+        help = "从 JSON/TOML 文件批量镜像仓库",
+        long_help = "读取仓库列表文件（.json 按 JSON 解析，其他按 TOML 解析），文件中的 repos 数组每项包含 from、to，可选 mirror、force 覆盖命令行选项，可选 from_auth、to_auth 指定 username（默认 oauth2）和 token（支持 keyring:<名称>）。"
+    )]
+    pub file: Option<PathBuf>,
+
+    /// 并行数量
+    #[arg(
+        short = 'j',
+        long,
+        default_value_t = 1,
+        value_name = "N",
+        help = "批量镜像时同时处理的仓库数量",
+        long_help = "批量镜像时同时处理的仓库数量，默认 1 即逐个处理。并行时各仓库的 git 输出会交错显示。"
+    )]
+    pub jobs: usize,
+
+    /// 推送所有引用
+    #[arg(
+        long,
+        help = "完整镜像所有引用",
+        long_help = "使用 git clone --mirror 拉取并推送 refs/* 下的所有引用（包括 notes 等）。不使用 --force 时只推送可快进的更新，不删除目标中的引用；同时使用 --force 时通过 git push --mirror 强制更新，并删除目标仓库中源仓库没有的引用。目标仓库拒绝推送时回退为逐个推送分支和标签。"
+    )]
+    pub mirror: bool,
+
+    /// 强制推送
+    #[arg(
+        long,
+        help = "强制推送",
+        long_help = "使用 git push --force 覆盖目标仓库中与源仓库分叉的引用。与 --mirror 同时使用时还会删除目标仓库中源仓库没有的引用。"
+    )]
+    pub force: bool,
+}
+
+/// 仓库列表文件结构
+#[derive(Debug, Deserialize)]
+struct MirrorFile {
+    /// 仓库列表
+    #[serde(default)]
+    repos: Vec<MirrorEntry>,
+}
+
+/// 单个仓库的镜像配置
+#[derive(Debug, Deserialize)]
+struct MirrorEntry {
+    /// 源仓库地址
+    from: String,
+    /// 目标仓库地址
+    to: String,
+    /// 是否完整镜像所有引用，未设置时使用命令行选项
+    mirror: Option<bool>,
+    /// 是否强制推送，未设置时使用命令行选项
+    force: Option<bool>,
+    /// 源仓库凭据
+    from_auth: Option<AuthEntry>,
+    /// 目标仓库凭据
+    to_auth: Option<AuthEntry>,
+}
+
+/// 凭据配置
+#[derive(Debug, Deserialize)]
+struct AuthEntry {
+    /// 用户名
+    #[serde(default = "default_username")]
+    username: String,
+    /// 访问令牌，支持 `keyring:<名称>` 引用
+    token: String,
+}
+
+/// 默认用户名，GitLab 和 GitHub 的访问令牌均可使用
+fn default_username() -> String {
+    "oauth2".to_string()
+}
+
+/// 已解析凭据、可直接执行的镜像任务
+struct MirrorTask {
+    from: String,
+    to: String,
+    options: MirrorOptions,
+    auth: MirrorAuth,
+}
+
+/// 解析凭据配置中的密钥引用
+fn resolve_auth(auth: Option<AuthEntry>) -> Result<Option<Credentials>> {
+    auth.map(|auth| {
+        Ok(Credentials {
+            username: auth.username,
+            token: resolve_secret(&auth.token)?,
+        })
+    })
+    .transpose()
+}
+
+/// 读取仓库列表文件并解析凭据
+fn load_tasks(path: &Path, defaults: MirrorOptions) -> Result<Vec<MirrorTask>> {
+    let content = std::fs::read_to_string(path).with_context(|| {
+        ScriptError::new(
+            ErrorKind::Config,
+            tr!("读取仓库列表失败: {}", path.display()),
+        )
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let file: MirrorFile = if is_json {
+        serde_json::from_str(&content).with_context(|| {
+            ScriptError::new(
+                ErrorKind::Config,
+                tr!("解析仓库列表失败: {}", path.display()),
+            )
+        })?
+    } else {
+        toml::from_str(&content).with_context(|| {
+            ScriptError::new(
+                ErrorKind::Config,
+                tr!("解析仓库列表失败: {}", path.display()),
+            )
+        })?
+    };
+
+    file.repos
+        .into_iter()
+        .map(|entry| {
+            let auth = MirrorAuth {
+                from: resolve_auth(entry.from_auth)
+                    .with_context(|| tr!("解析凭据失败: {}", entry.from))?,
+                to: resolve_auth(entry.to_auth)
+                    .with_context(|| tr!("解析凭据失败: {}", entry.to))?,
+            };
+            Ok(MirrorTask {
+                options: MirrorOptions {
+                    all_refs: entry.mirror.unwrap_or(defaults.all_refs),
+                    force: entry.force.unwrap_or(defaults.force),
+                },
+                from: entry.from,
+                to: entry.to,
+                auth,
+            })
+        })
+        .collect()
+}
+
+/// 批量镜像仓库并输出汇总报告
+async fn run_batch(tasks: Vec<MirrorTask>, jobs: usize) -> Result<()> {
+    let total = tasks.len();
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut set = JoinSet::new();
+
+    for (index, task) in tasks.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            println!("[{}/{}] {} -> {}", index + 1, total, task.from, task.to);
+            let result = mirror_repository(&task.from, &task.to, task.options, &task.auth).await;
+            (index, task.from, task.to, result)
+        });
+    }
+
+    let mut results = Vec::with_capacity(total);
+    while let Some(joined) = set.join_next().await {
+        results.push(joined.with_context(|| tr!("镜像任务异常退出"))?);
+    }
+    results.sort_by_key(|(index, _, _, _)| *index);
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    stats::add(Counter::Scanned, total as u64);
+    let mut failed = 0;
+    for (_, from, to, result) in &results {
+        match result {
+            Ok(branches) => {
+                stats::add(Counter::Processed, 1);
+                println!("{}", tr!("✓ {} -> {} ({} 个分支)", from, to, branches));
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                failed += 1;
+                println!("✗ {} -> {}: {:#}", from, to, e);
+            }
+        }
+    }
+    println!("{}", tr!("成功: {}", total - failed));
+    println!("{}", tr!("失败: {}", failed));
+
+    if failed > 0 {
+        return Err(
+            ScriptError::new(ErrorKind::PartialFailure, tr!("{} 个仓库镜像失败", failed)).into(),
+        );
+    }
+    Ok(())
+}
+
+/// 命令执行函数
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 镜像成功
+/// * `Err(anyhow::Error)` - 仓库列表无效、git 拉取或推送失败
+pub async fn run(args: RepoMirrorArgs) -> Result<()> {
+    let options = MirrorOptions {
+        all_refs: args.mirror,
+        force: args.force,
+    };
+
+    println!(
+        "{}",
+        tr!("{} 仓库镜像工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+
+    if let Some(file) = &args.file {
+        let tasks = load_tasks(file, options)?;
+        println!("{}", tr!("仓库列表: {}", file.display()));
+        println!("{}", tr!("仓库数量: {}", tasks.len()));
+        println!("{}", tr!("并行数量: {}", args.jobs.max(1)));
+        println!();
+        return run_batch(tasks, args.jobs).await;
+    }
+
+    // clap 保证未使用 --file 时 --from 和 --to 均已提供
+    let from = args.from.with_context(|| tr!("缺少 --from 参数"))?;
+    let to = args.to.with_context(|| tr!("缺少 --to 参数"))?;
+    println!("{}", tr!("源仓库: {}", from));
+    println!("{}", tr!("目标仓库: {}", to));
+    println!();
+
+    stats::add(Counter::Scanned, 1);
+    let result = mirror_repository(&from, &to, options, &MirrorAuth::default()).await;
+    if result.is_err() {
+        stats::add(Counter::Failed, 1);
+    }
+    let branches = result.with_context(|| tr!("仓库镜像失败"))?;
+    stats::add(Counter::Processed, 1);
+
+    println!();
+    println!("{}", tr!("操作成功完成！共镜像 {} 个分支", branches));
+    Ok(())
+}
@@ -10,9 +10,12 @@
 //! - 仅匹配目录,不匹配文件
 //! - 计算目录递归总大小
 //! - 输出完整路径、大小和修改时间
-//! - 权限不足时自动跳过
+//! - 权限不足时自动跳过,并报告无法访问的条目数量(此时大小为部分统计)
 
-use crate::utils::filesystem::calculate_dir_size;
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::filesystem::measure_dir_size;
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
 use anyhow::Result;
 use bytesize::ByteSize;
 use chrono::{DateTime, Local};
@@ -56,6 +59,16 @@ pub struct ResidueSearchArgs {
         long_help = "启用交互式删除功能。开启后,扫描结束时会询问是否删除每个匹配的目录。"
     )]
     pub interactive: bool,
+    /// 跳过删除确认
+    ///
+    /// 交互式选择目录后,默认会再次列出选中项并要求确认。
+    #[arg(
+        short = 'y',
+        long = "yes",
+        help = "跳过删除确认",
+        long_help = "交互式选择目录后,默认会再次列出选中的目录及大小并要求确认。开启后跳过该确认。"
+    )]
+    pub yes: bool,
 }
 
 /// 匹配项结构
@@ -65,6 +78,8 @@ pub struct MatchedItem {
     pub path: PathBuf,
     /// 大小(字节),目录递归总大小
     pub size: u64,
+    /// 权限不足等原因无法访问的条目数量,不为 0 时大小只是部分统计
+    pub skipped: usize,
     /// 最后修改时间
     pub modified_time: SystemTime,
 }
@@ -82,31 +97,43 @@ fn build_scan_roots() -> Result<Vec<PathBuf>> {
     // 1. C:\Program Files
     match env::var("ProgramFiles") {
         Ok(program_files) => roots.push(PathBuf::from(program_files)),
-        Err(_) => println!("环境变量 ProgramFiles 未设置, 已跳过 C:\\Program Files"),
+        Err(_) => println!(
+            "{}",
+            tr!("环境变量 ProgramFiles 未设置, 已跳过 C:\\Program Files")
+        ),
     }
 
     // 2. C:\Program Files (x86)
     match env::var("ProgramFiles(x86)") {
         Ok(program_files_x86) => roots.push(PathBuf::from(program_files_x86)),
-        Err(_) => println!("环境变量 ProgramFiles(x86) 未设置, 已跳过 C:\\Program Files (x86)"),
+        Err(_) => println!(
+            "{}",
+            tr!("环境变量 ProgramFiles(x86) 未设置, 已跳过 C:\\Program Files (x86)")
+        ),
     }
 
     // 3. C:\ProgramData
     match env::var("ProgramData") {
         Ok(program_data) => roots.push(PathBuf::from(program_data)),
-        Err(_) => println!("环境变量 ProgramData 未设置, 已跳过 C:\\ProgramData"),
+        Err(_) => println!(
+            "{}",
+            tr!("环境变量 ProgramData 未设置, 已跳过 C:\\ProgramData")
+        ),
     }
 
     // 4. C:\Users\\[用户名]
     match env::var("USERPROFILE") {
         Ok(user_profile) => roots.push(PathBuf::from(user_profile)),
-        Err(_) => println!("环境变量 USERPROFILE 未设置, 已跳过用户主目录"),
+        Err(_) => println!("{}", tr!("环境变量 USERPROFILE 未设置, 已跳过用户主目录")),
     }
 
     // 5. C:\Users\\[用户名]\\AppData\\Roaming
     match env::var("APPDATA") {
         Ok(appdata) => roots.push(PathBuf::from(appdata)),
-        Err(_) => println!("环境变量 APPDATA 未设置, 已跳过 AppData\\Roaming 目录"),
+        Err(_) => println!(
+            "{}",
+            tr!("环境变量 APPDATA 未设置, 已跳过 AppData\\Roaming 目录")
+        ),
     }
 
     // 6. C:\Users\\[用户名]\\AppData\\Local
@@ -115,7 +142,10 @@ fn build_scan_roots() -> Result<Vec<PathBuf>> {
             let local_appdata_path = PathBuf::from(&local_appdata);
             roots.push(local_appdata_path);
         }
-        Err(_) => println!("环境变量 LOCALAPPDATA 未设置, 已跳过 AppData\\Local"),
+        Err(_) => println!(
+            "{}",
+            tr!("环境变量 LOCALAPPDATA 未设置, 已跳过 AppData\\Local")
+        ),
     }
 
     // 去重(虽然正常情况下不会有重复)
@@ -135,7 +165,7 @@ fn build_scan_roots() -> Result<Vec<PathBuf>> {
     }
 
     if !missing_roots.is_empty() {
-        println!("以下扫描目录不存在, 已跳过:");
+        println!("{}", tr!("以下扫描目录不存在, 已跳过:"));
         for p in &missing_roots {
             println!("  - {}", p.display());
         }
@@ -143,7 +173,7 @@ fn build_scan_roots() -> Result<Vec<PathBuf>> {
     }
 
     if existing_roots.is_empty() {
-        anyhow::bail!("未找到任何有效的扫描根目录,请检查系统环境变量");
+        anyhow::bail!(tr!("未找到任何有效的扫描根目录,请检查系统环境变量"));
     }
 
     Ok(existing_roots)
@@ -199,13 +229,14 @@ fn scan_directory(
             Err(_) => continue,
         };
 
-        let size = calculate_dir_size(entry_path);
+        let size = measure_dir_size(entry_path);
 
         matched.insert(
             entry_path.to_path_buf(),
             MatchedItem {
                 path: entry_path.to_path_buf(),
-                size,
+                size: size.bytes,
+                skipped: size.skipped,
                 modified_time,
             },
         );
@@ -219,31 +250,34 @@ pub async fn run(args: ResidueSearchArgs) -> Result<()> {
     // 验证软件名参数
     let software_name = args.software_name.trim();
     if software_name.is_empty() {
-        anyhow::bail!("软件名不能为空或仅包含空白字符");
+        anyhow::bail!(tr!("软件名不能为空或仅包含空白字符"));
     }
 
     let software_name_lower = software_name.to_lowercase();
 
     // 显示工具信息头部
     println!(
-        "{}  软件卸载残留查找工具  {}",
-        "=".repeat(15),
-        "=".repeat(15)
+        "{}",
+        tr!(
+            "{}  软件卸载残留查找工具  {}",
+            "=".repeat(15),
+            "=".repeat(15)
+        )
     );
-    println!("查询软件: {}", software_name);
+    println!("{}", tr!("查询软件: {}", software_name));
     println!();
 
     // 构建扫描路径列表
     let scan_roots = build_scan_roots()?;
 
     // 显示扫描位置
-    println!("扫描位置:");
+    println!("{}", tr!("扫描位置:"));
     for root in &scan_roots {
         println!("  - {}", root.display());
     }
     println!();
 
-    println!("正在扫描,请稍候...");
+    println!("{}", tr!("正在扫描,请稍候..."));
     println!();
 
     // 扫描所有根目录, 使用 HashMap 全局去重
@@ -257,29 +291,55 @@ pub async fn run(args: ResidueSearchArgs) -> Result<()> {
     let all_matched_items: Vec<MatchedItem> = matched.into_values().collect();
 
     // 输出匹配结果
-    println!("{} 匹配结果 {}", "=".repeat(20), "=".repeat(20));
+    println!("{}", tr!("{} 匹配结果 {}", "=".repeat(20), "=".repeat(20)));
     println!();
 
     if all_matched_items.is_empty() {
-        println!("未找到匹配的目录");
+        println!("{}", tr!("未找到匹配的目录"));
     } else {
         for item in &all_matched_items {
             println!("  {}", item.path.display());
-            println!("    大小: {}", ByteSize(item.size));
+            if item.skipped > 0 {
+                println!(
+                    "{}",
+                    tr!(
+                        "    大小: {} (至少,{} 项无法访问)",
+                        ByteSize(item.size),
+                        item.skipped
+                    )
+                );
+            } else {
+                println!("{}", tr!("    大小: {}", ByteSize(item.size)));
+            }
             let datetime: DateTime<Local> = item.modified_time.into();
-            println!("    修改时间: {}", datetime.format("%Y-%m-%d %H:%M:%S"));
+            println!(
+                "{}",
+                tr!("    修改时间: {}", datetime.format("%Y-%m-%d %H:%M:%S"))
+            );
             println!();
         }
     }
 
     // 统计结果
-    println!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20));
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
 
     let total_size: u64 = all_matched_items.iter().map(|item| item.size).sum();
     let total_count = all_matched_items.len();
 
-    println!("匹配的目录: {} 个", total_count);
-    println!("总大小: {}", ByteSize(total_size));
+    println!("{}", tr!("匹配的目录: {} 个", total_count));
+    let total_skipped: usize = all_matched_items.iter().map(|item| item.skipped).sum();
+    if total_skipped > 0 {
+        println!(
+            "{}",
+            tr!(
+                "总大小: {} (部分统计,{} 项因权限不足等原因无法访问)",
+                ByteSize(total_size),
+                total_skipped
+            )
+        );
+    } else {
+        println!("{}", tr!("总大小: {}", ByteSize(total_size)));
+    }
 
     // 如果未启用交互式删除功能,提前返回
     if !args.interactive {
@@ -288,7 +348,7 @@ pub async fn run(args: ResidueSearchArgs) -> Result<()> {
 
     // 提供所有匹配目录供交互式选择
     if all_matched_items.is_empty() {
-        println!("\n没有匹配的目录可供删除");
+        println!("{}", tr!("\n没有匹配的目录可供删除"));
         return Ok(());
     }
 
@@ -300,32 +360,39 @@ pub async fn run(args: ResidueSearchArgs) -> Result<()> {
 
     // 使用 MultiSelect 让用户选择要删除的目录
     println!();
-    let selected = match MultiSelect::new("请选择要删除的目录", options).prompt() {
+    let selected = match MultiSelect::new(&tr!("请选择要删除的目录"), options).prompt() {
         Ok(selected) => selected,
         Err(_) => {
-            println!("操作已取消");
+            println!("{}", tr!("操作已取消"));
             return Ok(());
         }
     };
 
     if selected.is_empty() {
-        println!("未选择任何项,操作已取消");
+        println!("{}", tr!("未选择任何项,操作已取消"));
         return Ok(());
     }
 
     // 将选中的路径字符串转换为 PathBuf
     let selected_paths: Vec<PathBuf> = selected.iter().map(PathBuf::from).collect();
 
+    // 再次确认选中的目录
+    if !confirm_destructive("移动到回收站", &selected_paths, args.yes)? {
+        return Ok(());
+    }
+
     // 执行删除
     for path in selected_paths {
         let result = trash::delete(&path);
 
         match result {
             Ok(_) => {
-                println!("✓ 已将目录移动到回收站: {}", path.display());
+                stats::add(Counter::Deleted, 1);
+                println!("{}", tr!("✓ 已将目录移动到回收站: {}", path.display()));
             }
             Err(e) => {
-                println!("✗ 移动到回收站失败: {} - {}", path.display(), e);
+                stats::add(Counter::Failed, 1);
+                println!("{}", tr!("✗ 移动到回收站失败: {} - {}", path.display(), e));
             }
         }
     }
@@ -0,0 +1,362 @@
+//! # 计划任务工具 (schedule)
+//!
+//! 读取任务文件（cron 表达式 + 子命令 + 参数），作为常驻进程按计划执行任务，
+//! 例如每晚压缩下载目录。每个任务的输出写入独立的日志文件。
+//!
+//! 任务以子进程方式调用当前可执行文件执行，没有终端可交互，
+//! 因此需要确认的删除操作应在参数中加入 `--yes`。
+
+use crate::utils::config::config_path;
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::filesystem::is_reserved_name;
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use clap::Args;
+use cron::Schedule;
+use serde::Deserialize;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::str::FromStr;
+use tokio::process::Command;
+
+/// 新建任务文件时写入的模板
+pub const SCHEDULE_TEMPLATE: &str = r#"# scripts 计划任务
+#
+# cron 表达式包含秒字段：秒 分 时 日 月 星期 [年]
+#
+# [[job]]
+# name = "nightly-compress"
+# cron = "0 0 3 * * *"
+# command = "batch-compress"
+# args = ["-s", "D:/Downloads", "--delete", "--yes"]
+"#;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "schedule")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "按计划执行任务",
+    long_about = "读取任务文件中的 [[job]] 列表（cron 表达式、子命令和参数），作为常驻进程按计划执行。每个任务的输出追加到独立的日志文件。使用 --once 可立即执行一次所有任务后退出，用于测试任务配置。"
+)]
+pub struct ScheduleArgs {
+    /// 任务文件路径
+    #[arg(
+        short = 'f',
+        long,
+        value_name = "FILE",
+        help = "任务文件路径",
+        long_help = "TOML 格式的任务文件。默认 ~/.config/scripts/schedule.toml，不存在时根据模板创建。"
+    )]
+    pub file: Option<PathBuf>,
+
+    /// 立即执行一次后退出
+    #[arg(
+        long,
+        help = "立即执行一次所有任务后退出",
+        long_help = "忽略 cron 表达式，立即依次执行所有任务（或 --job 指定的任务）一次后退出，用于测试任务配置。有任务失败时以退出码 5 退出。"
+    )]
+    pub once: bool,
+
+    /// 仅执行指定任务
+    #[arg(
+        short = 'j',
+        long,
+        value_name = "NAME",
+        help = "仅执行指定名称的任务",
+        long_help = "只调度或执行名称匹配的任务，可与 --once 一起使用测试单个任务。"
+    )]
+    pub job: Option<String>,
+}
+
+/// 任务文件结构
+#[derive(Debug, Deserialize)]
+struct JobFile {
+    /// 任务列表
+    #[serde(default)]
+    job: Vec<Job>,
+}
+
+/// 单个计划任务
+#[derive(Debug, Deserialize)]
+pub struct Job {
+    /// 任务名称，同时用作日志文件名
+    pub name: String,
+    /// cron 表达式（包含秒字段）
+    pub cron: String,
+    /// 要执行的子命令
+    pub command: String,
+    /// 子命令参数
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// 获取默认任务文件路径
+///
+/// 与配置文件位于同一目录：`~/.config/scripts/schedule.toml`。
+fn default_schedule_path() -> Result<PathBuf> {
+    let config = config_path()?;
+    Ok(config.with_file_name("schedule.toml"))
+}
+
+/// 获取任务日志目录
+///
+/// 位于应用数据目录下的 `scripts/logs`。
+pub fn log_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("scripts")
+        .join("logs")
+}
+
+/// 检查任务名称是否可以用作日志文件名
+///
+/// 不能为空、以点开头或结尾，不能包含路径分隔符和 Windows 文件名中不允许的字符，
+/// 也不能是 Windows 保留名称，否则日志会写到日志目录之外或无法创建。
+fn validate_job_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && !name.ends_with(['.', ' '])
+        && !name.chars().any(|c| {
+            c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        })
+        && !is_reserved_name(name)
+}
+
+/// 读取并解析任务文件
+///
+/// # 参数
+///
+/// * `path` - 任务文件路径
+///
+/// # 返回值
+///
+/// * `Ok(Vec<(Job, Schedule)>)` - 任务及其解析后的 cron 计划
+/// * `Err(anyhow::Error)` - 读取失败、格式错误、任务名称或 cron 表达式无效
+pub fn load_jobs(path: &Path) -> Result<Vec<(Job, Schedule)>> {
+    let content = std::fs::read_to_string(path).with_context(|| {
+        ScriptError::new(
+            ErrorKind::Config,
+            tr!("读取任务文件失败: {}", path.display()),
+        )
+    })?;
+    let file: JobFile = toml::from_str(&content).with_context(|| {
+        ScriptError::new(
+            ErrorKind::Config,
+            tr!("解析任务文件失败: {}", path.display()),
+        )
+    })?;
+
+    file.job
+        .into_iter()
+        .map(|job| {
+            if !validate_job_name(&job.name) {
+                return Err(ScriptError::new(
+                    ErrorKind::Config,
+                    tr!(
+                        "任务名称无效: \"{}\"（用作日志文件名，不能包含路径分隔符或特殊字符）",
+                        job.name
+                    ),
+                )
+                .into());
+            }
+            let schedule = Schedule::from_str(&job.cron).with_context(|| {
+                ScriptError::new(
+                    ErrorKind::Config,
+                    tr!("任务 {} 的 cron 表达式无效: {}", job.name, job.cron),
+                )
+            })?;
+            Ok((job, schedule))
+        })
+        .collect()
+}
+
+/// 执行单个任务
+///
+/// 以子进程方式调用当前可执行文件，标准输出和标准错误追加到任务日志文件。
+///
+/// # 参数
+///
+/// * `job` - 要执行的任务
+///
+/// # 返回值
+///
+/// * `Ok(bool)` - 任务进程是否成功退出
+/// * `Err(anyhow::Error)` - 无法打开日志文件或启动进程
+pub async fn run_job(job: &Job) -> Result<bool> {
+    let log_dir = log_dir();
+    std::fs::create_dir_all(&log_dir)
+        .with_context(|| tr!("创建日志目录失败: {}", log_dir.display()))?;
+    let log_path = log_dir.join(format!("{}.log", job.name));
+
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| tr!("打开日志文件失败: {}", log_path.display()))?;
+    writeln!(
+        log,
+        "===== {} {} {} =====",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        job.command,
+        job.args.join(" ")
+    )?;
+
+    let exe = env::current_exe().with_context(|| tr!("无法获取当前可执行文件路径"))?;
+    let status = Command::new(exe)
+        .arg(&job.command)
+        .args(&job.args)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log.try_clone()?)
+        .status()
+        .await
+        .with_context(|| tr!("启动任务失败: {}", job.name))?;
+
+    writeln!(log, "{}\n", tr!("===== 退出状态: {} =====", status))?;
+    Ok(status.success())
+}
+
+/// 执行任务并输出结果摘要
+///
+/// # 返回值
+///
+/// * `bool` - 任务是否成功
+async fn run_and_report(job: &Job) -> bool {
+    println!(
+        "{}",
+        tr!(
+            "[{}] 执行任务: {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            job.name
+        )
+    );
+    match run_job(job).await {
+        Ok(true) => {
+            stats::add(Counter::Processed, 1);
+            println!("{}", tr!("✓ 任务完成: {}", job.name));
+            true
+        }
+        Ok(false) => {
+            stats::add(Counter::Failed, 1);
+            println!("{}", tr!("✗ 任务失败: {} (详见日志)", job.name));
+            false
+        }
+        Err(e) => {
+            stats::add(Counter::Failed, 1);
+            println!(
+                "{}",
+                tr!("✗ 任务失败: {} - {}", job.name, format!("{:#}", e))
+            );
+            false
+        }
+    }
+}
+
+/// 命令执行函数
+///
+/// 1. 读取任务文件（默认路径不存在时根据模板创建）
+/// 2. `--once` 模式下依次执行所有任务后退出
+/// 3. 否则循环等待最近一次触发时间并执行到期的任务
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 执行成功（常驻模式下不会返回）
+/// * `Err(anyhow::Error)` - 任务文件无效、没有可执行的任务，或 `--once` 模式下有任务失败
+pub async fn run(args: ScheduleArgs) -> Result<()> {
+    let path = match args.file {
+        Some(path) => path,
+        None => {
+            let path = default_schedule_path()?;
+            if !path.exists() {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| tr!("创建目录失败: {}", parent.display()))?;
+                }
+                std::fs::write(&path, SCHEDULE_TEMPLATE)
+                    .with_context(|| tr!("创建任务文件失败: {}", path.display()))?;
+                println!("{}", tr!("已创建任务文件模板: {}", path.display()));
+            }
+            path
+        }
+    };
+
+    let mut jobs = load_jobs(&path)?;
+    if let Some(name) = &args.job {
+        jobs.retain(|(job, _)| &job.name == name);
+    }
+
+    if jobs.is_empty() {
+        anyhow::bail!(tr!("没有可执行的任务，请编辑任务文件: {}", path.display()));
+    }
+
+    println!("{}", tr!("{} 计划任务 {}", "=".repeat(15), "=".repeat(15)));
+    println!("{}", tr!("任务文件: {}", path.display()));
+    println!("{}", tr!("日志目录: {}", log_dir().display()));
+    println!();
+
+    if args.once {
+        stats::add(Counter::Scanned, jobs.len() as u64);
+        let mut failed = 0;
+        for (job, _) in &jobs {
+            if !run_and_report(job).await {
+                failed += 1;
+            }
+        }
+        if failed > 0 {
+            return Err(ScriptError::new(
+                ErrorKind::PartialFailure,
+                tr!("{} 个任务执行失败", failed),
+            )
+            .into());
+        }
+        return Ok(());
+    }
+
+    for (job, schedule) in &jobs {
+        if let Some(next) = schedule.upcoming(Local).next() {
+            println!(
+                "{}",
+                tr!(
+                    "{}: {} (下次执行 {})",
+                    job.name,
+                    job.cron,
+                    next.format("%Y-%m-%d %H:%M:%S")
+                )
+            );
+        }
+    }
+    println!();
+
+    // 记录每个任务的下次执行时间，循环等待最早到期的任务
+    let mut next_runs: Vec<Option<DateTime<Local>>> = jobs
+        .iter()
+        .map(|(_, schedule)| schedule.upcoming(Local).next())
+        .collect();
+
+    loop {
+        let Some(earliest) = next_runs.iter().flatten().min().copied() else {
+            println!("{}", tr!("所有任务均已没有后续执行时间，退出"));
+            return Ok(());
+        };
+
+        let wait = (earliest - Local::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        for ((job, schedule), next) in jobs.iter().zip(next_runs.iter_mut()) {
+            if next.is_some_and(|time| time <= Local::now()) {
+                run_and_report(job).await;
+                *next = schedule.upcoming(Local).next();
+            }
+        }
+    }
+}
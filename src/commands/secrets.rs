@@ -0,0 +1,74 @@
+//! # 密钥管理工具 (secrets)
+//!
+//! 在系统密钥环中保存、读取和删除密钥。保存后可在参数或配置文件中
+//! 使用 `keyring:<名称>` 引用，避免明文密码出现在命令行历史和配置文件中。
+
+use crate::utils::i18n::tr;
+use crate::utils::secrets::{delete_secret, get_secret, set_secret};
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use inquire::Password;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "secrets")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "管理系统密钥环中的密钥",
+    long_about = "在系统密钥环（Windows 凭据管理器、macOS 钥匙串、Linux keyutils）中保存、读取和删除密钥。保存后可在参数或配置文件中使用 keyring:<名称> 引用，例如 batch-compress --password keyring:backup。"
+)]
+pub struct SecretsArgs {
+    #[command(subcommand)]
+    pub action: SecretsAction,
+}
+
+/// 密钥操作
+#[derive(Subcommand, Debug)]
+pub enum SecretsAction {
+    /// 保存密钥（未指定 --value 时交互式输入）
+    Set {
+        /// 密钥名称
+        name: String,
+        /// 密钥内容，不指定时交互式输入（推荐，避免出现在命令行历史中）
+        #[arg(long)]
+        value: Option<String>,
+    },
+    /// 读取并输出密钥
+    Get {
+        /// 密钥名称
+        name: String,
+    },
+    /// 删除密钥
+    Delete {
+        /// 密钥名称
+        name: String,
+    },
+}
+
+/// 命令执行函数
+pub async fn run(args: SecretsArgs) -> Result<()> {
+    match args.action {
+        SecretsAction::Set { name, value } => {
+            let value = match value {
+                Some(value) => value,
+                None => Password::new(&tr!("请输入密钥 {}:", name))
+                    .prompt()
+                    .with_context(|| tr!("读取输入失败"))?,
+            };
+            set_secret(&name, &value)?;
+            println!(
+                "{}",
+                tr!("已保存密钥: {}（引用方式: keyring:{}）", name, name)
+            );
+        }
+        SecretsAction::Get { name } => {
+            println!("{}", get_secret(&name)?);
+        }
+        SecretsAction::Delete { name } => {
+            delete_secret(&name)?;
+            println!("{}", tr!("已删除密钥: {}", name));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! # 文件分割工具 (split)
+//!
+//! 将大文件按指定大小分割为多个分块（`<文件名>.001`、`<文件名>.002` ...），
+//! 便于通过有大小限制的渠道传输。同时生成 `<文件名>.manifest` 清单，
+//! 记录每个分块和原文件的 Blake3 哈希，由 `scripts join` 合并时校验。
+
+use crate::utils::hash::encode_hash;
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// 读写缓冲区大小
+const BUFFER_SIZE: usize = 65536;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "split")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "将大文件分割为多个分块",
+    long_about = "将文件按指定大小分割为 <文件名>.001、<文件名>.002 等分块，并生成 <文件名>.manifest 清单，记录每个分块和原文件的 Blake3 哈希。使用 scripts join 合并并校验。"
+)]
+pub struct SplitArgs {
+    /// 要分割的文件
+    #[arg(
+        value_name = "FILE",
+        help = "要分割的文件",
+        long_help = "要分割的文件路径。"
+    )]
+    pub file: PathBuf,
+
+    /// 分块大小
+    #[arg(
+        short = 's',
+        long,
+        value_name = "SIZE",
+        help = "分块大小",
+        long_help = "每个分块的最大大小，例如 2GB、500MiB。"
+    )]
+    pub size: ByteSize,
+
+    /// 输出目录
+    #[arg(
+        short = 'o',
+        long,
+        value_name = "DIR",
+        help = "分块输出目录",
+        long_help = "分块和清单文件的输出目录，不存在时自动创建。默认与原文件相同的目录。"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+/// 获取文件对应的清单路径：`<文件路径>.manifest`
+///
+/// # 参数
+///
+/// * `file` - 原文件路径（合并时为合并后的文件路径）
+pub fn manifest_path(file: &Path) -> PathBuf {
+    let mut path = file.as_os_str().to_owned();
+    path.push(".manifest");
+    PathBuf::from(path)
+}
+
+/// 从原文件当前位置读取最多 `chunk_size` 字节写入一个分块
+///
+/// # 参数
+///
+/// * `source` - 已打开的原文件
+/// * `source_path` - 原文件路径，用于错误信息
+/// * `chunk_path` - 分块文件路径
+/// * `chunk_size` - 分块最大大小
+/// * `file_hasher` - 整个文件的哈希计算器，同时更新
+///
+/// # 返回值
+///
+/// * `Ok((u64, blake3::Hash))` - 写入的字节数和分块哈希；字节数小于 `chunk_size` 表示已读到文件末尾
+/// * `Err(anyhow::Error)` - 读写失败
+async fn write_chunk(
+    source: &mut tokio::fs::File,
+    source_path: &Path,
+    chunk_path: &Path,
+    chunk_size: u64,
+    file_hasher: &mut blake3::Hasher,
+) -> Result<(u64, blake3::Hash)> {
+    let mut chunk = tokio::fs::File::create(chunk_path)
+        .await
+        .with_context(|| tr!("创建分块失败: {}", chunk_path.display()))?;
+    let mut chunk_hasher = blake3::Hasher::new();
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut written = 0;
+
+    while written < chunk_size {
+        let limit = (chunk_size - written).min(BUFFER_SIZE as u64) as usize;
+        let n = source
+            .read(&mut buffer[..limit])
+            .await
+            .with_context(|| tr!("读取文件失败: {}", source_path.display()))?;
+        if n == 0 {
+            break;
+        }
+        chunk
+            .write_all(&buffer[..n])
+            .await
+            .with_context(|| tr!("写入分块失败: {}", chunk_path.display()))?;
+        chunk_hasher.update(&buffer[..n]);
+        file_hasher.update(&buffer[..n]);
+        written += n as u64;
+    }
+    chunk.flush().await?;
+
+    Ok((written, chunk_hasher.finalize()))
+}
+
+/// 命令执行函数
+///
+/// 顺序读取原文件，边写入分块边计算每个分块和整个文件的哈希，最后写入清单。
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 分割成功
+/// * `Err(anyhow::Error)` - 参数无效或读写失败
+pub async fn run(args: SplitArgs) -> Result<()> {
+    let chunk_size = args.size.as_u64();
+    if chunk_size == 0 {
+        anyhow::bail!(tr!("分块大小必须大于 0"));
+    }
+
+    if !args.file.is_file() {
+        anyhow::bail!(tr!("文件不存在: {}", args.file.display()));
+    }
+
+    let file_name = args
+        .file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .with_context(|| tr!("无效的文件名"))?;
+    let output_dir = match &args.output {
+        Some(dir) => dir.clone(),
+        None => args
+            .file
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+    };
+    tokio::fs::create_dir_all(&output_dir)
+        .await
+        .with_context(|| tr!("创建目录失败: {}", output_dir.display()))?;
+
+    let total_size = args.file.metadata()?.len();
+    println!(
+        "{}",
+        tr!("{} 文件分割工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!(
+        "{}",
+        tr!("文件: {} ({})", args.file.display(), ByteSize(total_size))
+    );
+    println!("{}", tr!("分块大小: {}", args.size));
+    println!();
+    stats::add(Counter::Scanned, 1);
+
+    let mut source = tokio::fs::File::open(&args.file)
+        .await
+        .with_context(|| tr!("打开文件失败: {}", args.file.display()))?;
+
+    let mut file_hasher = blake3::Hasher::new();
+    let mut manifest = String::from("# scripts split manifest\n");
+    let mut index = 1;
+    let mut remaining = total_size;
+
+    // 空文件也生成一个空分块，保证合并时可以还原
+    loop {
+        let chunk_name = format!("{}.{:03}", file_name, index);
+        let chunk_path = output_dir.join(&chunk_name);
+        let result = write_chunk(
+            &mut source,
+            &args.file,
+            &chunk_path,
+            chunk_size,
+            &mut file_hasher,
+        )
+        .await;
+        if result.is_err() {
+            stats::add(Counter::Failed, 1);
+        }
+        let (written, chunk_hash) = result?;
+        stats::add(Counter::Processed, 1);
+        stats::add(Counter::BytesIn, written);
+        stats::add(Counter::BytesOut, written);
+
+        manifest.push_str(&format!("{}  {}\n", encode_hash(&chunk_hash), chunk_name));
+        println!("{}", tr!("分块: {} ({})", chunk_name, ByteSize(written)));
+
+        remaining = remaining.saturating_sub(written);
+        if written < chunk_size || remaining == 0 {
+            break;
+        }
+        index += 1;
+    }
+
+    // 最后一行记录原文件的哈希
+    manifest.push_str(&format!(
+        "{}  {}\n",
+        encode_hash(&file_hasher.finalize()),
+        file_name
+    ));
+    let manifest_file = manifest_path(&output_dir.join(&file_name));
+    tokio::fs::write(&manifest_file, manifest)
+        .await
+        .with_context(|| tr!("写入清单失败: {}", manifest_file.display()))?;
+
+    println!();
+    println!("{}", tr!("清单: {}", manifest_file.display()));
+    println!("{}", tr!("操作成功完成！共 {} 个分块", index));
+    Ok(())
+}
@@ -0,0 +1,451 @@
+//! # 目录同步工具 (sync)
+//!
+//! 将源目录单向同步到目标目录，只复制新增和变化的文件，
+//! 可选将目标中多余的文件移动到回收站，类似 `rsync -a --delete`。
+//!
+//! ## 变化判断
+//!
+//! - 默认：大小或修改时间不同即视为变化，复制后保留源文件的修改时间
+//! - `--checksum`：大小相同时再比较 Blake3 哈希，忽略修改时间
+//!
+//! ## 符号链接
+//!
+//! - 默认：跳过源目录中的符号链接
+//! - `--follow-symlinks`：复制链接指向的内容，指向源目录之外的链接仍会跳过
+//! - `--preserve-symlinks`：在目标目录中按原样重建链接
+//!
+//! 写入目标前会检查实际路径，不会经由目标目录中的符号链接写到目标目录之外。
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::filesystem::create_symlink;
+use crate::utils::hash::calculate_file_hash;
+use crate::utils::i18n::tr;
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use clap::Args;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// 命令行参数结构体
+#[derive(Args, Debug)]
+#[command(name = "sync")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "将源目录单向同步到目标目录",
+    long_about = "将源目录中新增和变化的文件复制到目标目录，默认按大小和修改时间判断变化，使用 --checksum 时按 Blake3 哈希判断。使用 --delete 将目标中源目录没有的文件移动到回收站。目前只支持本地路径。"
+)]
+pub struct SyncArgs {
+    /// 源目录
+    #[arg(
+        value_name = "SRC",
+        help = "源目录",
+        long_help = "要同步的源目录，目前只支持本地路径。"
+    )]
+    pub source: String,
+
+    /// 目标目录
+    #[arg(
+        value_name = "DST",
+        help = "目标目录",
+        long_help = "同步的目标目录，不存在时自动创建，目前只支持本地路径。"
+    )]
+    pub target: String,
+
+    /// 删除目标中多余的文件
+    #[arg(
+        long,
+        help = "将目标中多余的文件移动到回收站",
+        long_help = "将目标目录中源目录没有的文件移动到回收站，使两侧文件一致。执行前需要确认。"
+    )]
+    pub delete: bool,
+
+    /// 按哈希判断变化
+    #[arg(
+        short = 'c',
+        long,
+        help = "按 Blake3 哈希判断文件是否变化",
+        long_help = "大小相同的文件计算 Blake3 哈希比较内容，忽略修改时间。更准确但需要读取两侧的文件。"
+    )]
+    pub checksum: bool,
+
+    /// 跟随符号链接
+    #[arg(
+        short = 'L',
+        long,
+        conflicts_with = "preserve_symlinks",
+        help = "复制符号链接指向的内容",
+        long_help = "按链接指向的文件和目录同步内容。指向源目录之外的链接不会被跟随，避免同步范围超出源目录。"
+    )]
+    pub follow_symlinks: bool,
+
+    /// 保留符号链接
+    #[arg(
+        long,
+        help = "在目标目录中重建符号链接",
+        long_help = "将源目录中的符号链接按原样（相同的链接目标）重建到目标目录，不复制链接指向的内容。使用 --delete 时也会删除目标中多余的链接。Windows 上需要管理员权限或开启开发者模式。"
+    )]
+    pub preserve_symlinks: bool,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际执行",
+        long_help = "列出将要复制和移动到回收站的文件及其大小，但不做任何修改。"
+    )]
+    pub dry_run: bool,
+
+    /// 跳过确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除前的确认",
+        long_help = "使用 --delete 时不再交互式确认，直接将多余的文件移动到回收站，适用于脚本和计划任务。"
+    )]
+    pub yes: bool,
+}
+
+/// 文件元数据：大小和修改时间
+type FileMeta = (u64, Option<SystemTime>);
+
+/// 解析同步端点，目前只接受本地路径
+fn parse_endpoint(value: &str) -> Result<PathBuf> {
+    if let Some((scheme, _)) = value.split_once("://") {
+        anyhow::bail!(tr!("不支持的地址类型 {}://，目前只支持本地路径", scheme));
+    }
+    Ok(PathBuf::from(value))
+}
+
+/// 目录扫描结果
+#[derive(Debug, Default)]
+struct Tree {
+    /// 普通文件：相对路径 -> 元数据
+    files: BTreeMap<PathBuf, FileMeta>,
+    /// 符号链接（仅 `--preserve-symlinks`）：相对路径 -> (链接目标, 是否指向目录)
+    links: BTreeMap<PathBuf, (PathBuf, bool)>,
+    /// 跳过的符号链接数量
+    skipped_links: usize,
+}
+
+/// 收集目录下所有文件和符号链接的相对路径
+///
+/// - `follow` 为 `true` 时跟随链接，但不进入解析后位于 `root` 之外的链接
+/// - `preserve` 为 `true` 时记录链接本身
+/// - 否则跳过链接
+fn collect_tree(root: &Path, follow: bool, preserve: bool) -> Tree {
+    let mut tree = Tree::default();
+    if !root.is_dir() {
+        return tree;
+    }
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+    let mut escaped = 0;
+    let walker = WalkDir::new(root)
+        .follow_links(follow)
+        .into_iter()
+        .filter_entry(|e| {
+            let inside = !follow
+                || !e.path_is_symlink()
+                || e.path()
+                    .canonicalize()
+                    .is_ok_and(|p| p.starts_with(&canonical_root));
+            if !inside {
+                escaped += 1;
+            }
+            inside
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let relative = relative.to_path_buf();
+
+        // 不跟随链接时，符号链接的类型是链接本身
+        if entry.file_type().is_symlink() {
+            match std::fs::read_link(entry.path()) {
+                Ok(original) if preserve => {
+                    let dir = entry.path().is_dir();
+                    tree.links.insert(relative, (original, dir));
+                }
+                _ => tree.skipped_links += 1,
+            }
+        } else if entry.file_type().is_file()
+            && let Ok(metadata) = entry.metadata()
+        {
+            tree.files
+                .insert(relative, (metadata.len(), metadata.modified().ok()));
+        }
+    }
+
+    tree.skipped_links += escaped;
+    tree
+}
+
+/// 确认目标路径的父目录位于目标目录之内
+///
+/// 目标目录中的子目录可能是指向外部的符号链接，在创建目录和写入前
+/// 检查最近一个已存在的上级目录的实际路径。
+fn ensure_inside(root: &Path, path: &Path) -> Result<()> {
+    let Some(existing) = path.ancestors().skip(1).find(|p| p.exists()) else {
+        return Ok(());
+    };
+    let resolved = existing
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", existing.display()))?;
+    if !resolved.starts_with(root) {
+        anyhow::bail!(tr!(
+            "目标路径经由符号链接指向目标目录之外: {} -> {}",
+            path.display(),
+            resolved.display()
+        ));
+    }
+    Ok(())
+}
+
+/// 删除已存在的符号链接（不影响链接指向的内容）
+fn remove_link(path: &Path) -> Result<()> {
+    if std::fs::symlink_metadata(path).is_ok_and(|m| m.is_symlink()) {
+        // Windows 上指向目录的链接需要用 remove_dir 删除
+        std::fs::remove_file(path)
+            .or_else(|_| std::fs::remove_dir(path))
+            .with_context(|| tr!("删除符号链接失败: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// 判断目标文件是否需要更新
+async fn needs_copy(
+    source: &Path,
+    target: &Path,
+    source_meta: FileMeta,
+    target_meta: FileMeta,
+    checksum: bool,
+) -> Result<bool> {
+    if source_meta.0 != target_meta.0 {
+        return Ok(true);
+    }
+    if checksum {
+        return Ok(calculate_file_hash(source).await? != calculate_file_hash(target).await?);
+    }
+    Ok(source_meta.1 != target_meta.1)
+}
+
+/// 创建目标文件的父目录，并确认其位于目标目录之内
+async fn prepare_parent(root: &Path, target: &Path) -> Result<()> {
+    ensure_inside(root, target)?;
+    if let Some(parent) = target.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| tr!("创建目录失败: {}", parent.display()))?;
+    }
+    Ok(())
+}
+
+/// 复制文件并保留源文件的修改时间
+///
+/// 先复制到目标旁的临时文件再重命名，中断时不会留下不完整的文件。
+/// 目标位置已有符号链接时先删除链接，避免写入链接指向的文件。
+async fn copy_file(
+    root: &Path,
+    source: &Path,
+    target: &Path,
+    modified: Option<SystemTime>,
+) -> Result<()> {
+    prepare_parent(root, target).await?;
+    remove_link(target)?;
+    let temp = TempPath::beside(target)?;
+    tokio::fs::copy(source, temp.path())
+        .await
+        .with_context(|| tr!("复制文件失败: {}", source.display()))?;
+    if let Some(modified) = modified {
+        File::options()
+            .write(true)
+            .open(temp.path())
+            .and_then(|file| file.set_modified(modified))
+            .with_context(|| tr!("设置修改时间失败: {}", target.display()))?;
+    }
+    temp.persist(target)
+}
+
+/// 在目标位置重建符号链接，替换已存在的文件或链接
+async fn copy_link(root: &Path, original: &Path, target: &Path, dir: bool) -> Result<()> {
+    prepare_parent(root, target).await?;
+    remove_link(target)?;
+    if target.is_file() {
+        tokio::fs::remove_file(target)
+            .await
+            .with_context(|| tr!("删除文件失败: {}", target.display()))?;
+    }
+    create_symlink(original, target, dir)
+}
+
+/// 命令执行函数
+///
+/// 1. 收集两侧文件，计算需要复制的文件和目标中多余的文件
+/// 2. 输出计划，或复制文件并在确认后将多余文件移动到回收站
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 同步完成
+/// * `Err(anyhow::Error)` - 地址无效、源目录无法访问或复制失败
+pub async fn run(args: SyncArgs) -> Result<()> {
+    let source = parse_endpoint(&args.source)?;
+    let target = parse_endpoint(&args.target)?;
+    if !source.is_dir() {
+        anyhow::bail!(tr!("源目录不存在: {}", source.display()));
+    }
+
+    println!(
+        "{}",
+        tr!("{} 目录同步工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", source.display()));
+    println!("{}", tr!("目标目录: {}", target.display()));
+    println!();
+
+    let source_tree = collect_tree(&source, args.follow_symlinks, args.preserve_symlinks);
+    // 目标目录中的链接从不跟随，避免读取或删除目标目录之外的文件
+    let target_tree = collect_tree(&target, false, args.preserve_symlinks);
+    let source_files = &source_tree.files;
+    let target_files = &target_tree.files;
+    stats::add(Counter::Scanned, source_files.len() as u64);
+
+    let mut copies: Vec<(PathBuf, PathBuf, FileMeta)> = Vec::new();
+    for (relative, source_meta) in source_files {
+        let from = source.join(relative);
+        let to = target.join(relative);
+        let changed = match target_files.get(relative) {
+            Some(target_meta) => {
+                needs_copy(&from, &to, *source_meta, *target_meta, args.checksum).await?
+            }
+            None => true,
+        };
+        if changed {
+            copies.push((from, to, *source_meta));
+        }
+    }
+
+    // 链接目标不同或目标中不存在的符号链接
+    let links: Vec<(PathBuf, PathBuf, &(PathBuf, bool))> = source_tree
+        .links
+        .iter()
+        .filter(|(relative, (original, _))| {
+            target_tree.links.get(*relative).map(|(o, _)| o) != Some(original)
+        })
+        .map(|(relative, link)| (source.join(relative), target.join(relative), link))
+        .collect();
+
+    let extras: Vec<PathBuf> = if args.delete {
+        let in_source = |relative: &PathBuf| {
+            source_files.contains_key(relative) || source_tree.links.contains_key(relative)
+        };
+        target_files
+            .keys()
+            .chain(target_tree.links.keys())
+            .filter(|relative| !in_source(relative))
+            .map(|relative| target.join(relative))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if source_tree.skipped_links > 0 {
+        println!(
+            "{}",
+            tr!(
+                "跳过符号链接: {} 个（使用 --follow-symlinks 或 --preserve-symlinks 处理链接）",
+                source_tree.skipped_links
+            )
+        );
+        println!();
+    }
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        let operations: Vec<Operation> = copies
+            .iter()
+            .map(|(from, to, _)| Operation::new(OperationKind::Copy, from, Some(to)))
+            .chain(
+                links
+                    .iter()
+                    .map(|(from, to, _)| Operation::new(OperationKind::Copy, from, Some(to))),
+            )
+            .chain(
+                extras
+                    .iter()
+                    .map(|path| Operation::new(OperationKind::Trash, path, None)),
+            )
+            .collect();
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    // 写入前解析目标目录的实际路径，用于检查写入位置
+    tokio::fs::create_dir_all(&target)
+        .await
+        .with_context(|| tr!("创建目录失败: {}", target.display()))?;
+    let target_root = target
+        .canonicalize()
+        .with_context(|| tr!("无法访问目录: {}", target.display()))?;
+
+    let mut copied_size = 0;
+    for (from, to, (size, modified)) in &copies {
+        copy_file(&target_root, from, to, *modified).await?;
+        copied_size += size;
+        stats::add(Counter::Processed, 1);
+        stats::add(Counter::BytesIn, *size);
+        stats::add(Counter::BytesOut, *size);
+        println!("{}", tr!("复制: {}", to.display()));
+    }
+
+    for (_, to, (original, dir)) in &links {
+        copy_link(&target_root, original, to, *dir).await?;
+        println!(
+            "{}",
+            tr!("链接: {} -> {}", to.display(), original.display())
+        );
+    }
+
+    let mut trashed = 0;
+    if !extras.is_empty() && confirm_destructive("移动到回收站", &extras, args.yes)? {
+        for path in &extras {
+            match trash::delete(path) {
+                Ok(_) => {
+                    trashed += 1;
+                    stats::add(Counter::Deleted, 1);
+                    println!("{}", tr!("✓ 已将文件移动到回收站: {}", path.display()));
+                }
+                Err(e) => {
+                    stats::add(Counter::Failed, 1);
+                    println!("{}", tr!("✗ 移动到回收站失败: {} - {}", path.display(), e));
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!(
+        "{}",
+        tr!("复制文件: {} ({})", copies.len(), ByteSize(copied_size))
+    );
+    if args.preserve_symlinks {
+        println!("{}", tr!("符号链接: {}", links.len()));
+    }
+    println!("{}", tr!("未变化: {}", source_files.len() - copies.len()));
+    if args.delete {
+        println!("{}", tr!("移动到回收站: {}", trashed));
+    }
+
+    Ok(())
+}
@@ -5,14 +5,25 @@
 //! 1. 以相对路径（不带前导斜杠）在文件内容中搜索，找到则认为**已使用**
 //! 2. 若未找到相对路径，再以文件名搜索，未找到则认为**未使用**
 //! 3. 其他情况标记为**待定**
-
+//!
+//! 默认只在指定扩展名的代码文件中搜索；`--search-all-text` 模式下搜索所有文本文件
+//! （包括 .json、.md、.toml、.yaml 等配置文件和无匹配扩展名的打包产物），
+//! 通过检查文件开头是否包含 NUL 字节排除二进制文件。
+
+use crate::utils::confirm::confirm_destructive;
+use crate::utils::i18n::{tr, translate};
+use crate::utils::plan::{Operation, OperationKind, print_plan};
+use crate::utils::stats::{self, Counter};
 use anyhow::{Context, Result};
+use bytesize::ByteSize;
 use clap::Args;
 use grep_regex::RegexMatcherBuilder;
 use grep_searcher::SearcherBuilder;
 use grep_searcher::sinks::UTF8;
 use ignore::WalkBuilder;
+use serde::Serialize;
 use std::collections::HashSet;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
@@ -75,6 +86,61 @@ pub struct UnusedFilesArgs {
         long_help = "要在其中搜索引用的代码文件扩展名，逗号分隔，不带点，大小写不敏感。例如：js,ts,css"
     )]
     pub code_extensions: String,
+
+    /// 搜索所有文本文件
+    #[arg(
+        long,
+        help = "在所有文本文件中搜索引用",
+        long_help = "忽略 --code-extensions，在所有文本文件中搜索引用，包括 .json、.md、.toml、.yaml 等配置文件和压缩后的打包产物。文件开头包含 NUL 字节的视为二进制文件并跳过，超过 --max-text-size 的文件也会跳过。更准确但更慢。"
+    )]
+    pub search_all_text: bool,
+
+    /// 搜索的文本文件大小上限
+    #[arg(
+        long,
+        value_name = "SIZE",
+        default_value = "5MiB",
+        requires = "search_all_text",
+        help = "--search-all-text 模式下搜索的文件大小上限",
+        long_help = "--search-all-text 模式下跳过超过该大小的文件，避免搜索大型日志和数据文件，例如 1MB、10MiB。默认 5MiB。"
+    )]
+    pub max_text_size: ByteSize,
+
+    /// 删除未使用的文件
+    ///
+    /// 启用后将确定未使用的文件移动到回收站（待定的文件不会删除）。
+    #[arg(
+        long,
+        help = "将未使用的文件移动到回收站",
+        long_help = "检查完成后将确定未使用的文件移动到回收站，待定的文件不会删除。检测结果可能有误报，删除前会列出文件并要求确认。"
+    )]
+    pub delete: bool,
+
+    /// 跳过删除确认
+    #[arg(
+        short = 'y',
+        long,
+        help = "跳过删除确认",
+        long_help = "启用 --delete 时默认会列出将被删除的文件并要求确认，开启后跳过确认直接执行，适合自动化场景。"
+    )]
+    pub yes: bool,
+
+    /// 仅输出执行计划
+    #[arg(
+        long,
+        help = "仅输出执行计划，不实际删除",
+        long_help = "与 --delete 一起使用时，列出将被移动到回收站的文件及其大小，但不实际删除。"
+    )]
+    pub dry_run: bool,
+
+    /// 以 JSON 格式输出
+    #[arg(
+        long,
+        conflicts_with = "delete",
+        help = "以 JSON 格式输出",
+        long_help = "以 JSON 对象形式输出已使用数量、未使用和待定文件列表（含大小，按大小降序）及各自的总大小，适合由其他工具处理。不能与 --delete 同时使用。"
+    )]
+    pub json: bool,
 }
 
 /// 获取文件相对于基础目录的相对路径（不带前导斜杠）
@@ -90,12 +156,12 @@ pub struct UnusedFilesArgs {
 fn get_relative_path(file_path: &Path, base_dir: &Path) -> Result<String> {
     let relative = file_path
         .strip_prefix(base_dir)
-        .with_context(|| format!("无法获取相对路径: {}", file_path.display()))?;
+        .with_context(|| tr!("无法获取相对路径: {}", file_path.display()))?;
 
     // 转换为字符串，并使用正斜杠
     let path_str = relative
         .to_str()
-        .context("路径包含无效的 UTF-8 字符")?
+        .with_context(|| tr!("路径包含无效的 UTF-8 字符"))?
         .replace('\\', "/");
 
     Ok(path_str)
@@ -122,7 +188,7 @@ fn search_in_file(
     // 创建字面量匹配器（转义特殊字符）
     let matcher = RegexMatcherBuilder::new()
         .build(&regex::escape(pattern))
-        .context("创建匹配器失败")?;
+        .with_context(|| tr!("创建匹配器失败"))?;
 
     // 用于记录是否找到匹配
     let mut found = false;
@@ -140,12 +206,27 @@ fn search_in_file(
     Ok(found)
 }
 
+/// 判断文件是否为文本文件
+///
+/// 读取文件开头 8KB，不包含 NUL 字节即视为文本文件（与 git、ripgrep 的判断方式相同）。
+fn is_text_file(path: &Path) -> bool {
+    let mut buffer = [0u8; 8192];
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return false;
+    };
+    match file.read(&mut buffer) {
+        Ok(len) => !buffer[..len].contains(&0),
+        Err(_) => false,
+    }
+}
+
 /// 收集目录中的所有代码文件路径
 ///
 /// # 参数
 ///
 /// * `search_dir` - 要搜索的目录路径
 /// * `code_extensions` - 代码文件扩展名集合
+/// * `all_text` - 搜索所有文本文件时的文件大小上限（字节数），为 `None` 时只按扩展名收集
 ///
 /// # 返回值
 ///
@@ -153,6 +234,7 @@ fn search_in_file(
 fn collect_code_files(
     search_dir: &Path,
     code_extensions: &HashSet<String>,
+    all_text: Option<u64>,
 ) -> Result<Vec<PathBuf>> {
     let mut code_files = Vec::new();
 
@@ -163,7 +245,7 @@ fn collect_code_files(
         .build();
 
     for entry in walker {
-        let entry = entry.context("遍历目录时出错")?;
+        let entry = entry.with_context(|| tr!("遍历目录时出错"))?;
         let path = entry.path();
 
         // 只处理文件
@@ -171,6 +253,15 @@ fn collect_code_files(
             continue;
         }
 
+        // 搜索所有文本文件时按大小和内容判断
+        if let Some(max_size) = all_text {
+            let small_enough = entry.metadata().is_ok_and(|m| m.len() <= max_size);
+            if small_enough && is_text_file(path) {
+                code_files.push(path.to_path_buf());
+            }
+            continue;
+        }
+
         // 只收集指定扩展名的代码文件
         if let Some(ext) = path.extension() {
             let ext_str = ext.to_string_lossy().to_lowercase();
@@ -237,7 +328,7 @@ fn check_file_status(
     let file_name = file_path
         .file_name()
         .and_then(|n| n.to_str())
-        .context("无效的文件名")?;
+        .with_context(|| tr!("无效的文件名"))?;
 
     // 第一步：搜索相对路径
     if search_in_code_files(searcher, code_files, &relative_path)? {
@@ -253,18 +344,95 @@ fn check_file_status(
     Ok(FileStatus::Unused)
 }
 
+/// 报告中的文件及其大小
+#[derive(Debug, Serialize)]
+pub struct ReportEntry {
+    /// 相对于检查目录的路径（`/` 分隔）
+    pub path: String,
+    /// 文件大小（字节数）
+    pub size: u64,
+}
+
+/// 检查结果报告（`--json` 输出）
+#[derive(Debug, Default, Serialize)]
+pub struct UnusedReport {
+    /// 已使用的文件数量
+    pub used: usize,
+    /// 未使用的文件，按大小降序
+    pub unused: Vec<ReportEntry>,
+    /// 待定的文件，按大小降序
+    pub uncertain: Vec<ReportEntry>,
+    /// 未使用文件的总大小（字节数）
+    pub unused_size: u64,
+    /// 待定文件的总大小（字节数）
+    pub uncertain_size: u64,
+}
+
+/// 输出文件列表
+fn print_entries(title: &str, entries: &[ReportEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+    println!("{} {} {}", "=".repeat(20), title, "=".repeat(20));
+    for entry in entries {
+        println!("{} ({})", entry.path, ByteSize(entry.size));
+    }
+    println!();
+}
+
+/// 输出未使用和待定文件中最大的前 10 个
+fn print_largest(report: &UnusedReport) {
+    let mut largest: Vec<(&ReportEntry, &str)> = report
+        .unused
+        .iter()
+        .map(|entry| (entry, translate("未使用")))
+        .chain(
+            report
+                .uncertain
+                .iter()
+                .map(|entry| (entry, translate("待定"))),
+        )
+        .collect();
+    if largest.is_empty() {
+        return;
+    }
+    largest.sort_by_key(|(entry, _)| std::cmp::Reverse(entry.size));
+
+    println!(
+        "{}",
+        tr!("{} 最大的文件 {}", "=".repeat(20), "=".repeat(20))
+    );
+    for (entry, label) in largest.iter().take(10) {
+        println!(
+            "{:>10}  [{}] {}",
+            ByteSize(entry.size).to_string(),
+            label,
+            entry.path
+        );
+    }
+    println!();
+}
+
 /// 命令执行函数
+///
+/// 1. 收集资源文件和待搜索的文件
+/// 2. 逐个判断资源文件的使用状态并记录大小
+/// 3. 输出报告（文本或 JSON），可选将未使用的文件移动到回收站
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 检查完成
+/// * `Err(anyhow::Error)` - 目录不存在、参数无效或搜索失败
 pub async fn run(args: UnusedFilesArgs) -> Result<()> {
     // 验证目录是否存在
     if !args.dir.exists() {
-        anyhow::bail!("目录不存在: {}", args.dir.display());
+        anyhow::bail!(tr!("目录不存在: {}", args.dir.display()));
     }
 
-    // 显示程序信息
-    println!("{}  未使用文件查找工具 {}", "=".repeat(15), "=".repeat(15));
-    println!("目录: {}", args.dir.display());
-    println!();
-
     // 解析资源文件扩展名参数
     let resource_extensions: HashSet<String> = args
         .resource_extensions
@@ -274,7 +442,7 @@ pub async fn run(args: UnusedFilesArgs) -> Result<()> {
         .collect();
 
     if resource_extensions.is_empty() {
-        anyhow::bail!("资源文件扩展名列表不能为空");
+        anyhow::bail!(tr!("资源文件扩展名列表不能为空"));
     }
 
     // 解析代码文件扩展名参数
@@ -286,26 +454,48 @@ pub async fn run(args: UnusedFilesArgs) -> Result<()> {
         .collect();
 
     if code_extensions.is_empty() {
-        anyhow::bail!("代码文件扩展名列表不能为空");
+        anyhow::bail!(tr!("代码文件扩展名列表不能为空"));
     }
 
-    println!(
-        "资源文件扩展名: {}",
-        resource_extensions
-            .iter()
-            .map(|s| s.as_str())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-    println!(
-        "代码文件扩展名: {}",
-        code_extensions
-            .iter()
-            .map(|s| s.as_str())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-    println!();
+    // JSON 模式下标准输出只包含报告
+    if !args.json {
+        println!(
+            "{}",
+            tr!("{}  未使用文件查找工具 {}", "=".repeat(15), "=".repeat(15))
+        );
+        println!("{}", tr!("目录: {}", args.dir.display()));
+        println!();
+        println!(
+            "{}",
+            tr!(
+                "资源文件扩展名: {}",
+                resource_extensions
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        );
+        if args.search_all_text {
+            println!(
+                "{}",
+                tr!("搜索范围: 所有不超过 {} 的文本文件", args.max_text_size)
+            );
+        } else {
+            println!(
+                "{}",
+                tr!(
+                    "代码文件扩展名: {}",
+                    code_extensions
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            );
+        }
+        println!();
+    }
 
     // 收集要检查的资源文件
     let files_to_check: Vec<PathBuf> = WalkDir::new(&args.dir)
@@ -323,75 +513,146 @@ pub async fn run(args: UnusedFilesArgs) -> Result<()> {
         .map(|entry| entry.path().to_path_buf())
         .collect();
 
-    if files_to_check.is_empty() {
-        println!("未找到匹配的资源文件");
+    stats::add(Counter::Scanned, files_to_check.len() as u64);
+
+    if files_to_check.is_empty() && !args.json {
+        println!("{}", tr!("未找到匹配的资源文件"));
         return Ok(());
     }
 
-    println!("找到 {} 个资源文件需要检查\n", files_to_check.len());
-
     // 预收集所有代码文件（只收集一次）
-    println!("正在收集代码文件...");
-    let code_files = collect_code_files(&args.dir, &code_extensions).context("收集代码文件失败")?;
-
-    println!("找到 {} 个代码文件\n", code_files.len());
+    let all_text = args.search_all_text.then(|| args.max_text_size.as_u64());
+    let code_files = collect_code_files(&args.dir, &code_extensions, all_text)
+        .with_context(|| tr!("收集代码文件失败"))?;
+
+    if !args.json {
+        println!(
+            "{}",
+            tr!("找到 {} 个资源文件需要检查", files_to_check.len())
+        );
+        println!("{}", tr!("找到 {} 个待搜索的文件\n", code_files.len()));
+    }
 
     // 创建可复用的搜索器实例（只创建一次）
     let mut searcher = SearcherBuilder::new().build();
 
-    // 统计计数器和路径列表
-    let mut used_count = 0;
-    let mut unused_files: Vec<String> = Vec::new();
-    let mut uncertain_files: Vec<String> = Vec::new();
-
     // 检查每个文件
+    let mut report = UnusedReport::default();
     for file_path in files_to_check {
         let relative_path = get_relative_path(&file_path, &args.dir)
-            .with_context(|| format!("获取相对路径失败: {}", file_path.display()))?;
+            .with_context(|| tr!("获取相对路径失败: {}", file_path.display()))?;
 
         let status = check_file_status(&mut searcher, &file_path, &args.dir, &code_files)
-            .with_context(|| format!("检查文件失败: {}", file_path.display()))?;
+            .with_context(|| tr!("检查文件失败: {}", file_path.display()))?;
 
+        let entry = ReportEntry {
+            path: relative_path,
+            size: file_path.metadata().map(|m| m.len()).unwrap_or(0),
+        };
         match status {
-            FileStatus::Used => {
-                used_count += 1;
-            }
+            FileStatus::Used => report.used += 1,
             FileStatus::Unused => {
-                unused_files.push(relative_path);
+                report.unused_size += entry.size;
+                report.unused.push(entry);
             }
             FileStatus::Uncertain => {
-                uncertain_files.push(relative_path);
+                report.uncertain_size += entry.size;
+                report.uncertain.push(entry);
             }
         }
     }
-
-    // 输出未使用的文件
-    if !unused_files.is_empty() {
-        println!("{} 未使用的文件 {}", "=".repeat(20), "=".repeat(20));
-        for file in &unused_files {
-            println!("{}", file);
-        }
-        println!();
+    report
+        .unused
+        .sort_by_key(|entry| std::cmp::Reverse(entry.size));
+    report
+        .uncertain
+        .sort_by_key(|entry| std::cmp::Reverse(entry.size));
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).with_context(|| tr!("序列化 JSON 失败"))?
+        );
+        return Ok(());
     }
 
-    // 输出待定的文件
-    if !uncertain_files.is_empty() {
-        println!("{} 待定的文件 {}", "=".repeat(20), "=".repeat(20));
-        for file in &uncertain_files {
-            println!("{}", file);
-        }
-        println!();
-    }
+    print_entries(&tr!("未使用的文件"), &report.unused);
+    print_entries(&tr!("待定的文件"), &report.uncertain);
+    print_largest(&report);
 
     // 显示统计信息
-    println!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20));
-    println!("已使用: {}", used_count);
-    println!("未使用: {}", unused_files.len());
-    println!("待定: {}", uncertain_files.len());
+    println!("{}", tr!("{} 统计结果 {}", "=".repeat(20), "=".repeat(20)));
+    println!("{}", tr!("已使用: {}", report.used));
+    println!(
+        "{}",
+        tr!(
+            "未使用: {} ({})",
+            report.unused.len(),
+            ByteSize(report.unused_size)
+        )
+    );
+    println!(
+        "{}",
+        tr!(
+            "待定: {} ({})",
+            report.uncertain.len(),
+            ByteSize(report.uncertain_size)
+        )
+    );
     println!(
-        "总计: {}",
-        used_count + unused_files.len() + uncertain_files.len()
+        "{}",
+        tr!(
+            "总计: {}",
+            report.used + report.unused.len() + report.uncertain.len()
+        )
     );
+    println!(
+        "{}",
+        tr!(
+            "可回收空间: {} (含待定 {})",
+            ByteSize(report.unused_size),
+            ByteSize(report.unused_size + report.uncertain_size)
+        )
+    );
+
+    // 如果未启用删除功能或没有未使用的文件,直接返回
+    if !args.delete || report.unused.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    let unused_paths: Vec<PathBuf> = report
+        .unused
+        .iter()
+        .map(|entry| args.dir.join(&entry.path))
+        .collect();
+
+    // dry-run 模式只输出执行计划
+    if args.dry_run {
+        let operations: Vec<Operation> = unused_paths
+            .iter()
+            .map(|path| Operation::new(OperationKind::Trash, path, None))
+            .collect();
+        print_plan(&operations);
+        return Ok(());
+    }
+
+    if !confirm_destructive("移动到回收站", &unused_paths, args.yes)? {
+        return Ok(());
+    }
+
+    for path in unused_paths {
+        match trash::delete(&path) {
+            Ok(_) => {
+                stats::add(Counter::Deleted, 1);
+                println!("{}", tr!("✓ 已将文件移动到回收站: {}", path.display()));
+            }
+            Err(e) => {
+                stats::add(Counter::Failed, 1);
+                println!("{}", tr!("✗ 移动到回收站失败: {} - {}", path.display(), e));
+            }
+        }
+    }
 
     Ok(())
 }
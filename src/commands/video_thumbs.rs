@@ -0,0 +1,356 @@
+//! 视频缩略图生成命令模块
+//!
+//! 本模块为目录中的每个视频生成封面帧（JPEG），并可选生成动态 WebP 预览。
+//!
+//! # 功能特性
+//!
+//! - 递归扫描目录,最多支持 3 层嵌套
+//! - 在指定时间点截取单张封面,或按时长均匀截取 N 张
+//! - 可选生成从指定时间点开始的动态 WebP 预览
+//! - 输出文件与源文件同目录,如果目标文件已存在则覆盖
+
+use crate::utils::filesystem::path_size;
+use crate::utils::i18n::tr;
+use crate::utils::media::{collect_video_files, ensure_ffmpeg, find_ffmpeg, probe_duration};
+use crate::utils::stats::{self, Counter};
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// 视频缩略图命令行参数
+#[derive(Args, Debug)]
+#[command(name = "video_thumbs")]
+#[command(version = "0.1.0")]
+#[command(
+    about = "为视频生成封面帧和动态预览",
+    long_about = "扫描指定目录(最多嵌套三层)下的视频文件,在指定时间点截取封面帧,或按时长均匀截取 N 张封面,并可选生成动态 WebP 预览。输出文件与源文件同目录:单张封面为 <名称>.jpg,多张为 <名称>-01.jpg 等,预览为 <名称>.webp。"
+)]
+pub struct VideoThumbsArgs {
+    /// 源目录路径
+    #[arg(
+        short = 's',
+        long,
+        value_name = "SOURCE_DIRECTORY",
+        help = "源目录路径（必须为目录）",
+        long_help = "指定要扫描的源目录，工具会扫描该目录及其子目录（最多三层）中的视频文件。"
+    )]
+    pub source: PathBuf,
+
+    /// 截取封面的时间点（秒）
+    #[arg(
+        short = 't',
+        long,
+        default_value_t = 5.0,
+        value_name = "SECONDS",
+        help = "截取封面的时间点（秒）",
+        long_help = "在该时间点截取单张封面，同时作为动态预览的起点。超出视频时长时改为截取视频中点。默认 5 秒。"
+    )]
+    pub timestamp: f64,
+
+    /// 均匀截取的帧数
+    #[arg(
+        short = 'n',
+        long,
+        value_name = "COUNT",
+        help = "均匀截取的封面数量",
+        long_help = "指定后忽略 --timestamp，按视频时长均匀截取 N 张封面，输出为 <名称>-01.jpg、<名称>-02.jpg 等。"
+    )]
+    pub count: Option<usize>,
+
+    /// 输出宽度
+    #[arg(
+        short = 'w',
+        long,
+        default_value_t = 640,
+        value_name = "PIXELS",
+        help = "输出图片宽度（像素）",
+        long_help = "封面和预览的宽度，高度按原始比例自动计算。默认 640。"
+    )]
+    pub width: u32,
+
+    /// 生成动态 WebP 预览
+    #[arg(
+        short = 'p',
+        long,
+        help = "生成动态 WebP 预览",
+        long_help = "开启后额外生成 <名称>.webp 动态预览，从 --timestamp 指定的时间点开始。默认关闭。"
+    )]
+    pub preview: bool,
+
+    /// 动态预览时长（秒）
+    #[arg(
+        long,
+        default_value_t = 3.0,
+        value_name = "SECONDS",
+        help = "动态预览时长（秒）",
+        long_help = "动态 WebP 预览的时长，仅在启用 --preview 时生效。默认 3 秒。"
+    )]
+    pub preview_duration: f64,
+
+    /// 未安装 ffmpeg 时下载静态构建
+    #[arg(
+        long,
+        help = "未安装 ffmpeg 时自动下载静态构建",
+        long_help = "未检测到 ffmpeg 时，下载适用于当前系统的 ffmpeg 静态构建到应用数据目录并使用，而不是通过 winget 安装。"
+    )]
+    pub download_ffmpeg: bool,
+}
+
+/// 运行 ffmpeg 并检查退出状态
+///
+/// ffmpeg 仅输出错误信息，避免批量处理时刷屏。
+async fn run_ffmpeg(cmd: &mut Command, source_path: &Path) -> Result<()> {
+    cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+
+    let mut child = cmd
+        .spawn()
+        .with_context(|| tr!("启动 ffmpeg 失败: {}", source_path.display()))?;
+
+    let status = child
+        .wait()
+        .await
+        .with_context(|| tr!("等待 ffmpeg 完成失败: {}", source_path.display()))?;
+
+    if !status.success() {
+        anyhow::bail!(tr!("ffmpeg 处理失败: {}", source_path.display()));
+    }
+
+    Ok(())
+}
+
+/// 截取视频的单帧图片
+///
+/// # 参数
+///
+/// * `source_path` - 源视频文件路径
+/// * `output_path` - 输出图片路径
+/// * `seconds` - 截取时间点（秒）
+/// * `width` - 输出宽度（像素），高度按比例计算
+///
+/// # 返回值
+///
+/// * `Ok(())` - 截取成功
+/// * `Err(anyhow::Error)` - 截取失败
+pub async fn extract_frame(
+    source_path: &Path,
+    output_path: &Path,
+    seconds: f64,
+    width: u32,
+) -> Result<()> {
+    let mut cmd = Command::new(find_ffmpeg());
+    cmd.arg("-hide_banner")
+        .arg("-loglevel")
+        .arg("error")
+        .arg("-ss")
+        .arg(format!("{:.3}", seconds))
+        .arg("-i")
+        .arg(source_path)
+        .arg("-frames:v")
+        .arg("1")
+        .arg("-vf")
+        .arg(format!("scale={}:-2", width))
+        .arg("-q:v")
+        .arg("2")
+        .arg("-y")
+        .arg(output_path);
+
+    run_ffmpeg(&mut cmd, source_path).await
+}
+
+/// 生成动态 WebP 预览
+///
+/// # 参数
+///
+/// * `source_path` - 源视频文件路径
+/// * `output_path` - 输出 WebP 路径
+/// * `start` - 起始时间点（秒）
+/// * `duration` - 预览时长（秒）
+/// * `width` - 输出宽度（像素），高度按比例计算
+///
+/// # 返回值
+///
+/// * `Ok(())` - 生成成功
+/// * `Err(anyhow::Error)` - 生成失败
+///
+/// # 技术细节
+///
+/// - 帧率降为 10fps 以控制文件大小
+/// - `-loop 0` 无限循环播放
+/// - `-an` 丢弃音频
+pub async fn generate_webp_preview(
+    source_path: &Path,
+    output_path: &Path,
+    start: f64,
+    duration: f64,
+    width: u32,
+) -> Result<()> {
+    let mut cmd = Command::new(find_ffmpeg());
+    cmd.arg("-hide_banner")
+        .arg("-loglevel")
+        .arg("error")
+        .arg("-ss")
+        .arg(format!("{:.3}", start))
+        .arg("-t")
+        .arg(format!("{:.3}", duration))
+        .arg("-i")
+        .arg(source_path)
+        .arg("-vf")
+        .arg(format!("fps=10,scale={}:-2", width))
+        .arg("-c:v")
+        .arg("libwebp")
+        .arg("-loop")
+        .arg("0")
+        .arg("-an")
+        .arg("-y")
+        .arg(output_path);
+
+    run_ffmpeg(&mut cmd, source_path).await
+}
+
+/// 为单个视频生成封面和预览
+///
+/// # 参数
+///
+/// * `video_file` - 源视频文件路径
+/// * `args` - 命令行参数
+///
+/// # 返回值
+///
+/// * `Ok(())` - 处理成功
+/// * `Err(anyhow::Error)` - 处理失败
+async fn process_video(video_file: &Path, args: &VideoThumbsArgs) -> Result<()> {
+    let duration = probe_duration(video_file)?;
+
+    // 时间点超出视频时长时,改为截取视频中点
+    let start = if args.timestamp < duration {
+        args.timestamp
+    } else {
+        duration / 2.0
+    };
+
+    // 累计生成的封面和预览大小
+    let mut written = 0;
+    match args.count {
+        Some(count) if count > 0 => {
+            let stem = video_file
+                .file_stem()
+                .and_then(|n| n.to_str())
+                .with_context(|| tr!("无效的文件名"))?;
+            for i in 0..count {
+                // 取每个等分区间的中点,避开片头和片尾
+                let seconds = duration * (i as f64 + 0.5) / count as f64;
+                let output_path = video_file.with_file_name(format!("{}-{:02}.jpg", stem, i + 1));
+                extract_frame(video_file, &output_path, seconds, args.width).await?;
+                written += path_size(&output_path);
+                println!("{}", tr!("封面: {}", output_path.display()));
+            }
+        }
+        _ => {
+            let output_path = video_file.with_extension("jpg");
+            extract_frame(video_file, &output_path, start, args.width).await?;
+            written += path_size(&output_path);
+            println!("{}", tr!("封面: {}", output_path.display()));
+        }
+    }
+
+    if args.preview {
+        let output_path = video_file.with_extension("webp");
+        generate_webp_preview(
+            video_file,
+            &output_path,
+            start,
+            args.preview_duration,
+            args.width,
+        )
+        .await?;
+        written += path_size(&output_path);
+        println!("{}", tr!("预览: {}", output_path.display()));
+    }
+
+    stats::add(Counter::Processed, 1);
+    stats::add(Counter::BytesIn, path_size(video_file));
+    stats::add(Counter::BytesOut, written);
+    Ok(())
+}
+
+/// 执行视频缩略图命令
+///
+/// # 参数
+///
+/// * `args` - 命令行参数
+///
+/// # 返回
+///
+/// 执行成功返回 `Ok(())`,失败返回错误信息
+///
+/// # 错误
+///
+/// - 当源目录不存在或无法访问时返回错误
+/// - 当源路径不是目录时返回错误
+/// - 当 ffprobe/ffmpeg 处理失败时返回错误
+pub async fn run(args: VideoThumbsArgs) -> Result<()> {
+    // 确保 ffmpeg 可用
+    ensure_ffmpeg(args.download_ffmpeg)?;
+
+    // 规范化源目录路径并检查可访问性
+    let source_dir = args
+        .source
+        .canonicalize()
+        .with_context(|| tr!("无法访问源目录: {}", args.source.display()))?;
+
+    // 确保源路径是目录而非文件
+    if !source_dir.is_dir() {
+        anyhow::bail!(tr!("源路径必须是目录: {}", source_dir.display()));
+    }
+
+    // 打印任务信息
+    println!(
+        "{}",
+        tr!("{} 视频缩略图工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", source_dir.display()));
+    match args.count {
+        Some(count) if count > 0 => println!("{}", tr!("截取方式: 均匀截取 {} 张", count)),
+        _ => println!("{}", tr!("截取方式: 第 {} 秒", args.timestamp)),
+    }
+    println!(
+        "{}",
+        tr!(
+            "动态预览: {}",
+            if args.preview {
+                tr!("已启用")
+            } else {
+                tr!("未启用")
+            }
+        )
+    );
+    println!();
+
+    // 收集所有视频文件(最多扫描 3 层目录)
+    let video_files = collect_video_files(&source_dir, 3);
+
+    if video_files.is_empty() {
+        println!("{}", tr!("没有找到视频文件"));
+        return Ok(());
+    }
+
+    println!("{}", tr!("找到 {} 个视频文件\n", video_files.len()));
+    stats::add(Counter::Scanned, video_files.len() as u64);
+
+    // 逐个生成缩略图
+    for (index, video_file) in video_files.iter().enumerate() {
+        println!("{}", tr!("进度: {}/{}", index + 1, video_files.len()));
+        println!("{}", tr!("处理: {}", video_file.display()));
+        let result = process_video(video_file, &args).await;
+        if result.is_err() {
+            stats::add(Counter::Failed, 1);
+        }
+        result.with_context(|| tr!("处理 {} 失败", video_file.display()))?;
+        println!();
+    }
+
+    println!("{}", tr!("操作成功完成！"));
+    Ok(())
+}
@@ -7,21 +7,24 @@
 //!
 //! - 递归扫描目录,最多支持 3 层嵌套
 //! - 支持多种输入视频格式 (mp4, mkv, avi, mov 等)
-//! - 转码为 AV1 编码,质量参数 CRF=25
+//! - 转码为 AV1 编码,质量参数 CRF 默认 25
+//! - 支持自动选择或强制指定 AV1 编码器
 //! - 保留原始文件路径,根据目标格式更新扩展名
 //! - 如果目标文件已存在则覆盖
-
-use crate::utils::filesystem::get_file_extension;
-use crate::utils::media::{ensure_ffmpeg, test_encoder};
+//! - 先写入目标目录中的临时文件，转码完成后再重命名为目标文件，中断或失败不会留下不完整的文件
+
+use crate::utils::error::Failures;
+use crate::utils::filesystem::path_size;
+use crate::utils::i18n::tr;
+use crate::utils::media::{Av1Encoder, collect_video_files, ensure_ffmpeg, find_ffmpeg};
+use crate::utils::stats::{self, Counter};
+use crate::utils::tempfiles::TempPath;
 use anyhow::{Context, Result};
-use cached::proc_macro::cached;
 use clap::{Args, ValueEnum};
-use std::env;
 use std::fmt::Debug;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
-use uuid::Uuid;
 
 /// 目标视频格式
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
@@ -62,102 +65,56 @@ pub struct VideoTranscodeArgs {
         long_help = "指定转码后的目标格式：webm (AV1 + Opus) 或 mp4 (AV1 + AAC)。"
     )]
     pub format: TargetFormat,
-}
 
-/// 收集指定目录下的所有视频文件
-///
-/// # 参数
-///
-/// * `source_dir` - 源目录路径
-/// * `max_depth` - 最大扫描深度
-///
-/// # 返回
-///
-/// 返回找到的所有视频文件路径列表
-fn collect_video_files(source_dir: &Path, max_depth: usize) -> Vec<PathBuf> {
-    // 支持的视频文件扩展名列表
-    let video_extensions = [
-        "mp4", "mkv", "avi", "mov", "wmv", "flv", "webm", "m4v", "3gp", "ts", "mts", "m2ts",
-    ];
-
-    let mut video_files = Vec::new();
-
-    // 递归遍历目录,收集所有视频文件
-    for entry in walkdir::WalkDir::new(source_dir)
-        .max_depth(max_depth)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-
-        // 跳过非文件项
-        if !path.is_file() {
-            continue;
-        }
+    /// AV1 编码器
+    #[arg(
+        short = 'e',
+        long,
+        value_enum,
+        default_value_t = Av1Encoder::Auto,
+        help = "AV1 编码器",
+        long_help = "指定使用的 AV1 编码器：auto（按 NVENC > QSV > AMF > SVT-AV1 自动选择）、nvenc、qsv、amf 或 svt。可先运行 av1-bench 比较各编码器速度。"
+    )]
+    pub encoder: Av1Encoder,
 
-        // 检查文件扩展名是否为视频格式
-        let ext = get_file_extension(path);
-        if !ext.is_empty() && video_extensions.contains(&ext.as_str()) {
-            video_files.push(path.to_path_buf());
-        }
-    }
+    /// 编码质量
+    #[arg(
+        long,
+        default_value_t = 25,
+        value_name = "CRF",
+        help = "编码质量 (CRF)",
+        long_help = "AV1 编码的 CRF 值，数值越小质量越高、文件越大。默认 25。"
+    )]
+    pub crf: u8,
 
-    video_files
-}
+    /// 未安装 ffmpeg 时下载静态构建
+    #[arg(
+        long,
+        help = "未安装 ffmpeg 时自动下载静态构建",
+        long_help = "未检测到 ffmpeg 时，下载适用于当前系统的 ffmpeg 静态构建到应用数据目录并使用，而不是通过 winget 安装。"
+    )]
+    pub download_ffmpeg: bool,
 
-/// 获取可用的 AV1 编码器（带缓存）
-///
-/// 按优先级顺序检测系统中可用的 AV1 编码器，首次检测后缓存结果。
-///
-/// # 编码器优先级
-///
-/// 1. `av1_nvenc` - NVIDIA GPU (NVENC)
-/// 2. `av1_qsv` - Intel GPU (Quick Sync Video)
-/// 3. `av1_amf` - AMD GPU (AMF)
-/// 4. `svt-av1` - SVT-AV1 (Multi-thread)
-/// 5. `libsvtav1` - SVT-AV1 (libsvtav1)
-///
-/// # 返回值
-///
-/// * `Ok(String)` - 可用编码器名称
-/// * `Err(anyhow::Error)` - 未找到可用的 AV1 编码器
-///
-/// # 技术细节
-///
-/// - 使用 `cached` 宏缓存成功结果，避免重复检测
-/// - 按优先级顺序测试编码器，返回第一个可用的编码器
-///
-/// # 示例
-///
-/// ```rust
-/// use scripts::commands::video_transcode::detect_av1_encoder;
-///
-/// match detect_av1_encoder() {
-///     Ok(encoder) => println!("使用编码器: {}", encoder),
-///     Err(e) => eprintln!("错误: {}", e),
-/// }
-/// ```
-#[cached(result = true)]
-pub fn detect_av1_encoder() -> Result<String> {
-    let priority_encoders = ["av1_nvenc", "av1_qsv", "av1_amf", "svt-av1", "libsvtav1"];
-
-    priority_encoders
-        .into_iter()
-        .find(|encoder| test_encoder(encoder))
-        .map(String::from)
-        .ok_or_else(|| {
-            anyhow::anyhow!("未找到可用的 AV1 编码器，请检查硬件驱动或安装支持 AV1 的 ffmpeg")
-        })
+    /// 出错后继续处理
+    #[arg(
+        short = 'k',
+        long,
+        help = "单个文件失败时继续处理其他文件",
+        long_help = "默认遇到第一个转码失败的文件即停止。开启后记录失败并继续处理剩余文件，结束时列出所有失败的文件及错误，并以非零退出码退出。"
+    )]
+    pub keep_going: bool,
 }
 
 /// 将视频文件转码为 WebM AV1 格式
 ///
-/// 自动检测可用的 AV1 编码器，将视频文件转换为 WebM 格式，音频使用 Opus 编码。
+/// 使用指定的 AV1 编码器将视频文件转换为 WebM 格式，音频使用 Opus 编码。
 ///
 /// # 参数
 ///
 /// * `source_path` - 源视频文件路径
 /// * `output_path` - 目标 WebM 文件路径
+/// * `encoder` - ffmpeg 编码器名称，通常由 [`Av1Encoder::resolve`] 获得
+/// * `crf` - 编码质量，数值越小质量越高
 ///
 /// # 返回值
 ///
@@ -167,8 +124,7 @@ pub fn detect_av1_encoder() -> Result<String> {
 /// # 技术细节
 ///
 /// - 使用 ffmpeg 进行转码
-/// - 自动选择可用的 AV1 编码器（优先级：NVENC > QSV > AMF > SVT-AV1）
-/// - 视频编码: AV1, CRF=25
+/// - 视频编码: AV1, CRF 由参数指定
 /// - 音频编码: Opus, 128k 码率
 /// - 线程数: 0 (自动检测)
 /// - `-y` 参数自动覆盖已存在的输出文件
@@ -183,64 +139,70 @@ pub fn detect_av1_encoder() -> Result<String> {
 /// async fn main() -> anyhow::Result<()> {
 ///     let source = Path::new("input.mp4");
 ///     let output = Path::new("output.webm");
-///     transcode_to_webm_av1(source, output).await?;
+///     transcode_to_webm_av1(source, output, "libsvtav1", 25).await?;
 ///     Ok(())
 /// }
 /// ```
-pub async fn transcode_to_webm_av1(source_path: &Path, output_path: &Path) -> Result<()> {
-    let encoder = detect_av1_encoder()?;
-
+pub async fn transcode_to_webm_av1(
+    source_path: &Path,
+    output_path: &Path,
+    encoder: &str,
+    crf: u8,
+) -> Result<()> {
     if !source_path.is_file() {
-        anyhow::bail!("源文件不存在: {}", source_path.display());
+        anyhow::bail!(tr!("源文件不存在: {}", source_path.display()));
     }
 
-    let temp_file = env::temp_dir().join(format!("{}.webm", Uuid::now_v7()));
+    // 先输出到目标目录中的临时文件，完成后再重命名，避免留下不完整的输出文件
+    let temp_file = TempPath::beside(output_path)?;
 
-    let mut cmd = Command::new("ffmpeg");
+    let mut cmd = Command::new(find_ffmpeg());
     cmd.arg("-i")
         .arg(source_path)
         .arg("-threads")
         .arg("0")
         .arg("-c:v")
-        .arg(&encoder)
+        .arg(encoder)
         .arg("-crf")
-        .arg("25")
+        .arg(crf.to_string())
         .arg("-c:a")
         .arg("libopus")
         .arg("-b:a")
         .arg("128k")
         .arg("-y")
-        .arg(&temp_file)
+        .arg(temp_file.path())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
     let mut child = cmd
         .spawn()
-        .with_context(|| format!("启动 ffmpeg 失败: {}", source_path.display()))?;
+        .with_context(|| tr!("启动 ffmpeg 失败: {}", source_path.display()))?;
 
     let status: std::process::ExitStatus = child
         .wait()
         .await
-        .with_context(|| format!("等待 ffmpeg 完成 失败: {}", source_path.display()))?;
+        .with_context(|| tr!("等待 ffmpeg 完成 失败: {}", source_path.display()))?;
 
     if !status.success() {
-        anyhow::bail!("ffmpeg 转码失败: {}", source_path.display());
+        anyhow::bail!(tr!("ffmpeg 转码失败: {}", source_path.display()));
     }
 
-    tokio::fs::copy(&temp_file, output_path).await?;
+    temp_file.persist(output_path)?;
 
-    println!("转码完成: {}", output_path.display());
+    println!("{}", tr!("转码完成: {}", output_path.display()));
     Ok(())
 }
 
 /// 将视频文件转码为 MP4 AV1 格式
 ///
-/// 自动检测可用的 AV1 编码器，将视频文件转换为 MP4 格式，音频使用 AAC 编码。
+/// 使用指定的 AV1 编码器将视频文件转换为 MP4 格式，音频使用 AAC 编码。
 ///
 /// # 参数
 ///
 /// * `source_path` - 源视频文件路径
 /// * `output_path` - 目标 MP4 文件路径
+/// * `encoder` - ffmpeg 编码器名称，通常由 [`Av1Encoder::resolve`] 获得
+/// * `crf` - 编码质量，数值越小质量越高
 ///
 /// # 返回值
 ///
@@ -250,8 +212,7 @@ pub async fn transcode_to_webm_av1(source_path: &Path, output_path: &Path) -> Re
 /// # 技术细节
 ///
 /// - 使用 ffmpeg 进行转码
-/// - 自动选择可用的 AV1 编码器（优先级：NVENC > QSV > AMF > SVT-AV1）
-/// - 视频编码: AV1, CRF=25
+/// - 视频编码: AV1, CRF 由参数指定
 /// - 音频编码: AAC, 128k 码率
 /// - 线程数: 0 (自动检测)
 /// - `-y` 参数自动覆盖已存在的输出文件
@@ -266,53 +227,57 @@ pub async fn transcode_to_webm_av1(source_path: &Path, output_path: &Path) -> Re
 /// async fn main() -> anyhow::Result<()> {
 ///     let source = Path::new("input.mkv");
 ///     let output = Path::new("output.mp4");
-///     transcode_to_mp4_av1(source, output).await?;
+///     transcode_to_mp4_av1(source, output, "libsvtav1", 25).await?;
 ///     Ok(())
 /// }
 /// ```
-pub async fn transcode_to_mp4_av1(source_path: &Path, output_path: &Path) -> Result<()> {
-    let encoder = detect_av1_encoder()?;
-
+pub async fn transcode_to_mp4_av1(
+    source_path: &Path,
+    output_path: &Path,
+    encoder: &str,
+    crf: u8,
+) -> Result<()> {
     if !source_path.is_file() {
-        anyhow::bail!("源文件不存在: {}", source_path.display());
+        anyhow::bail!(tr!("源文件不存在: {}", source_path.display()));
     }
 
-    let temp_file = env::temp_dir().join(format!("{}.mp4", Uuid::now_v7()));
+    // 先输出到目标目录中的临时文件，完成后再重命名，避免留下不完整的输出文件
+    let temp_file = TempPath::beside(output_path)?;
 
-    let mut cmd = Command::new("ffmpeg");
+    let mut cmd = Command::new(find_ffmpeg());
     cmd.arg("-i")
         .arg(source_path)
         .arg("-threads")
         .arg("0")
         .arg("-c:v")
-        .arg(&encoder)
+        .arg(encoder)
         .arg("-crf")
-        .arg("25")
+        .arg(crf.to_string())
         .arg("-c:a")
         .arg("aac")
         .arg("-b:a")
         .arg("128k")
         .arg("-y")
-        .arg(&temp_file)
+        .arg(temp_file.path())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit());
 
     let mut child = cmd
         .spawn()
-        .with_context(|| format!("启动 ffmpeg 失败: {}", source_path.display()))?;
+        .with_context(|| tr!("启动 ffmpeg 失败: {}", source_path.display()))?;
 
     let status: std::process::ExitStatus = child
         .wait()
         .await
-        .with_context(|| format!("等待 ffmpeg 完成 失败: {}", source_path.display()))?;
+        .with_context(|| tr!("等待 ffmpeg 完成 失败: {}", source_path.display()))?;
 
     if !status.success() {
-        anyhow::bail!("ffmpeg 转码失败: {}", source_path.display());
+        anyhow::bail!(tr!("ffmpeg 转码失败: {}", source_path.display()));
     }
 
-    tokio::fs::copy(&temp_file, output_path).await?;
+    temp_file.persist(output_path)?;
 
-    println!("转码完成: {}", output_path.display());
+    println!("{}", tr!("转码完成: {}", output_path.display()));
     Ok(())
 }
 
@@ -322,6 +287,8 @@ pub async fn transcode_to_mp4_av1(source_path: &Path, output_path: &Path) -> Res
 ///
 /// * `source_path` - 源视频文件路径
 /// * `format` - 目标格式 (WebM 或 MP4)
+/// * `encoder` - ffmpeg 编码器名称
+/// * `crf` - 编码质量
 ///
 /// # 返回
 ///
@@ -330,17 +297,29 @@ pub async fn transcode_to_mp4_av1(source_path: &Path, output_path: &Path) -> Res
 /// # 错误
 ///
 /// 当转码过程失败时返回错误
-async fn transcode_video(source_path: &Path, format: TargetFormat) -> Result<()> {
-    match format {
+async fn transcode_video(
+    source_path: &Path,
+    format: TargetFormat,
+    encoder: &str,
+    crf: u8,
+) -> Result<()> {
+    let output_path = match format {
         TargetFormat::Webm => {
             let output_path = source_path.with_extension("webm");
-            transcode_to_webm_av1(source_path, &output_path).await
+            transcode_to_webm_av1(source_path, &output_path, encoder, crf).await?;
+            output_path
         }
         TargetFormat::Mp4 => {
             let output_path = source_path.with_extension("mp4");
-            transcode_to_mp4_av1(source_path, &output_path).await
+            transcode_to_mp4_av1(source_path, &output_path, encoder, crf).await?;
+            output_path
         }
-    }
+    };
+
+    stats::add(Counter::Processed, 1);
+    stats::add(Counter::BytesIn, path_size(source_path));
+    stats::add(Counter::BytesOut, path_size(&output_path));
+    Ok(())
 }
 
 /// 执行视频转码命令
@@ -360,42 +339,57 @@ async fn transcode_video(source_path: &Path, format: TargetFormat) -> Result<()>
 /// - 当转码过程失败时返回错误
 pub async fn run(args: VideoTranscodeArgs) -> Result<()> {
     // 确保 ffmpeg 可用
-    ensure_ffmpeg()?;
+    ensure_ffmpeg(args.download_ffmpeg)?;
+
+    // 选择 AV1 编码器
+    let encoder = args.encoder.resolve()?;
 
     // 规范化源目录路径并检查可访问性
     let source_dir = args
         .source
         .canonicalize()
-        .with_context(|| format!("无法访问源目录: {}", args.source.display()))?;
+        .with_context(|| tr!("无法访问源目录: {}", args.source.display()))?;
 
     // 确保源路径是目录而非文件
     if !source_dir.is_dir() {
-        anyhow::bail!("源路径必须是目录: {}", source_dir.display());
+        anyhow::bail!(tr!("源路径必须是目录: {}", source_dir.display()));
     }
 
     // 打印转码任务信息
-    println!("{} 视频转码工具 {}", "=".repeat(15), "=".repeat(15));
-    println!("源目录: {}", source_dir.display());
-    println!("编码质量: CRF=25");
+    println!(
+        "{}",
+        tr!("{} 视频转码工具 {}", "=".repeat(15), "=".repeat(15))
+    );
+    println!("{}", tr!("源目录: {}", source_dir.display()));
+    println!("{}", tr!("编码器: {}", encoder));
+    println!("{}", tr!("编码质量: CRF={}", args.crf));
     println!();
 
     // 收集所有视频文件(最多扫描 3 层目录)
     let video_files = collect_video_files(&source_dir, 3);
 
     if video_files.is_empty() {
-        println!("没有找到视频文件");
+        println!("{}", tr!("没有找到视频文件"));
         return Ok(());
     }
 
-    println!("找到 {} 个视频文件\n", video_files.len());
+    println!("{}", tr!("找到 {} 个视频文件\n", video_files.len()));
+    stats::add(Counter::Scanned, video_files.len() as u64);
 
     // 逐个转码视频文件
+    let mut failures = Failures::default();
     for (index, video_file) in video_files.iter().enumerate() {
-        println!("进度: {}/{}", index + 1, video_files.len());
-        transcode_video(video_file, args.format).await?;
+        println!("{}", tr!("进度: {}/{}", index + 1, video_files.len()));
+        if let Err(e) = transcode_video(video_file, args.format, &encoder, args.crf).await {
+            if !args.keep_going {
+                return Err(e);
+            }
+            failures.record(video_file, e);
+        }
         println!();
     }
+    failures.finish()?;
 
-    println!("操作成功完成！");
+    println!("{}", tr!("操作成功完成！"));
     Ok(())
 }
@@ -2,8 +2,9 @@
 //!
 //! 一个集成了多种文件处理功能的命令行工具，支持子命令模式。
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use utils::error::ErrorFormat;
+use utils::i18n::{Lang, tr};
 
 mod commands;
 mod utils;
@@ -22,6 +23,38 @@ mod utils;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// 错误输出格式
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = ErrorFormat::Text,
+        help = "错误输出格式",
+        long_help = "text 输出人类可读的错误信息；json 向标准错误输出单行 JSON 对象 {\"error\": {kind, code, message, causes}}。退出码：1 一般错误，2 参数错误，3 配置错误，4 网络错误，5 部分失败，6 校验失败，7 缺少外部工具。"
+    )]
+    error_format: ErrorFormat,
+
+    /// 运行统计输出文件
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "将运行统计保存为 JSON 文件",
+        long_help = "命令结束后将执行记录保存为 JSON 文件（已存在时覆盖），包括子命令、参数、开始时间、耗时、退出码，以及扫描、处理、删除、失败的数量和读写字节数，便于仪表盘或监控脚本读取。"
+    )]
+    stats_json: Option<std::path::PathBuf>,
+
+    /// 输出语言
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "LANG",
+        help = "输出语言",
+        long_help = "命令输出和错误信息使用的语言：zh 中文，en 英文。未指定时根据环境变量 LC_ALL、LC_MESSAGES、LANG 判断（以 en 开头为英文），否则使用中文。帮助信息始终为中文。"
+    )]
+    lang: Option<Lang>,
 }
 
 /// 子命令枚举
@@ -29,30 +62,179 @@ struct Cli {
 /// 定义了所有支持的子命令，每个子命令对应一个具体的功能模块。
 #[derive(Subcommand, Debug)]
 enum Commands {
+    /// 从视频文件中提取音频
+    AudioExtract(commands::audio_extract::AudioExtractArgs),
+    /// 比较各 AV1 编码器的速度和输出大小
+    Av1Bench(commands::av1_bench::Av1BenchArgs),
+    /// 将目录备份为带时间戳和哈希的 7z 快照
+    Backup(commands::backup::BackupArgs),
     /// 批量压缩目录下的文件和子目录为 7z 格式
     BatchCompress(commands::batch_compress::BatchCompressArgs),
+    /// 将旧文件移动到回收站
+    CleanOld(commands::clean_old::CleanOldArgs),
+    /// 清理异常退出残留的临时文件
+    CleanTemp(commands::clean_temp::CleanTempArgs),
+    /// 查看或编辑配置文件
+    Config(commands::config::ConfigArgs),
+    /// 比较两个目录树的差异
+    DirDiff(commands::dir_diff::DirDiffArgs),
+    /// 检查外部工具是否可用
+    Doctor(commands::doctor::DoctorArgs),
+    /// 分析目录的磁盘占用
+    Du(commands::du::DuArgs),
+    /// 使用 7-Zip 解压压缩包
+    Extract(commands::extract::ExtractArgs),
+    /// 将嵌套目录中的文件移动到同一目录
+    Flatten(commands::flatten::FlattenArgs),
+    /// 整理目录下的 git 仓库
+    GitClean(commands::git_clean::GitCleanArgs),
+    /// 计算文件哈希或校验清单
+    Hash(commands::hash::HashArgs),
     /// 将文件从源目录复制到目标目录，使用哈希值重命名
     HashCopy(commands::hash_copy::HashCopyArgs),
+    /// 查询命令执行历史
+    History(commands::history::HistoryArgs),
+    /// 校验并合并分割后的文件
+    Join(commands::join::JoinArgs),
+    /// 生成媒体文件信息报告
+    MediaInfo(commands::media_info::MediaInfoArgs),
+    /// 按保留策略清理过期的压缩包
+    PruneArchives(commands::prune_archives::PruneArchivesArgs),
+    /// 按模板和规则批量重命名文件
+    Rename(commands::rename::RenameArgs),
+    /// 将 git 仓库镜像到另一个仓库
+    RepoMirror(commands::repo_mirror::RepoMirrorArgs),
     /// 查找软件卸载残留
     ResidueSearch(commands::residue_search::ResidueSearchArgs),
+    /// 按计划执行任务
+    Schedule(commands::schedule::ScheduleArgs),
+    /// 管理系统密钥环中的密钥
+    Secrets(commands::secrets::SecretsArgs),
+    /// 将大文件分割为多个分块
+    Split(commands::split::SplitArgs),
+    /// 将源目录单向同步到目标目录
+    Sync(commands::sync::SyncArgs),
     /// 查找目录中未被使用的文件
     UnusedFiles(commands::unused_files::UnusedFilesArgs),
+    /// 为视频生成封面帧和动态预览
+    VideoThumbs(commands::video_thumbs::VideoThumbsArgs),
     /// 将视频文件转码为 WebM AV1 格式
     VideoTranscode(commands::video_transcode::VideoTranscodeArgs),
 }
 
-/// 主函数
-///
-/// 程序入口点，负责解析命令行参数并调用相应的子命令处理函数。
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
-
-    match cli.command {
+/// 执行子命令
+async fn run_command(command: Commands) -> anyhow::Result<()> {
+    match command {
+        Commands::AudioExtract(args) => commands::audio_extract::run(args).await,
+        Commands::Av1Bench(args) => commands::av1_bench::run(args).await,
+        Commands::Backup(args) => commands::backup::run(args).await,
         Commands::BatchCompress(args) => commands::batch_compress::run(args).await,
+        Commands::CleanOld(args) => commands::clean_old::run(args).await,
+        Commands::CleanTemp(args) => commands::clean_temp::run(args).await,
+        Commands::Config(args) => commands::config::run(args).await,
+        Commands::DirDiff(args) => commands::dir_diff::run(args).await,
+        Commands::Doctor(args) => commands::doctor::run(args).await,
+        Commands::Du(args) => commands::du::run(args).await,
+        Commands::Extract(args) => commands::extract::run(args).await,
+        Commands::Flatten(args) => commands::flatten::run(args).await,
+        Commands::GitClean(args) => commands::git_clean::run(args).await,
+        Commands::Hash(args) => commands::hash::run(args).await,
         Commands::HashCopy(args) => commands::hash_copy::run(args).await,
+        Commands::History(args) => commands::history::run(args).await,
+        Commands::Join(args) => commands::join::run(args).await,
+        Commands::MediaInfo(args) => commands::media_info::run(args).await,
+        Commands::PruneArchives(args) => commands::prune_archives::run(args).await,
+        Commands::Rename(args) => commands::rename::run(args).await,
+        Commands::RepoMirror(args) => commands::repo_mirror::run(args).await,
         Commands::ResidueSearch(args) => commands::residue_search::run(args).await,
+        Commands::Schedule(args) => commands::schedule::run(args).await,
+        Commands::Secrets(args) => commands::secrets::run(args).await,
+        Commands::Split(args) => commands::split::run(args).await,
+        Commands::Sync(args) => commands::sync::run(args).await,
         Commands::UnusedFiles(args) => commands::unused_files::run(args).await,
+        Commands::VideoThumbs(args) => commands::video_thumbs::run(args).await,
         Commands::VideoTranscode(args) => commands::video_transcode::run(args).await,
     }
 }
+
+/// 记录命令结束：写入执行历史，输出运行统计，按需保存统计文件
+fn finish_invocation(
+    invocation: &utils::history::Invocation,
+    stats_json: Option<&std::path::Path>,
+    code: i32,
+    error: Option<String>,
+) {
+    let entry = invocation.finish(code, error);
+    utils::stats::print_stats(&entry);
+    if let Some(path) = stats_json
+        && let Err(e) = utils::stats::write_stats_json(path, &entry)
+    {
+        eprintln!("{}", tr!("警告: {}", format!("{:#}", e)));
+    }
+}
+
+/// 主函数
+///
+/// 程序入口点，负责读取配置文件中的默认参数、解析命令行参数并调用相应的子命令处理函数。
+/// 子命令失败时按错误类型输出错误信息并以对应的退出码退出；
+/// 按 Ctrl+C 中断时取消子命令，清理登记的临时文件后以退出码 130 退出。
+/// 每次调用结束（包括出错和中断）时将子命令、脱敏后的参数、退出码和运行统计写入执行历史。
+#[tokio::main]
+async fn main() {
+    // 配置文件无效时给出警告并忽略,保证 `scripts config edit` 仍可用于修复
+    let command = utils::config::load_config()
+        .and_then(|config| utils::config::apply_config_defaults(Cli::command(), &config))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", tr!("警告: {}，已忽略配置文件", format!("{:#}", e)));
+            Cli::command()
+        });
+    let matches = command.get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    utils::i18n::set_lang(cli.lang.unwrap_or_else(utils::i18n::detect_lang));
+
+    let error_format = cli.error_format;
+
+    // 记录执行历史和运行统计
+    let invocation = matches
+        .subcommand_name()
+        .map(|name| utils::history::Invocation::start(&Cli::command(), name));
+    let stats_json = cli.stats_json.clone();
+
+    // 在独立任务中等待中断信号，子命令仍在运行时即可清理登记的文件并退出
+    let interrupted = invocation.clone();
+    let interrupted_stats_json = stats_json.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!();
+            eprintln!("{}", tr!("已中断"));
+            for path in utils::cancel::cleanup_pending() {
+                eprintln!("{}", tr!("已清理: {}", path.display()));
+            }
+            if let Some(invocation) = &interrupted {
+                finish_invocation(
+                    invocation,
+                    interrupted_stats_json.as_deref(),
+                    130,
+                    Some(tr!("已中断")),
+                );
+            }
+            std::process::exit(130);
+        }
+    });
+
+    let result = run_command(cli.command).await;
+
+    let (code, error) = match &result {
+        Ok(()) => (0, None),
+        Err(e) => {
+            utils::error::report_error(e, error_format);
+            (utils::error::exit_code(e), Some(format!("{:#}", e)))
+        }
+    };
+    if let Some(invocation) = &invocation {
+        finish_invocation(invocation, stats_json.as_deref(), code, error);
+    }
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
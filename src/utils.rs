@@ -1,8 +1,19 @@
 //! # 工具模块 (utils)
 //!
-//! 提供文件处理工具集的公共功能，包括哈希计算、文件系统操作等。
+//! 提供文件处理工具集的公共功能，包括哈希计算、文件系统操作、配置文件读取等。
 
+pub mod cancel;
 pub mod compress;
+pub mod config;
+pub mod confirm;
+pub mod error;
 pub mod filesystem;
+pub mod git;
 pub mod hash;
+pub mod history;
+pub mod i18n;
 pub mod media;
+pub mod plan;
+pub mod secrets;
+pub mod stats;
+pub mod tempfiles;
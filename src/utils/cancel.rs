@@ -0,0 +1,84 @@
+//! # 中断清理模块
+//!
+//! 记录正在生成的临时文件和未完成的输出文件。用户按 Ctrl+C 中断时，
+//! 主函数取消正在执行的子命令并删除这些文件，避免留下不完整的压缩包或临时目录。
+//!
+//! 外部进程（7z、ffmpeg、git）与本程序位于同一进程组，会同时收到中断信号并退出。
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 当前需要在中断时清理的路径
+static PENDING: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// 中断清理登记
+///
+/// 存在期间，对应路径会在中断时被删除；操作完成（成功或已自行处理失败）后
+/// 丢弃即可取消登记，不会删除文件。
+#[derive(Debug)]
+pub struct CleanupGuard {
+    /// 登记的路径
+    path: PathBuf,
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = PENDING.lock()
+            && let Some(index) = pending.iter().position(|p| p == &self.path)
+        {
+            pending.swap_remove(index);
+        }
+    }
+}
+
+/// 登记中断时需要删除的文件或目录
+///
+/// # 参数
+///
+/// * `path` - 临时文件、临时目录或未完成的输出文件
+///
+/// # 返回值
+///
+/// * `CleanupGuard` - 丢弃时取消登记
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::cancel::register_cleanup;
+///
+/// let _guard = register_cleanup(&output_path);
+/// compress_7z(item_path, &output_path, None, CompressOptions::default()).await?;
+/// // 压缩完成，离开作用域后取消登记
+/// ```
+pub fn register_cleanup(path: impl AsRef<Path>) -> CleanupGuard {
+    let path = path.as_ref().to_path_buf();
+    if let Ok(mut pending) = PENDING.lock() {
+        pending.push(path.clone());
+    }
+    CleanupGuard { path }
+}
+
+/// 删除所有已登记的路径
+///
+/// 由主函数在收到中断信号后调用。
+///
+/// # 返回值
+///
+/// * `Vec<PathBuf>` - 实际删除的路径
+pub fn cleanup_pending() -> Vec<PathBuf> {
+    let pending = match PENDING.lock() {
+        Ok(mut pending) => std::mem::take(&mut *pending),
+        Err(_) => return Vec::new(),
+    };
+
+    pending
+        .into_iter()
+        .filter(|path| {
+            if path.is_dir() {
+                std::fs::remove_dir_all(path).is_ok()
+            } else {
+                std::fs::remove_file(path).is_ok()
+            }
+        })
+        .collect()
+}
@@ -1,26 +1,52 @@
 //! # 压缩相关工具
 //!
-//! 提供基于 7-Zip 的通用压缩函数，例如将文件或目录压缩为 .7z。
+//! 提供基于 7-Zip 的通用压缩和解压函数，例如将文件或目录压缩为 .7z。
 
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::filesystem::long_path;
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
 use cached::proc_macro::cached;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Stdio;
 
-/// 查找系统中安装的 7-Zip 可执行文件（带缓存）
+/// 7-Zip 压缩选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompressOptions {
+    /// 压缩级别 0-9（`-mx`），0 为仅存储，9 为极限压缩；`None` 时使用 7-Zip 默认级别 5
+    pub level: Option<u8>,
+    /// 分卷大小（字节数，`-v`），`None` 时不分卷
+    pub volume_size: Option<u64>,
+}
+
+/// 获取分卷压缩包第一个分卷的路径：`<压缩包>.001`
+///
+/// # 参数
+///
+/// * `output_path` - 压缩包路径，例如 `photos.7z`
+///
+/// # 返回值
 ///
-/// 首次调用时按优先级顺序查找 7-Zip：
+/// * `PathBuf` - 第一个分卷路径，例如 `photos.7z.001`
+pub fn first_volume_path(output_path: &Path) -> PathBuf {
+    let mut name = output_path.as_os_str().to_os_string();
+    name.push(".001");
+    PathBuf::from(name)
+}
+
+/// 在常见安装位置查找 7-Zip 可执行文件
+///
+/// 按优先级顺序查找：
 /// 1. Windows 常见安装路径（Program Files 和 Program Files (x86)）
 /// 2. 用户目录下的安装路径
 ///
-/// 后续调用直接返回缓存结果，避免重复查找。
+/// # 返回值
 ///
-/// # Panics
-///
-/// 如果未找到 7-Zip 可执行文件，会 panic。
-#[cached]
-pub fn find_7z() -> PathBuf {
-    let home_dir = dirs::home_dir().unwrap();
+/// * `Some(PathBuf)` - 7-Zip 可执行文件路径
+/// * `None` - 未找到 7-Zip
+pub fn locate_7z() -> Option<PathBuf> {
+    let home_dir = dirs::home_dir().unwrap_or_default();
     let common_paths = [
         PathBuf::from("C:\\Program Files\\7-Zip\\7z.exe"),
         PathBuf::from("C:\\Program Files (x86)\\7-Zip\\7z.exe"),
@@ -28,12 +54,26 @@ pub fn find_7z() -> PathBuf {
         home_dir.join("AppData\\Local\\Programs\\7-Zip\\7z.exe"),
         home_dir.join("7-Zip\\7z.exe"),
     ];
-    for path in &common_paths {
-        if path.exists() {
-            return path.clone();
-        }
-    }
-    panic!("未找到 7z 可执行文件。请从 https://www.7-zip.org/ 安装 7-Zip");
+    common_paths.into_iter().find(|path| path.exists())
+}
+
+/// 查找系统中安装的 7-Zip 可执行文件（带缓存）
+///
+/// 首次调用时通过 [`locate_7z`] 查找，找到后缓存结果，避免重复查找。
+///
+/// # 返回值
+///
+/// * `Ok(PathBuf)` - 7-Zip 可执行文件路径
+/// * `Err(anyhow::Error)` - 未找到 7-Zip，错误类型为 [`ErrorKind::Dependency`]
+#[cached(result = true)]
+pub fn find_7z() -> Result<PathBuf> {
+    locate_7z().ok_or_else(|| {
+        ScriptError::new(
+            ErrorKind::Dependency,
+            tr!("未找到 7z 可执行文件。请从 https://www.7-zip.org/ 安装 7-Zip"),
+        )
+        .into()
+    })
 }
 
 /// 使用 7-Zip 压缩文件或目录为 .7z
@@ -43,44 +83,119 @@ pub fn find_7z() -> PathBuf {
 ///
 /// # 7z 命令格式
 ///
-/// 原始命令: `7z a <archive> <item> [-p<password>] [-mhe=on]`
+/// 原始命令: `7z a <archive> <item> [-p<password>] [-mhe=on] [-mx=<level>] [-v<size>b]`
 ///
 /// 参数说明:
 /// - `a`: 添加文件到存档（Add files to archive）
 /// - `<archive>`: 目标压缩包完整路径（必须包含文件名和 `.7z` 扩展名，不能是目录）
 /// - `<item>`: 要压缩的文件或目录路径
+///
+/// 路径过长或包含 Windows 保留名称时，会通过 [`long_path`] 转换为扩展长度路径传给 7-Zip。
 /// - `-p<password>`: 设置密码保护
 /// - `-mhe=on`: 启用归档头加密（加密文件名，需要密码才能查看压缩包内容）
+/// - `-mx=<level>`: 压缩级别 0-9
+/// - `-v<size>b`: 按指定字节数分卷，输出 `<archive>.001`、`<archive>.002` ...
 ///
-/// # Panics
+/// # 返回值
 ///
-/// 如果压缩命令执行失败或返回非零退出码，会 panic。
-pub async fn compress_7z(item_path: &Path, output_path: &Path, password: Option<&str>) {
+/// * `Ok(())` - 压缩成功
+/// * `Err(anyhow::Error)` - 未找到 7-Zip（[`ErrorKind::Dependency`]）、启动失败或返回非零退出码
+pub async fn compress_7z(
+    item_path: &Path,
+    output_path: &Path,
+    password: Option<&str>,
+    options: CompressOptions,
+) -> Result<()> {
     let mut args = vec![
         "a".to_string(),
-        output_path.to_string_lossy().to_string(),
-        item_path.to_string_lossy().to_string(),
+        long_path(output_path).to_string_lossy().to_string(),
+        long_path(item_path).to_string_lossy().to_string(),
     ];
 
     if let Some(pwd) = password {
         args.push(format!("-p{}", pwd));
         args.push("-mhe=on".to_string());
     }
+    if let Some(level) = options.level {
+        args.push(format!("-mx={}", level));
+    }
+    if let Some(size) = options.volume_size {
+        args.push(format!("-v{}b", size));
+    }
 
-    let mut child = tokio::process::Command::new(find_7z())
+    // 参数中可能包含密码，错误信息中只输出路径
+    let status = tokio::process::Command::new(find_7z()?)
         .args(&args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap_or_else(|e| panic!("执行 7z 命令失败: args={:?}, error={}", args, e));
+        .status()
+        .await
+        .with_context(|| tr!("执行 7z 命令失败: {}", item_path.display()))?;
+
+    if !status.success() {
+        anyhow::bail!(tr!(
+            "7z 压缩失败: {}，退出码: {}",
+            item_path.display(),
+            status.code().unwrap_or(-1)
+        ));
+    }
+    Ok(())
+}
+
+/// 使用 7-Zip 解压压缩包到指定目录
+///
+/// 支持 7-Zip 能识别的所有格式（.7z、.zip、.rar、.tar 等）。
+/// 未提供 `password` 时，如果压缩包已加密，7-Zip 会在终端中交互式询问密码。
+///
+/// # 7z 命令格式
+///
+/// 原始命令: `7z x <archive> -o<output_dir> [-p<password>] -y`
+///
+/// 参数说明:
+/// - `x`: 解压并保留目录结构（eXtract with full paths）
+/// - `-o<output_dir>`: 输出目录（与 `-o` 之间没有空格）
+/// - `-p<password>`: 解压密码
+/// - `-y`: 对所有询问回答是（例如覆盖已存在的文件）
+///
+/// # 参数
+///
+/// * `archive_path` - 压缩包路径
+/// * `output_dir` - 解压目标目录
+/// * `password` - 可选的解压密码
+///
+/// # 返回值
+///
+/// * `Ok(())` - 解压成功
+/// * `Err(anyhow::Error)` - 未找到 7-Zip（[`ErrorKind::Dependency`]）、启动失败或返回非零退出码（例如密码错误）
+pub async fn extract_7z(
+    archive_path: &Path,
+    output_dir: &Path,
+    password: Option<&str>,
+) -> Result<()> {
+    let mut args = vec![
+        "x".to_string(),
+        long_path(archive_path).to_string_lossy().to_string(),
+        format!("-o{}", long_path(output_dir).to_string_lossy()),
+        "-y".to_string(),
+    ];
+    if let Some(pwd) = password {
+        args.push(format!("-p{}", pwd));
+    }
 
-    let status = child.wait().await.expect("等待 7z 命令完成失败");
+    let status = tokio::process::Command::new(find_7z()?)
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .await
+        .with_context(|| tr!("执行 7z 命令失败: {}", archive_path.display()))?;
 
     if !status.success() {
-        panic!(
-            "7z 压缩失败: args={:?}, 退出码: {}",
-            args,
+        anyhow::bail!(tr!(
+            "7z 解压失败: {}，退出码: {}",
+            archive_path.display(),
             status.code().unwrap_or(-1)
-        );
+        ));
     }
+    Ok(())
 }
@@ -0,0 +1,138 @@
+//! # 配置文件模块
+//!
+//! 读取 `~/.config/scripts/config.toml` 中按子命令划分的默认参数，
+//! 并将其注入为 clap 参数的默认值，命令行参数始终优先。
+
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use clap::Command;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// 新建配置文件时写入的模板
+pub const CONFIG_TEMPLATE: &str = r#"# scripts 配置文件
+#
+# 每个子命令对应一个表，键为参数的长名称（如 extensions、move-after-copy），
+# 值作为该参数的默认值。命令行中显式传入的参数优先于这里的配置。
+#
+# [hash-copy]
+# extensions = "jpg,png,gif"
+# target = "D:/backup"
+#
+# [video-transcode]
+# encoder = "svt"
+# crf = 30
+#
+# [batch-compress]
+# password = "keyring:backup"   # 引用 `scripts secrets set backup` 保存的密钥
+"#;
+
+/// 获取配置文件路径
+///
+/// 固定为 `~/.config/scripts/config.toml`，各平台一致，便于同步。
+///
+/// # 返回值
+///
+/// * `Ok(PathBuf)` - 配置文件路径
+/// * `Err(anyhow::Error)` - 无法确定用户主目录
+pub fn config_path() -> Result<PathBuf> {
+    let home_dir = dirs::home_dir().with_context(|| tr!("无法确定用户主目录"))?;
+    Ok(home_dir.join(".config").join("scripts").join("config.toml"))
+}
+
+/// 读取配置文件
+///
+/// 配置文件不存在时返回空表。
+///
+/// # 返回值
+///
+/// * `Ok(Table)` - 配置内容，顶层键为子命令名称
+/// * `Err(anyhow::Error)` - 读取或解析失败
+pub fn load_config() -> Result<Table> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(Table::new());
+    }
+
+    let content = std::fs::read_to_string(&path).with_context(|| {
+        ScriptError::new(
+            ErrorKind::Config,
+            tr!("读取配置文件失败: {}", path.display()),
+        )
+    })?;
+    content.parse::<Table>().with_context(|| {
+        ScriptError::new(
+            ErrorKind::Config,
+            tr!("解析配置文件失败: {}", path.display()),
+        )
+    })
+}
+
+/// 将 TOML 值转换为命令行参数字符串
+///
+/// 数组会以逗号连接，与扩展名列表等参数的格式一致。
+fn value_to_arg(value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(i) => Ok(i.to_string()),
+        Value::Float(f) => Ok(f.to_string()),
+        Value::Boolean(b) => Ok(b.to_string()),
+        Value::Array(items) => Ok(items
+            .iter()
+            .map(value_to_arg)
+            .collect::<Result<Vec<_>>>()?
+            .join(",")),
+        _ => anyhow::bail!(tr!("不支持的配置值类型: {}", value)),
+    }
+}
+
+/// 将配置文件中的默认值应用到命令定义
+///
+/// 对配置中的每个 `[子命令]` 表，按参数长名称（或字段名）查找参数并设置默认值。
+/// 设置了默认值的必填参数会变为可选，命令行参数仍然优先。
+///
+/// # 参数
+///
+/// * `command` - 顶层命令定义
+/// * `config` - 配置内容
+///
+/// # 返回值
+///
+/// * `Ok(Command)` - 应用默认值后的命令定义
+/// * `Err(anyhow::Error)` - 配置中包含未知的子命令、参数或不支持的值类型
+pub fn apply_config_defaults(mut command: Command, config: &Table) -> Result<Command> {
+    for (subcommand_name, section) in config {
+        let section = section
+            .as_table()
+            .with_context(|| tr!("配置项 [{}] 必须是表", subcommand_name))?;
+
+        let subcommand = command
+            .find_subcommand(subcommand_name)
+            .with_context(|| tr!("配置文件中未知的子命令: [{}]", subcommand_name))?;
+
+        // 先校验并收集参数 id,避免在可变借用中查找
+        let mut defaults = Vec::new();
+        for (key, value) in section {
+            let arg = subcommand
+                .get_arguments()
+                .find(|arg| {
+                    arg.get_long() == Some(key.as_str())
+                        || arg.get_id().as_str() == key.replace('-', "_")
+                })
+                .with_context(|| tr!("配置项 [{}] 中未知的参数: {}", subcommand_name, key))?;
+            let value = value_to_arg(value)
+                .with_context(|| tr!("配置项 [{}] 参数 {} 无效", subcommand_name, key))?;
+            defaults.push((arg.get_id().clone(), value));
+        }
+
+        command = command.mut_subcommand(subcommand_name, |mut subcommand| {
+            for (id, value) in defaults {
+                subcommand = subcommand.mut_arg(id, |arg| arg.default_value(value).required(false));
+            }
+            subcommand
+        });
+    }
+
+    Ok(command)
+}
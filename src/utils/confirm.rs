@@ -0,0 +1,82 @@
+//! # 确认模块
+//!
+//! 为删除、移动等破坏性操作提供统一的确认流程：先输出将受影响的项目及大小汇总，
+//! 再要求用户交互式确认；自动化场景可通过 `--yes` 跳过确认。
+
+use crate::utils::filesystem::path_size;
+use crate::utils::i18n::{tr, translate};
+use anyhow::Result;
+use bytesize::ByteSize;
+use inquire::Confirm;
+use std::path::PathBuf;
+
+/// 确认破坏性操作
+///
+/// 输出将受影响的项目列表（含大小）和总计，然后请求用户确认。
+/// `yes` 为 `true` 时跳过交互直接确认；无法交互（例如非终端环境）时视为取消。
+///
+/// # 参数
+///
+/// * `action` - 操作描述，例如 "移动到回收站"，输出时通过消息目录翻译
+/// * `items` - 将受影响的文件或目录
+/// * `yes` - 是否已通过 `--yes` 预先确认
+///
+/// # 返回值
+///
+/// * `Ok(true)` - 已确认，可以继续
+/// * `Ok(false)` - 用户取消或无法交互
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::confirm::confirm_destructive;
+///
+/// if !confirm_destructive("移动到回收站", &paths, args.yes)? {
+///     return Ok(());
+/// }
+/// ```
+pub fn confirm_destructive(action: &'static str, items: &[PathBuf], yes: bool) -> Result<bool> {
+    if items.is_empty() {
+        return Ok(true);
+    }
+    let action = translate(action);
+
+    println!(
+        "{}",
+        tr!("{} 待确认操作 {}", "=".repeat(20), "=".repeat(20))
+    );
+    println!("{}", tr!("以下 {} 项将被{}:", items.len(), action));
+
+    let mut total_size = 0;
+    for item in items {
+        let size = path_size(item);
+        total_size += size;
+        println!("  {} ({})", item.display(), ByteSize(size));
+    }
+
+    println!("{}", tr!("总大小: {}", ByteSize(total_size)));
+    println!();
+
+    if yes {
+        println!("{}", tr!("已通过 --yes 确认"));
+        return Ok(true);
+    }
+
+    match Confirm::new(&tr!("确认{}以上 {} 项？", action, items.len()))
+        .with_default(false)
+        .prompt()
+    {
+        Ok(true) => Ok(true),
+        Ok(false) => {
+            println!("{}", tr!("操作已取消"));
+            Ok(false)
+        }
+        Err(_) => {
+            println!(
+                "{}",
+                tr!("无法获取确认，操作已取消（非交互环境请使用 --yes）")
+            );
+            Ok(false)
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! # 错误分类模块
+//!
+//! 为需要区分失败类型的错误附加 [`ErrorKind`]，由主函数映射为不同的退出码，
+//! 并可按 `--error-format json` 输出机器可读的错误对象，便于自动化脚本按失败类型分支处理。
+//!
+//! 未分类的错误使用退出码 1；参数错误由 clap 处理，退出码为 2。
+
+use crate::utils::i18n::tr;
+use crate::utils::stats::{self, Counter};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// 错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 配置文件或任务列表等输入文件无效
+    Config,
+    /// 下载、拉取或推送远程仓库等网络操作失败
+    Network,
+    /// 批量操作中部分项目失败
+    PartialFailure,
+    /// 哈希或完整性校验失败
+    Verification,
+    /// 缺少必需的外部工具
+    Dependency,
+}
+
+impl ErrorKind {
+    /// JSON 错误对象中使用的名称，例如 "partial_failure"
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Config => "config",
+            ErrorKind::Network => "network",
+            ErrorKind::PartialFailure => "partial_failure",
+            ErrorKind::Verification => "verification",
+            ErrorKind::Dependency => "dependency",
+        }
+    }
+
+    /// 对应的进程退出码
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Config => 3,
+            ErrorKind::Network => 4,
+            ErrorKind::PartialFailure => 5,
+            ErrorKind::Verification => 6,
+            ErrorKind::Dependency => 7,
+        }
+    }
+}
+
+/// 带类型的错误
+///
+/// 既可以作为根错误返回，也可以通过 `with_context` 附加到已有错误上；
+/// 主函数通过 `downcast_ref` 在整个错误链中查找。
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::error::{ErrorKind, ScriptError};
+///
+/// // 作为根错误
+/// return Err(ScriptError::new(ErrorKind::Verification, "校验失败").into());
+///
+/// // 附加到已有错误
+/// toml::from_str(&content)
+///     .with_context(|| ScriptError::new(ErrorKind::Config, "解析任务文件失败"))?;
+/// ```
+#[derive(Debug)]
+pub struct ScriptError {
+    /// 错误类型
+    pub kind: ErrorKind,
+    /// 错误描述
+    pub message: String,
+}
+
+impl ScriptError {
+    /// 创建带类型的错误
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// 批量操作的失败记录
+///
+/// 配合 `--keep-going` 使用：单个项目失败时记录错误并继续处理，
+/// 结束时输出失败列表并返回 [`ErrorKind::PartialFailure`] 错误。
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::error::Failures;
+///
+/// let mut failures = Failures::default();
+/// for file in &files {
+///     if let Err(e) = process(file).await {
+///         if !args.keep_going {
+///             return Err(e);
+///         }
+///         failures.record(file, e);
+///     }
+/// }
+/// failures.finish()?;
+/// ```
+#[derive(Debug, Default)]
+pub struct Failures {
+    /// 失败的项目及错误描述
+    items: Vec<(PathBuf, String)>,
+}
+
+impl Failures {
+    /// 记录一个失败的项目并立即输出错误，同时计入运行统计
+    pub fn record(&mut self, item: &Path, error: anyhow::Error) {
+        stats::add(Counter::Failed, 1);
+        let message = format!("{:#}", error);
+        println!("{}", tr!("✗ 失败: {} - {}", item.display(), message));
+        self.items.push((item.to_path_buf(), message));
+    }
+
+    /// 输出失败汇总
+    ///
+    /// # 返回值
+    ///
+    /// * `Ok(())` - 没有失败的项目
+    /// * `Err(anyhow::Error)` - 存在失败的项目，错误类型为 [`ErrorKind::PartialFailure`]
+    pub fn finish(self) -> anyhow::Result<()> {
+        if self.items.is_empty() {
+            return Ok(());
+        }
+
+        println!();
+        println!("{}", tr!("{} 失败项目 {}", "=".repeat(20), "=".repeat(20)));
+        for (item, message) in &self.items {
+            println!("✗ {}", item.display());
+            println!("  {}", message);
+        }
+
+        Err(ScriptError::new(
+            ErrorKind::PartialFailure,
+            tr!("{} 个项目处理失败", self.items.len()),
+        )
+        .into())
+    }
+}
+
+/// 错误输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ErrorFormat {
+    /// 人类可读的文本
+    #[default]
+    Text,
+    /// 单行 JSON 对象
+    Json,
+}
+
+/// JSON 格式的错误对象
+#[derive(Serialize)]
+struct ErrorReport {
+    /// 错误类型，未分类时为 `error`
+    kind: &'static str,
+    /// 退出码
+    code: i32,
+    /// 最外层错误描述
+    message: String,
+    /// 完整的错误链
+    causes: Vec<String>,
+}
+
+/// 获取错误对应的退出码
+///
+/// # 参数
+///
+/// * `error` - 命令返回的错误
+///
+/// # 返回值
+///
+/// * `i32` - 错误链中带类型错误对应的退出码，未分类时为 1
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    error
+        .downcast_ref::<ScriptError>()
+        .map_or(1, |e| e.kind.exit_code())
+}
+
+/// 输出错误信息到标准错误
+///
+/// # 参数
+///
+/// * `error` - 命令返回的错误
+/// * `format` - 输出格式
+pub fn report_error(error: &anyhow::Error, format: ErrorFormat) {
+    match format {
+        ErrorFormat::Text => eprintln!("Error: {:?}", error),
+        ErrorFormat::Json => {
+            let report = ErrorReport {
+                kind: error
+                    .downcast_ref::<ScriptError>()
+                    .map_or("error", |e| e.kind.name()),
+                code: exit_code(error),
+                message: error.to_string(),
+                causes: error.chain().skip(1).map(|e| e.to_string()).collect(),
+            };
+            let json = serde_json::json!({ "error": report });
+            eprintln!("{}", json);
+        }
+    }
+}
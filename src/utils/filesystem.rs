@@ -2,9 +2,21 @@
 //!
 //! 提供文件和目录的创建、删除等文件系统操作功能。
 
-use std::path::Path;
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// Windows 经典路径长度上限（MAX_PATH，不含结尾的空字符）
+const MAX_PATH: usize = 259;
+
+/// Windows 保留的设备名称
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
 /// 获取文件扩展名（小写）
 ///
 /// 提取路径中的文件扩展名并转换为小写形式。
@@ -41,10 +53,142 @@ pub fn get_file_extension<P: AsRef<Path>>(path: P) -> String {
         .unwrap_or_default()
 }
 
+/// 判断文件名是否为 Windows 保留的设备名称
+///
+/// `CON`、`PRN`、`AUX`、`NUL`、`COM1`-`COM9`、`LPT1`-`LPT9` 不区分大小写，
+/// 带扩展名（例如 `aux.js`）或结尾带空格和点时同样视为保留名称。
+///
+/// # 参数
+///
+/// * `name` - 文件名（不含目录）
+///
+/// # 返回值
+///
+/// * `bool` - 是否为保留名称
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::filesystem::is_reserved_name;
+///
+/// assert!(is_reserved_name("aux.js"));
+/// assert!(is_reserved_name("Con"));
+/// assert!(!is_reserved_name("auxiliary.js"));
+/// ```
+pub fn is_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name);
+    let stem = stem.trim_end_matches([' ', '.']);
+    RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
+
+/// 转换为 Windows 扩展长度路径
+///
+/// 在 Windows 上，路径超过 MAX_PATH 或包含保留名称（例如 `node_modules/.../aux.js`）时，
+/// 转换为带 `\\?\` 前缀的绝对路径，使文件系统操作和 7-Zip 等外部程序能够访问这些文件；
+/// 其他路径和其他平台上原样返回。
+///
+/// # 参数
+///
+/// * `path` - 文件或目录路径
+///
+/// # 返回值
+///
+/// * `PathBuf` - 可直接用于文件系统操作的路径
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::filesystem::long_path;
+/// use std::path::Path;
+///
+/// // Windows 上: \\?\D:\project\node_modules\pkg\aux.js
+/// let path = long_path(Path::new("D:/project/node_modules/pkg/aux.js"));
+/// ```
+pub fn long_path(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+
+    let reserved = path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .any(is_reserved_name);
+    if !reserved && path.as_os_str().len() <= MAX_PATH {
+        return path.to_path_buf();
+    }
+
+    // 扩展长度路径不做任何解析，必须是使用 `\` 分隔的绝对路径
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    let absolute = absolute.to_string_lossy().replace('/', "\\");
+    if absolute.starts_with(r"\\?\") {
+        PathBuf::from(absolute)
+    } else if let Some(unc) = absolute.strip_prefix(r"\\") {
+        PathBuf::from(format!(r"\\?\UNC\{}", unc))
+    } else {
+        PathBuf::from(format!(r"\\?\{}", absolute))
+    }
+}
+
+/// 目录大小统计结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirSize {
+    /// 可访问文件的总大小（字节数）
+    pub bytes: u64,
+    /// 因权限不足等原因无法读取而跳过的条目数量，不为 0 时 `bytes` 只是部分统计
+    pub skipped: usize,
+}
+
+/// 统计目录大小，并记录无法访问的条目数量
+///
+/// 使用 WalkDir 遍历目录（不跟随符号链接），累加所有文件的大小。
+/// 权限不足的子目录和无法读取元数据的文件会被跳过并计数，不会中断统计。
+/// 超长路径通过 [`long_path`] 访问。
+///
+/// # 参数
+///
+/// * `path` - 要统计的目录路径
+///
+/// # 返回值
+///
+/// * `DirSize` - 总大小和跳过的条目数量
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::filesystem::measure_dir_size;
+/// use std::path::Path;
+///
+/// let size = measure_dir_size(Path::new("C:/ProgramData"));
+/// if size.skipped > 0 {
+///     println!("{} 项无法访问，大小为部分统计", size.skipped);
+/// }
+/// ```
+pub fn measure_dir_size<P: AsRef<Path>>(path: P) -> DirSize {
+    let mut size = DirSize::default();
+    for entry in WalkDir::new(long_path(path.as_ref())) {
+        let Ok(entry) = entry else {
+            size.skipped += 1;
+            continue;
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match entry.metadata() {
+            Ok(metadata) => size.bytes += metadata.len(),
+            Err(_) => size.skipped += 1,
+        }
+    }
+    size
+}
+
 /// 计算目录的实际大小（字节数）
 ///
-/// 使用 WalkDir 遍历目录，累加所有文件的大小。
-/// 权限不足时自动跳过，不会抛出异常。
+/// 使用 [`measure_dir_size`] 遍历目录，权限不足时自动跳过，不会抛出异常。
+/// 需要知道是否有条目被跳过时直接使用 [`measure_dir_size`]。
 ///
 /// # 参数
 ///
@@ -64,10 +208,113 @@ pub fn get_file_extension<P: AsRef<Path>>(path: P) -> String {
 /// println!("目录大小: {} 字节", size);
 /// ```
 pub fn calculate_dir_size<P: AsRef<Path>>(path: P) -> u64 {
-    WalkDir::new(path.as_ref())
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .map(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
-        .sum()
+    measure_dir_size(path).bytes
+}
+
+/// 获取文件或目录的大小（字节数）
+///
+/// 目录使用 [`calculate_dir_size`] 递归计算，文件直接读取元数据。
+/// 无法访问时返回 0。
+///
+/// # 参数
+///
+/// * `path` - 文件或目录路径
+///
+/// # 返回值
+///
+/// * `u64` - 大小（字节数）
+pub fn path_size<P: AsRef<Path>>(path: P) -> u64 {
+    let path = path.as_ref();
+    if path.is_dir() {
+        calculate_dir_size(path)
+    } else {
+        path.metadata().map(|m| m.len()).unwrap_or(0)
+    }
+}
+
+/// 根据 glob 模式列表构建匹配器
+///
+/// `*` 可以匹配路径分隔符，因此 `*.log` 同时匹配子目录中的日志文件。
+///
+/// # 参数
+///
+/// * `patterns` - glob 模式列表，例如 `["*.log", "cache/**"]`
+///
+/// # 返回值
+///
+/// * `Ok(Some(GlobSet))` - 匹配器
+/// * `Ok(None)` - 模式列表为空
+/// * `Err(anyhow::Error)` - 模式无效
+pub fn build_globset(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| tr!("无效的 glob 模式: {}", pattern))?);
+    }
+    Ok(Some(
+        builder
+            .build()
+            .with_context(|| tr!("构建 glob 匹配器失败"))?,
+    ))
+}
+
+/// 移动文件
+///
+/// 优先使用重命名；跨文件系统重命名失败时回退为复制后删除源文件。
+///
+/// # 参数
+///
+/// * `from` - 源文件路径
+/// * `to` - 目标文件路径
+///
+/// # 返回值
+///
+/// * `Ok(())` - 移动成功
+/// * `Err(anyhow::Error)` - 复制或删除失败
+pub async fn move_file<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if tokio::fs::rename(from, to).await.is_ok() {
+        return Ok(());
+    }
+
+    tokio::fs::copy(from, to)
+        .await
+        .with_context(|| tr!("复制文件失败: {} -> {}", from.display(), to.display()))?;
+    tokio::fs::remove_file(from)
+        .await
+        .with_context(|| tr!("删除源文件失败: {}", from.display()))
+}
+
+/// 创建符号链接
+///
+/// Windows 区分文件链接和目录链接，由 `dir` 指定；Unix 上忽略该参数。
+/// Windows 上创建符号链接需要管理员权限或开启开发者模式。
+///
+/// # 参数
+///
+/// * `original` - 链接指向的路径，原样写入链接（相对路径相对于链接所在目录）
+/// * `link` - 要创建的链接路径
+/// * `dir` - 链接是否指向目录
+///
+/// # 返回值
+///
+/// * `Ok(())` - 创建成功
+/// * `Err(anyhow::Error)` - 创建失败，例如链接路径已存在或权限不足
+pub fn create_symlink(original: &Path, link: &Path, dir: bool) -> Result<()> {
+    #[cfg(windows)]
+    let result = if dir {
+        std::os::windows::fs::symlink_dir(original, link)
+    } else {
+        std::os::windows::fs::symlink_file(original, link)
+    };
+    #[cfg(not(windows))]
+    let result = {
+        let _ = dir;
+        std::os::unix::fs::symlink(original, link)
+    };
+
+    result.with_context(|| tr!("创建符号链接失败: {}", link.display()))
 }
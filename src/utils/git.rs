@@ -0,0 +1,396 @@
+//! # Git 相关工具
+//!
+//! 封装 git 命令调用和仓库镜像逻辑，供 repo-mirror 等命令复用。
+
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::i18n::tr;
+use crate::utils::tempfiles::TempPath;
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use walkdir::WalkDir;
+
+/// 仓库镜像选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MirrorOptions {
+    /// 推送 `refs/*` 下的所有引用，而不仅是分支和标签
+    pub all_refs: bool,
+    /// 强制推送，覆盖目标仓库中分叉的引用；与 `all_refs` 同时使用时还会删除目标中多余的引用
+    pub force: bool,
+}
+
+/// HTTPS 仓库的访问凭据
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    /// 用户名，GitLab 访问令牌通常使用 `oauth2`
+    pub username: String,
+    /// 密码或访问令牌（已解析的明文）
+    pub token: String,
+}
+
+impl Credentials {
+    /// 生成注入 `http.extraHeader` 的环境变量
+    ///
+    /// 通过 `GIT_CONFIG_COUNT` 系列环境变量传递（需要 git 2.31+），
+    /// 凭据不会出现在命令行参数、仓库地址或错误信息中。
+    fn git_env(&self) -> Vec<(String, String)> {
+        let basic = STANDARD.encode(format!("{}:{}", self.username, self.token));
+        vec![
+            ("GIT_CONFIG_COUNT".to_string(), "1".to_string()),
+            (
+                "GIT_CONFIG_KEY_0".to_string(),
+                "http.extraHeader".to_string(),
+            ),
+            (
+                "GIT_CONFIG_VALUE_0".to_string(),
+                format!("Authorization: Basic {}", basic),
+            ),
+        ]
+    }
+}
+
+/// 镜像时源仓库和目标仓库各自使用的凭据
+///
+/// 为 `None` 时使用 git 自身的凭据配置。
+#[derive(Debug, Clone, Default)]
+pub struct MirrorAuth {
+    /// 拉取源仓库使用的凭据
+    pub from: Option<Credentials>,
+    /// 推送目标仓库使用的凭据
+    pub to: Option<Credentials>,
+}
+
+/// 获取可选凭据对应的环境变量
+fn auth_env(credentials: Option<&Credentials>) -> Vec<(String, String)> {
+    credentials.map(Credentials::git_env).unwrap_or_default()
+}
+
+/// git 命令返回非零退出码
+#[derive(Debug)]
+struct GitError {
+    /// git 参数
+    command: String,
+    /// 退出码
+    code: i32,
+    /// 标准错误内容
+    stderr: String,
+}
+
+impl GitError {
+    /// 是否为网络或认证等访问远程仓库的失败
+    fn is_transport(&self) -> bool {
+        const PATTERNS: &[&str] = &[
+            "could not resolve host",
+            "unable to access",
+            "failed to connect",
+            "connection timed out",
+            "connection refused",
+            "could not read from remote repository",
+            "authentication failed",
+            "could not read username",
+            "permission denied (publickey",
+            "the remote end hung up",
+            "rpc failed",
+        ];
+        let stderr = self.stderr.to_lowercase();
+        PATTERNS.iter().any(|pattern| stderr.contains(pattern))
+    }
+
+    /// 是否为远程仓库拒绝推送（例如禁止删除或写入隐藏引用）
+    fn is_remote_rejected(&self) -> bool {
+        self.stderr.contains("[remote rejected]")
+    }
+}
+
+impl fmt::Display for GitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            tr!("git {} 失败，退出码: {}", self.command, self.code)
+        )
+    }
+}
+
+impl std::error::Error for GitError {}
+
+/// 判断错误是否为远程仓库拒绝推送
+fn is_remote_rejected(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<GitError>()
+        .is_some_and(GitError::is_remote_rejected)
+}
+
+/// 执行 git 命令并返回标准输出
+///
+/// 标准错误直接输出到终端，便于查看 git 的进度和错误信息。
+///
+/// # 参数
+///
+/// * `args` - git 参数
+/// * `cwd` - 工作目录，`None` 表示当前目录
+///
+/// # 返回值
+///
+/// * `Ok(String)` - 标准输出内容
+/// * `Err(anyhow::Error)` - 启动 git 失败或返回非零退出码
+pub async fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<String> {
+    run_git_with_env(args, cwd, &[]).await
+}
+
+/// 使用额外的环境变量执行 git 命令并返回标准输出
+///
+/// # 参数
+///
+/// * `args` - git 参数
+/// * `cwd` - 工作目录，`None` 表示当前目录
+/// * `envs` - 额外的环境变量
+///
+/// # 返回值
+///
+/// * `Ok(String)` - 标准输出内容
+/// * `Err(anyhow::Error)` - 启动 git 失败（[`ErrorKind::Dependency`]）、访问远程仓库失败
+///   （[`ErrorKind::Network`]）或返回其他非零退出码
+pub async fn run_git_with_env(
+    args: &[&str],
+    cwd: Option<&Path>,
+    envs: &[(String, String)],
+) -> Result<String> {
+    // 固定英文输出，便于根据标准错误区分失败类型
+    let mut cmd = Command::new("git");
+    cmd.args(args)
+        .envs(envs.iter().map(|(k, v)| (k, v)))
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(cwd) = cwd {
+        cmd.current_dir(cwd);
+    }
+
+    let mut child = cmd.spawn().with_context(|| {
+        ScriptError::new(
+            ErrorKind::Dependency,
+            tr!("执行 git 命令失败，请确认已安装 git"),
+        )
+    })?;
+
+    // 标准错误边输出到终端边保存
+    let mut stderr = child.stderr.take().context("stderr")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut captured = Vec::new();
+        let mut buffer = [0; 4096];
+        while let Ok(n) = stderr.read(&mut buffer).await {
+            if n == 0 {
+                break;
+            }
+            let _ = std::io::stderr().write_all(&buffer[..n]);
+            captured.extend_from_slice(&buffer[..n]);
+        }
+        String::from_utf8_lossy(&captured).to_string()
+    });
+
+    let output = child
+        .wait_with_output()
+        .await
+        .with_context(|| tr!("执行 git 命令失败，请确认已安装 git"))?;
+    let stderr = stderr_task.await.unwrap_or_default();
+
+    if !output.status.success() {
+        let error = GitError {
+            command: args.join(" "),
+            code: output.status.code().unwrap_or(-1),
+            stderr,
+        };
+        if error.is_transport() {
+            return Err(anyhow::Error::new(error).context(ScriptError::new(
+                ErrorKind::Network,
+                tr!("访问远程仓库失败"),
+            )));
+        }
+        return Err(error.into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 查找目录下的所有 git 仓库
+///
+/// 包含 `.git` 目录或文件（工作树、子模块）的目录视为仓库，找到仓库后不再进入其子目录。
+/// 不跟随符号链接，无法访问的目录自动跳过。
+///
+/// # 参数
+///
+/// * `root` - 要扫描的根目录
+///
+/// # 返回值
+///
+/// * `Vec<PathBuf>` - 按路径排序的仓库目录列表
+pub fn find_repositories(root: &Path) -> Vec<PathBuf> {
+    let mut repos = Vec::new();
+    let mut walker = WalkDir::new(root).into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if entry.path().join(".git").exists() {
+            repos.push(entry.into_path());
+            walker.skip_current_dir();
+        }
+    }
+
+    repos.sort();
+    repos
+}
+
+/// 列出本地仓库中的所有分支名称
+async fn list_branches(repo: &Path) -> Result<Vec<String>> {
+    let output = run_git(
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads/"],
+        Some(repo),
+    )
+    .await?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// 将源仓库镜像到目标仓库
+///
+/// - `all_refs` 为 `true` 时使用 `git clone --mirror` 拉取所有引用并推送 `refs/*`；
+///   `force` 为 `true` 时使用 `git push --mirror`，强制更新并删除目标中多余的引用，
+///   否则只推送可快进的更新；目标仓库拒绝推送（例如禁止删除或写入隐藏引用）时，
+///   回退为逐个推送分支和标签
+/// - 否则在临时裸仓库中拉取分支和标签，逐个推送分支，最后推送所有标签
+///
+/// 临时仓库在完成后删除。
+///
+/// # 参数
+///
+/// * `from` - 源仓库地址
+/// * `to` - 目标仓库地址
+/// * `options` - 镜像选项
+/// * `auth` - 源仓库和目标仓库的凭据
+///
+/// # 返回值
+///
+/// * `Ok(usize)` - 镜像的分支数量
+/// * `Err(anyhow::Error)` - 拉取或推送失败
+pub async fn mirror_repository(
+    from: &str,
+    to: &str,
+    options: MirrorOptions,
+    auth: &MirrorAuth,
+) -> Result<usize> {
+    let temp = TempPath::dir("mirror")?;
+    let repo = temp.path();
+    let from_env = auth_env(auth.from.as_ref());
+    let to_env = auth_env(auth.to.as_ref());
+    if options.all_refs {
+        mirror_all_refs(repo, (from, &from_env), (to, &to_env), options.force).await
+    } else {
+        mirror_branches(repo, (from, &from_env), (to, &to_env), options.force).await
+    }
+}
+
+/// 仓库地址及访问该仓库时使用的环境变量
+type Remote<'a> = (&'a str, &'a [(String, String)]);
+
+/// 使用 `git clone --mirror` 镜像所有引用，目标仓库拒绝推送时回退为逐个推送分支
+async fn mirror_all_refs(
+    repo: &Path,
+    from: Remote<'_>,
+    to: Remote<'_>,
+    force: bool,
+) -> Result<usize> {
+    let repo_str = repo.to_string_lossy().to_string();
+    println!("{}", tr!("镜像克隆: {}", from.0));
+    run_git_with_env(
+        &["clone", "--mirror", "--quiet", from.0, &repo_str],
+        None,
+        from.1,
+    )
+    .await?;
+
+    let branches = list_branches(repo).await?;
+
+    println!("{}", tr!("镜像推送: {}", to.0));
+    // 不强制时推送 refs/* 但不带 +，分叉的引用会被拒绝，目标中多余的引用不会被删除
+    let args: &[&str] = if force {
+        &["push", "--mirror", to.0]
+    } else {
+        &["push", to.0, "refs/*:refs/*"]
+    };
+    match run_git_with_env(args, Some(repo), to.1).await {
+        Ok(_) => Ok(branches.len()),
+        Err(e) if is_remote_rejected(&e) => {
+            println!(
+                "{}",
+                tr!("镜像推送失败（{}），回退为逐个推送分支", format!("{:#}", e))
+            );
+            push_branches(repo, to, &branches, force).await?;
+            Ok(branches.len())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 在临时裸仓库中拉取分支和标签，然后逐个推送
+async fn mirror_branches(
+    repo: &Path,
+    from: Remote<'_>,
+    to: Remote<'_>,
+    force: bool,
+) -> Result<usize> {
+    let repo_str = repo.to_string_lossy().to_string();
+    run_git(&["init", "--bare", "--quiet", &repo_str], None).await?;
+
+    println!("{}", tr!("拉取: {}", from.0));
+    run_git_with_env(
+        &["fetch", "--tags", from.0, "+refs/heads/*:refs/heads/*"],
+        Some(repo),
+        from.1,
+    )
+    .await?;
+
+    let branches = list_branches(repo).await?;
+    push_branches(repo, to, &branches, force).await?;
+    Ok(branches.len())
+}
+
+/// 逐个推送分支，最后推送所有标签
+async fn push_branches(
+    repo: &Path,
+    to: Remote<'_>,
+    branches: &[String],
+    force: bool,
+) -> Result<()> {
+    let (url, envs) = to;
+    let force: &[&str] = if force { &["--force"] } else { &[] };
+
+    for (index, branch) in branches.iter().enumerate() {
+        println!(
+            "{}",
+            tr!("推送分支 ({}/{}): {}", index + 1, branches.len(), branch)
+        );
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+        let args = [&["push"], force, &[url, refspec.as_str()]].concat();
+        run_git_with_env(&args, Some(repo), envs).await?;
+    }
+
+    println!("{}", tr!("推送标签: {}", url));
+    let args = [&["push"], force, &[url, "--tags"]].concat();
+    run_git_with_env(&args, Some(repo), envs).await?;
+    Ok(())
+}
@@ -1,11 +1,54 @@
 //! # 哈希计算模块
 //!
-//! 提供文件哈希计算功能，使用 Blake3 算法和 Base58 编码。
+//! 提供文件哈希计算功能，使用 Blake3 算法，默认使用 Base58 编码。
+//! 所有命令共用本模块计算哈希，保证同一文件在各处得到相同的结果。
 
+use crate::utils::i18n::tr;
 use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::collections::BTreeMap;
 use std::path::Path;
 use tokio::io::AsyncReadExt;
 
+/// 哈希值编码方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum HashEncoding {
+    /// Base58 编码，用于 hash-copy 文件名和各类清单
+    #[default]
+    Base58,
+    /// 小写十六进制编码，与 `b3sum` 输出一致
+    Hex,
+}
+
+impl HashEncoding {
+    /// 按当前编码方式编码 Blake3 哈希值
+    ///
+    /// # 参数
+    ///
+    /// * `hash` - Blake3 哈希值
+    pub fn encode(self, hash: &blake3::Hash) -> String {
+        match self {
+            HashEncoding::Base58 => bs58::encode(hash.as_bytes()).into_string(),
+            HashEncoding::Hex => hash.to_hex().to_string(),
+        }
+    }
+
+    /// 根据已编码的哈希值推断编码方式
+    ///
+    /// 64 位十六进制字符串视为 [`HashEncoding::Hex`]，其他视为 [`HashEncoding::Base58`]。
+    ///
+    /// # 参数
+    ///
+    /// * `value` - 已编码的哈希值
+    pub fn detect(value: &str) -> Self {
+        if value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit()) {
+            HashEncoding::Hex
+        } else {
+            HashEncoding::Base58
+        }
+    }
+}
+
 /// 计算文件的 Blake3 哈希值并使用 Base58 编码
 ///
 /// 对文件内容进行 Blake3 哈希计算，然后将哈希值编码为 Base58 格式。
@@ -41,12 +84,30 @@ use tokio::io::AsyncReadExt;
 /// }
 /// ```
 pub async fn calculate_file_hash<P: AsRef<Path>>(file_path: P) -> Result<String> {
+    calculate_file_hash_as(file_path, HashEncoding::Base58).await
+}
+
+/// 计算文件的 Blake3 哈希值并使用指定方式编码
+///
+/// # 参数
+///
+/// * `file_path` - 要计算哈希的文件路径
+/// * `encoding` - 编码方式
+///
+/// # 返回值
+///
+/// * `Ok(String)` - 编码后的哈希值
+/// * `Err(anyhow::Error)` - 计算哈希失败，包含详细错误信息
+pub async fn calculate_file_hash_as<P: AsRef<Path>>(
+    file_path: P,
+    encoding: HashEncoding,
+) -> Result<String> {
     let file_path = file_path.as_ref();
 
     // 异步打开文件进行读取
     let mut file = tokio::fs::File::open(file_path)
         .await
-        .with_context(|| format!("打开文件失败: {}", file_path.display()))?;
+        .with_context(|| tr!("打开文件失败: {}", file_path.display()))?;
 
     // 创建 Blake3 哈希器
     let mut hasher = blake3::Hasher::new();
@@ -57,15 +118,66 @@ pub async fn calculate_file_hash<P: AsRef<Path>>(file_path: P) -> Result<String>
         let n = file
             .read(&mut buffer)
             .await
-            .with_context(|| format!("读取文件失败: {}", file_path.display()))?;
+            .with_context(|| tr!("读取文件失败: {}", file_path.display()))?;
         if n == 0 {
             break; // 文件读取完毕
         }
         hasher.update(&buffer[..n]);
     }
 
-    // 完成哈希计算并进行 Base58 编码
-    let hash = hasher.finalize();
-    let hash_bytes = hash.as_bytes();
-    Ok(bs58::encode(hash_bytes).into_string())
+    // 完成哈希计算并按指定方式编码
+    Ok(encoding.encode(&hasher.finalize()))
+}
+
+/// 将 Blake3 哈希值编码为 Base58 字符串
+///
+/// 与 [`calculate_file_hash`] 的输出格式一致，用于边读写边计算哈希的场景。
+///
+/// # 参数
+///
+/// * `hash` - Blake3 哈希值
+///
+/// # 返回值
+///
+/// * `String` - Base58 编码的哈希值
+pub fn encode_hash(hash: &blake3::Hash) -> String {
+    HashEncoding::Base58.encode(hash)
+}
+
+/// 读取校验和清单文件
+///
+/// 清单每行格式为 `<哈希>  <相对路径>`（哈希与路径之间为两个空格），
+/// 哈希为 [`calculate_file_hash`] 生成的 Base58 编码 Blake3 值。
+/// 空行和以 `#` 开头的行会被忽略。
+///
+/// # 参数
+///
+/// * `manifest_path` - 清单文件路径
+///
+/// # 返回值
+///
+/// * `Ok(BTreeMap<String, String>)` - 相对路径（使用 `/` 分隔）到哈希的映射
+/// * `Err(anyhow::Error)` - 读取失败或格式错误
+pub fn read_manifest<P: AsRef<Path>>(manifest_path: P) -> Result<BTreeMap<String, String>> {
+    let manifest_path = manifest_path.as_ref();
+    let content = std::fs::read_to_string(manifest_path)
+        .with_context(|| tr!("读取清单文件失败: {}", manifest_path.display()))?;
+
+    let mut entries = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (hash, path) = line.split_once("  ").with_context(|| {
+            tr!(
+                "清单格式错误: {} 第 {} 行",
+                manifest_path.display(),
+                index + 1
+            )
+        })?;
+        entries.insert(path.replace('\\', "/"), hash.to_string());
+    }
+
+    Ok(entries)
 }
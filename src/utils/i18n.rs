@@ -0,0 +1,955 @@
+//! # 多语言输出模块
+//!
+//! 提供中文和英文两种输出语言。代码中的消息保持中文原文，通过 [`tr!`] 在消息目录中
+//! 查找对应的英文译文，找不到译文时按原文输出。
+//!
+//! 语言由全局选项 `--lang` 指定，未指定时依次根据环境变量 `LC_ALL`、`LC_MESSAGES`、`LANG`
+//! 判断（以 `en` 开头为英文），无法判断时使用中文。命令行帮助信息仍为中文。
+//!
+//! 消息中的 `{}` 占位符按顺序替换为参数，译文中占位符的数量和顺序必须与原文一致；
+//! 不支持 `{:#}` 等格式说明，需要时先用 `format!` 格式化参数。
+
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::sync::{LazyLock, OnceLock};
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// 中文
+    Zh,
+    /// 英文
+    En,
+}
+
+/// 通过 `--lang` 指定的语言
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// 消息目录，中文原文到英文译文的映射
+static CATALOG: LazyLock<HashMap<&'static str, &'static str>> =
+    LazyLock::new(|| MESSAGES.iter().copied().collect());
+
+/// 设置输出语言，只在解析命令行参数后调用一次
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+/// 根据环境变量判断语言
+///
+/// 依次检查 `LC_ALL`、`LC_MESSAGES`、`LANG`，使用第一个非空的值，以 `en` 开头时为英文。
+pub fn detect_lang() -> Lang {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map_or(Lang::Zh, |value| {
+            if value.starts_with("en") {
+                Lang::En
+            } else {
+                Lang::Zh
+            }
+        })
+}
+
+/// 获取当前输出语言
+///
+/// 解析命令行参数之前（例如读取配置文件时）根据环境变量判断。
+pub fn lang() -> Lang {
+    LANG.get().copied().unwrap_or_else(detect_lang)
+}
+
+/// 获取消息在当前语言下的文本
+///
+/// # 参数
+///
+/// * `message` - 中文原文
+///
+/// # 返回值
+///
+/// * `&str` - 中文时为原文；英文时为译文，消息目录中没有时为原文
+pub fn translate(message: &'static str) -> &'static str {
+    match lang() {
+        Lang::Zh => message,
+        Lang::En => CATALOG.get(message).copied().unwrap_or(message),
+    }
+}
+
+/// 按顺序将消息中的 `{}` 替换为参数
+///
+/// # 参数
+///
+/// * `template` - 消息文本
+/// * `args` - 参数，多余的参数被忽略，不足时保留 `{}`
+pub fn format_message(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(index) = rest.find("{}") {
+        output.push_str(&rest[..index]);
+        match args.next() {
+            Some(arg) => output.push_str(&arg.to_string()),
+            None => output.push_str("{}"),
+        }
+        rest = &rest[index + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 按当前语言输出消息
+///
+/// 第一个参数为中文原文（字符串字面量），其后为替换 `{}` 的参数，返回 `String`。
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::i18n::tr;
+///
+/// println!("{}", tr!("操作已取消"));
+/// println!("{}", tr!("源目录: {}", source.display()));
+/// anyhow::bail!(tr!("路径必须是目录: {}", dir.display()));
+/// ```
+macro_rules! tr {
+    ($message:literal) => {
+        $crate::utils::i18n::translate($message).to_string()
+    };
+    ($message:literal, $($arg:expr),+ $(,)?) => {
+        $crate::utils::i18n::format_message(
+            $crate::utils::i18n::translate($message),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+pub(crate) use tr;
+
+/// 消息目录：(中文原文, 英文译文)
+const MESSAGES: &[(&str, &str)] = &[
+    // commands/audio_extract.rs
+    ("源文件不存在: {}", "Source file does not exist: {}"),
+    ("启动 ffmpeg 失败: {}", "Failed to start ffmpeg: {}"),
+    ("等待 ffmpeg 完成失败: {}", "Failed to wait for ffmpeg: {}"),
+    (
+        "ffmpeg 提取音频失败: {}",
+        "ffmpeg failed to extract audio: {}",
+    ),
+    ("提取完成: {}", "Extracted: {}"),
+    ("无法访问源目录: {}", "Cannot access source directory: {}"),
+    (
+        "源路径必须是目录: {}",
+        "Source path must be a directory: {}",
+    ),
+    ("{} 音频提取工具 {}", "{} Audio Extract {}"),
+    ("源目录: {}", "Source directory: {}"),
+    ("目标格式: {} ({})", "Output format: {} ({})"),
+    ("音频码率: {}", "Audio bitrate: {}"),
+    ("没有找到视频文件", "No video files found"),
+    ("找到 {} 个视频文件\n", "Found {} video file(s)\n"),
+    ("进度: {}/{}", "Progress: {}/{}"),
+    ("操作成功完成！", "Done!"),
+    // commands/av1_bench.rs
+    ("测试编码失败: {}", "Test encode failed: {}"),
+    ("输入视频不存在: {}", "Input video does not exist: {}"),
+    ("{} AV1 编码器基准测试 {}", "{} AV1 Encoder Benchmark {}"),
+    ("输入: {} (前 {} 秒)", "Input: {} (first {} s)"),
+    ("输入: testsrc2 {} {} 秒", "Input: testsrc2 {} {} s"),
+    ("编码质量: CRF=25", "Quality: CRF=25"),
+    ("{}: 不可用", "{}: unavailable"),
+    ("{}: 正在测试 {} ...", "{}: testing {} ..."),
+    ("{} 测试结果 {}", "{} Results {}"),
+    (
+        "未找到可用的 AV1 编码器，请检查硬件驱动或安装支持 AV1 的 ffmpeg",
+        "No usable AV1 encoder found; check the hardware drivers or install an ffmpeg build with AV1 support",
+    ),
+    (
+        "{} {} 耗时: {}s  速度: {}x  大小: {}",
+        "{} {} time: {}s  speed: {}x  size: {}",
+    ),
+    (
+        "最快的编码器: {} (video-transcode --encoder {})",
+        "Fastest encoder: {} (video-transcode --encoder {})",
+    ),
+    // commands/backup.rs
+    ("无法读取目录: {}", "Cannot read directory: {}"),
+    (
+        "无法确定源目录名称",
+        "Cannot determine the source directory name",
+    ),
+    ("创建目录失败: {}", "Failed to create directory: {}"),
+    ("{} 目录备份工具 {}", "{} Directory Backup {}"),
+    ("保存目录: {}", "Output directory: {}"),
+    (
+        "加密模式: 已启用(加密文件内容和文件名)",
+        "Encryption: enabled (file contents and names)",
+    ),
+    ("快照已存在: {}", "Snapshot already exists: {}"),
+    ("快照已保存: {} ({})", "Snapshot saved: {} ({})"),
+    (
+        "✓ 已将过期快照移动到回收站: {}",
+        "✓ Moved expired snapshot to trash: {}",
+    ),
+    (
+        "✗ 移动到回收站失败: {} - {}",
+        "✗ Failed to move to trash: {} - {}",
+    ),
+    ("移动到回收站", "move to trash"),
+    // commands/batch_compress.rs
+    ("无效的项目名称", "Invalid item name"),
+    ("处理: {}", "Processing: {}"),
+    ("压缩文件已存在: {}", "Archive already exists: {}"),
+    (
+        "相同内容的压缩文件已存在: {}",
+        "An archive with identical content already exists: {}",
+    ),
+    (
+        "压缩完成(已加密): {} -> {}",
+        "Compressed (encrypted): {} -> {}",
+    ),
+    ("压缩完成: {} -> {}", "Compressed: {} -> {}"),
+    (
+        "无法将原始项目移动到回收站: {}",
+        "Cannot move original item to trash: {}",
+    ),
+    (
+        "已将原始项目移动到回收站: {}",
+        "Moved original item to trash: {}",
+    ),
+    ("保留原始项目: {}", "Keeping original item: {}"),
+    ("{} 批量压缩工具 {}", "{} Batch Compress {}"),
+    ("加密模式: 未启用", "Encryption: disabled"),
+    (
+        "命名模板不能包含路径分隔符: {}",
+        "Name template must not contain path separators: {}",
+    ),
+    (
+        "{hash8} 不能与 --volume 同时使用",
+        "{hash8} cannot be used together with --volume",
+    ),
+    ("命名模板: {}.7z", "Name template: {}.7z"),
+    ("压缩级别: {}", "Compression level: {}"),
+    ("分卷大小: {}", "Volume size: {}"),
+    ("删除原始文件: 已启用", "Delete originals: enabled"),
+    ("删除原始文件: 未启用", "Delete originals: disabled"),
+    ("没有找到要处理的项目", "No items to process"),
+    ("找到 {} 个项目要处理\n", "Found {} item(s) to process\n"),
+    ("处理 {} 失败", "Failed to process {}"),
+    ("压缩后移动到回收站", "move to trash after compressing"),
+    // commands/clean_old.rs
+    (
+        "必须至少指定 --days 或 --keep 之一",
+        "At least one of --days or --keep is required",
+    ),
+    ("无法访问目录: {}", "Cannot access directory: {}"),
+    ("路径必须是目录: {}", "Path must be a directory: {}"),
+    ("{} 旧文件清理工具 {}", "{} Old File Cleanup {}"),
+    ("目录: {}", "Directory: {}"),
+    ("保留天数: {}", "Keep days: {}"),
+    ("保留数量: {}", "Keep count: {}"),
+    ("没有需要清理的文件", "Nothing to clean up"),
+    ("✓ 已将文件移动到回收站: {}", "✓ Moved file to trash: {}"),
+    // commands/clean_temp.rs
+    ("{} 临时文件清理工具 {}", "{} Temp File Cleanup {}"),
+    ("临时目录: {}", "Temp directory: {}"),
+    (
+        "清理条件: {} 小时前修改",
+        "Criteria: modified more than {} hour(s) ago",
+    ),
+    ("没有需要清理的临时文件", "No temp files to clean up"),
+    ("✓ 已删除: {}", "✓ Deleted: {}"),
+    ("✗ 删除失败: {} - {}", "✗ Failed to delete: {} - {}"),
+    ("{} 统计结果 {}", "{} Summary {}"),
+    ("删除: {}", "Deleted: {}"),
+    ("失败: {}", "Failed: {}"),
+    ("释放空间: {}", "Space freed: {}"),
+    ("永久删除", "delete permanently"),
+    // commands/config.rs
+    ("配置文件: {}", "Config file: {}"),
+    ("读取配置文件失败: {}", "Failed to read config file: {}"),
+    (
+        "配置文件不存在，使用 `scripts config edit` 创建",
+        "Config file does not exist; create it with `scripts config edit`",
+    ),
+    ("创建配置文件失败: {}", "Failed to create config file: {}"),
+    ("已创建配置文件: {}", "Created config file: {}"),
+    ("启动编辑器失败: {}", "Failed to start editor: {}"),
+    ("编辑器异常退出: {}", "Editor exited abnormally: {}"),
+    // commands/dir_diff.rs
+    ("路径不存在: {}", "Path does not exist: {}"),
+    ("清单中不存在: {}", "Not in manifest: {}"),
+    ("比较 {} 失败", "Failed to compare {}"),
+    ("序列化 JSON 失败", "Failed to serialize JSON"),
+    ("{} 目录差异对比 {}", "{} Directory Diff {}"),
+    ("新增: {}", "Added: {}"),
+    ("变化: {}", "Changed: {}"),
+    // commands/doctor.rs
+    ("未知的子命令: {}", "Unknown subcommand: {}"),
+    ("{} 环境检查工具 {}", "{} Environment Check {}"),
+    ("检查命令: {}", "Checking command: {}"),
+    (
+        "该命令不依赖外部工具",
+        "This command does not depend on external tools",
+    ),
+    ("可选", "optional"),
+    ("✗ {}: 未找到 ({})", "✗ {}: not found ({})"),
+    ("  安装: {}", "  Install: {}"),
+    ("AV1 编码器 ({}):", "AV1 encoders ({}):"),
+    (
+        "  安装: 更新显卡驱动，或使用包含 libsvtav1 的 ffmpeg 构建（--download-ffmpeg）",
+        "  Install: update the GPU drivers, or use an ffmpeg build with libsvtav1 (--download-ffmpeg)",
+    ),
+    ("AV1 编码器", "AV1 encoder"),
+    ("可用: {}", "Available: {}"),
+    ("缺失: {}", "Missing: {}"),
+    (
+        "{} 缺少必需的外部工具: {}",
+        "{} is missing required external tools: {}",
+    ),
+    (
+        "从 https://www.7-zip.org/ 下载安装，或执行 winget install 7zip.7zip",
+        "Download from https://www.7-zip.org/, or run winget install 7zip.7zip",
+    ),
+    (
+        "执行 winget install ffmpeg，或在相关命令中加上 --download-ffmpeg 下载静态构建",
+        "Run winget install ffmpeg, or pass --download-ffmpeg to the command to download a static build",
+    ),
+    ("随 ffmpeg 一起安装", "Installed together with ffmpeg"),
+    (
+        "从 https://git-scm.com/ 下载安装，或执行 winget install Git.Git",
+        "Download from https://git-scm.com/, or run winget install Git.Git",
+    ),
+    (
+        "--download-ffmpeg 需要，Windows 10 及以上系统自带",
+        "Needed by --download-ffmpeg; included with Windows 10 and later",
+    ),
+    (
+        "未安装 ffmpeg 时用于自动安装，可从 Microsoft Store 安装“应用安装程序”",
+        "Used to install ffmpeg automatically; install \"App Installer\" from the Microsoft Store",
+    ),
+    // commands/du.rs
+    ("{}└── {}  (其余 {} 项)", "{}└── {}  ({} more)"),
+    ("计算目录大小失败", "Failed to calculate directory size"),
+    (
+        "{} 项因权限不足等原因无法访问，以上大小为部分统计",
+        "{} item(s) could not be accessed (e.g. permission denied); sizes above are partial",
+    ),
+    // commands/extract.rs
+    ("{} 解压工具 {}", "{} Extract {}"),
+    ("压缩包数量: {}", "Archives: {}"),
+    ("压缩包不存在: {}", "Archive does not exist: {}"),
+    ("解压: {} -> {}", "Extracting: {} -> {}"),
+    // commands/flatten.rs
+    ("已删除空目录: {}", "Removed empty directory: {}"),
+    ("{} 目录扁平化工具 {}", "{} Flatten Directory {}"),
+    ("目标目录: {}", "Target directory: {}"),
+    ("无效的文件名", "Invalid file name"),
+    ("跳过(内容相同): {}", "Skipped (identical content): {}"),
+    ("移动: {} -> {}", "Move: {} -> {}"),
+    ("移动文件: {}", "Moved files: {}"),
+    ("跳过重复: {}", "Skipped duplicates: {}"),
+    // commands/git_clean.rs
+    ("仓库: {}", "Repository: {}"),
+    ("  工作区: {}  .git: {}", "  Worktree: {}  .git: {}"),
+    (
+        "  已合并到 {} 的分支: 无",
+        "  Branches merged into {}: none",
+    ),
+    ("  已合并到 {} 的分支: {}", "  Branches merged into {}: {}"),
+    ("  [计划] 删除分支: {}", "  [plan] Delete branch: {}"),
+    ("删除分支失败: {}", "Failed to delete branch: {}"),
+    ("  已删除分支: {}", "  Deleted branch: {}"),
+    (
+        "  未找到主分支，跳过分支检查",
+        "  Main branch not found, skipping branch check",
+    ),
+    ("  [计划] git gc", "  [plan] git gc"),
+    (
+        "  git gc: {} -> {}（释放 {}）",
+        "  git gc: {} -> {} (freed {})",
+    ),
+    ("{} Git 仓库整理工具 {}", "{} Git Repository Cleanup {}"),
+    ("扫描目录: {}", "Scanning directory: {}"),
+    ("  ✗ 处理失败: {}", "  ✗ Failed: {}"),
+    ("仓库数量: {}", "Repositories: {}"),
+    ("删除分支: {}", "Deleted branches: {}"),
+    ("{} 个仓库处理失败", "{} repository(ies) failed"),
+    // commands/hash.rs
+    ("复制到剪贴板失败", "Failed to copy to clipboard"),
+    ("{} 文件哈希工具 {}", "{} File Hash {}"),
+    ("清单: {}", "Manifest: {}"),
+    ("✗ 缺失: {}", "✗ Missing: {}"),
+    ("✗ 不匹配: {}", "✗ Mismatch: {}"),
+    ("✗ 读取失败: {}: {}", "✗ Read failed: {}: {}"),
+    ("通过: {}", "Passed: {}"),
+    ("{} 个文件校验失败", "{} file(s) failed verification"),
+    ("已复制到剪贴板", "Copied to clipboard"),
+    ("写入清单失败: {}", "Failed to write manifest: {}"),
+    (
+        "已写入清单: {} ({} 个文件)",
+        "Wrote manifest: {} ({} file(s))",
+    ),
+    // commands/hash_copy.rs
+    ("计算文件哈希失败", "Failed to hash file"),
+    ("目标已存在: {}", "Target already exists: {}"),
+    ("复制文件到 {} 失败", "Failed to copy file to {}"),
+    ("复制完成: {} -> {}", "Copied: {} -> {}"),
+    (
+        "无法将源文件移动到回收站: {}",
+        "Cannot move source file to trash: {}",
+    ),
+    (
+        "已将源文件移动到回收站: {}",
+        "Moved source file to trash: {}",
+    ),
+    (
+        "源目录和目标目录不能相同",
+        "Source and target directories must differ",
+    ),
+    ("源目录不存在: {}", "Source directory does not exist: {}"),
+    ("{} 哈希复制工具 {}", "{} Hash Copy {}"),
+    ("扩展名列表不能为空", "Extension list must not be empty"),
+    ("文件扩展名: {}", "File extensions: {}"),
+    (
+        "跳过符号链接: {} 个（使用 --follow-symlinks 处理链接指向的文件）",
+        "Skipped symlinks: {} (use --follow-symlinks to process the files they point to)",
+    ),
+    ("复制后移动到回收站", "move to trash after copying"),
+    // commands/history.rs
+    (
+        "  扫描 {}，处理 {}，删除 {}，失败 {}，读取 {}，写入 {}",
+        "  scanned {}, processed {}, deleted {}, failed {}, read {}, written {}",
+    ),
+    ("没有历史记录", "No history"),
+    ("删除历史文件失败: {}", "Failed to delete history file: {}"),
+    ("已清空历史记录", "History cleared"),
+    ("{} 执行历史工具 {}", "{} Command History {}"),
+    ("历史文件: {}", "History file: {}"),
+    ("没有匹配的记录", "No matching entries"),
+    // commands/join.rs
+    ("打开分块失败: {}", "Failed to open chunk: {}"),
+    ("读取分块失败: {}", "Failed to read chunk: {}"),
+    ("分块校验失败: {}", "Chunk verification failed: {}"),
+    ("✓ 分块校验通过: {}", "✓ Chunk verified: {}"),
+    ("创建输出文件失败: {}", "Failed to create output file: {}"),
+    (
+        "合并结果校验失败: {}",
+        "Joined file verification failed: {}",
+    ),
+    ("合并完成: {} ({})", "Joined: {} ({})"),
+    (
+        "清单中缺少原文件哈希: {}",
+        "Manifest is missing the original file hash: {}",
+    ),
+    ("无效的分块名称: {}", "Invalid chunk name: {}"),
+    ("清单中没有分块: {}", "Manifest contains no chunks: {}"),
+    ("输出文件已存在: {}", "Output file already exists: {}"),
+    ("{} 文件合并工具 {}", "{} File Join {}"),
+    ("分块数: {}", "Chunks: {}"),
+    // commands/media_info.rs
+    ("跳过 {}: {}", "Skipped {}: {}"),
+    ("{} 媒体信息报告 {}", "{} Media Info Report {}"),
+    ("编码过滤: {}", "Codec filter: {}"),
+    ("文件", "File"),
+    ("扫描文件: {}", "Scanned files: {}"),
+    ("匹配文件: {}", "Matched files: {}"),
+    ("视频编码", "Video"),
+    ("音频编码", "Audio"),
+    ("分辨率", "Resolution"),
+    ("时长", "Duration"),
+    ("码率", "Bitrate"),
+    ("容器", "Container"),
+    // commands/prune_archives.rs
+    (
+        "必须至少指定 --keep 或 --keep-days 之一",
+        "At least one of --keep or --keep-days is required",
+    ),
+    ("{} 压缩包清理工具 {}", "{} Archive Pruning {}"),
+    ("每组保留数量: {}", "Keep per group: {}"),
+    ("目录中没有 7z 压缩包", "No 7z archives in the directory"),
+    (
+        "{}: {} 个压缩包，保留 {} 个，清理 {} 个",
+        "{}: {} archive(s), keeping {}, pruning {}",
+    ),
+    ("没有需要清理的压缩包", "No archives to prune"),
+    (
+        "✓ 已将压缩包移动到回收站: {}",
+        "✓ Moved archive to trash: {}",
+    ),
+    ("清理: {}", "Pruned: {}"),
+    // commands/rename.rs
+    (
+        "读取修改时间失败: {}",
+        "Failed to read modification time: {}",
+    ),
+    (
+        "必须至少指定 --template、--find/--replace 或 --case 之一",
+        "At least one of --template, --find/--replace or --case is required",
+    ),
+    ("无效的正则表达式", "Invalid regular expression"),
+    (
+        "跳过(名称冲突): {} -> {}",
+        "Skipped (name conflict): {} -> {}",
+    ),
+    ("{} 重命名预览 {}", "{} Rename Preview {}"),
+    ("没有需要重命名的文件", "No files to rename"),
+    (
+        "共 {} 个文件将被重命名，跳过 {} 个",
+        "{} file(s) will be renamed, {} skipped",
+    ),
+    (
+        "dry-run 模式，未执行任何操作",
+        "Dry-run mode, nothing was changed",
+    ),
+    ("操作已取消", "Cancelled"),
+    (
+        "无法获取确认，操作已取消（非交互环境请使用 --yes）",
+        "Cannot get confirmation, cancelled (use --yes in non-interactive environments)",
+    ),
+    (
+        "无效的新文件名: {} -> \"{}\"（不能为空或包含路径分隔符）",
+        "Invalid new file name: {} -> \"{}\" (must not be empty or contain path separators)",
+    ),
+    ("已撤销: {} -> {}", "Reverted: {} -> {}"),
+    (
+        "✗ 撤销失败: {} -> {} - {}",
+        "✗ Failed to revert: {} -> {} - {}",
+    ),
+    ("重命名: {} -> {}", "Renamed: {} -> {}"),
+    ("重命名", "rename"),
+    ("重命名失败: {} -> {}", "Rename failed: {} -> {}"),
+    (
+        "目标已存在，拒绝覆盖: {}",
+        "Target already exists, refusing to overwrite: {}",
+    ),
+    // commands/repo_mirror.rs
+    ("读取仓库列表失败: {}", "Failed to read repository list: {}"),
+    (
+        "解析仓库列表失败: {}",
+        "Failed to parse repository list: {}",
+    ),
+    ("解析凭据失败: {}", "Failed to resolve credentials: {}"),
+    ("镜像任务异常退出", "Mirror task exited abnormally"),
+    ("✓ {} -> {} ({} 个分支)", "✓ {} -> {} ({} branch(es))"),
+    ("成功: {}", "Succeeded: {}"),
+    ("{} 个仓库镜像失败", "{} repository(ies) failed to mirror"),
+    ("{} 仓库镜像工具 {}", "{} Repository Mirror {}"),
+    ("仓库列表: {}", "Repository list: {}"),
+    ("并行数量: {}", "Parallel jobs: {}"),
+    ("缺少 --from 参数", "Missing --from argument"),
+    ("缺少 --to 参数", "Missing --to argument"),
+    ("源仓库: {}", "Source repository: {}"),
+    ("目标仓库: {}", "Target repository: {}"),
+    ("仓库镜像失败", "Repository mirror failed"),
+    (
+        "操作成功完成！共镜像 {} 个分支",
+        "Done! Mirrored {} branch(es)",
+    ),
+    // commands/residue_search.rs
+    (
+        "环境变量 ProgramFiles 未设置, 已跳过 C:\\Program Files",
+        "Environment variable ProgramFiles is not set, skipped C:\\Program Files",
+    ),
+    (
+        "环境变量 ProgramFiles(x86) 未设置, 已跳过 C:\\Program Files (x86)",
+        "Environment variable ProgramFiles(x86) is not set, skipped C:\\Program Files (x86)",
+    ),
+    (
+        "环境变量 ProgramData 未设置, 已跳过 C:\\ProgramData",
+        "Environment variable ProgramData is not set, skipped C:\\ProgramData",
+    ),
+    (
+        "环境变量 USERPROFILE 未设置, 已跳过用户主目录",
+        "Environment variable USERPROFILE is not set, skipped the user home directory",
+    ),
+    (
+        "环境变量 APPDATA 未设置, 已跳过 AppData\\Roaming 目录",
+        "Environment variable APPDATA is not set, skipped AppData\\Roaming",
+    ),
+    (
+        "环境变量 LOCALAPPDATA 未设置, 已跳过 AppData\\Local",
+        "Environment variable LOCALAPPDATA is not set, skipped AppData\\Local",
+    ),
+    (
+        "以下扫描目录不存在, 已跳过:",
+        "The following scan directories do not exist and were skipped:",
+    ),
+    (
+        "未找到任何有效的扫描根目录,请检查系统环境变量",
+        "No valid scan root directory found; check the system environment variables",
+    ),
+    (
+        "软件名不能为空或仅包含空白字符",
+        "Software name must not be empty or whitespace only",
+    ),
+    (
+        "{}  软件卸载残留查找工具  {}",
+        "{}  Uninstall Residue Search  {}",
+    ),
+    ("查询软件: {}", "Software: {}"),
+    ("扫描位置:", "Scan locations:"),
+    ("正在扫描,请稍候...", "Scanning, please wait..."),
+    ("{} 匹配结果 {}", "{} Matches {}"),
+    ("未找到匹配的目录", "No matching directories found"),
+    (
+        "    大小: {} (至少,{} 项无法访问)",
+        "    Size: {} (at least; {} item(s) inaccessible)",
+    ),
+    ("    大小: {}", "    Size: {}"),
+    ("    修改时间: {}", "    Modified: {}"),
+    ("匹配的目录: {} 个", "Matching directories: {}"),
+    (
+        "总大小: {} (部分统计,{} 项因权限不足等原因无法访问)",
+        "Total size: {} (partial; {} item(s) inaccessible, e.g. permission denied)",
+    ),
+    ("总大小: {}", "Total size: {}"),
+    (
+        "\n没有匹配的目录可供删除",
+        "\nNo matching directories to delete",
+    ),
+    ("请选择要删除的目录", "Select the directories to delete"),
+    ("未选择任何项,操作已取消", "Nothing selected, cancelled"),
+    (
+        "✓ 已将目录移动到回收站: {}",
+        "✓ Moved directory to trash: {}",
+    ),
+    // commands/schedule.rs
+    ("读取任务文件失败: {}", "Failed to read job file: {}"),
+    ("解析任务文件失败: {}", "Failed to parse job file: {}"),
+    (
+        "任务 {} 的 cron 表达式无效: {}",
+        "Invalid cron expression for job {}: {}",
+    ),
+    (
+        "任务名称无效: \"{}\"（用作日志文件名，不能包含路径分隔符或特殊字符）",
+        "Invalid job name: \"{}\" (used as the log file name; must not contain path separators or special characters)",
+    ),
+    ("{} 个任务执行失败", "{} job(s) failed"),
+    ("创建日志目录失败: {}", "Failed to create log directory: {}"),
+    ("打开日志文件失败: {}", "Failed to open log file: {}"),
+    (
+        "无法获取当前可执行文件路径",
+        "Cannot determine the current executable path",
+    ),
+    ("启动任务失败: {}", "Failed to start job: {}"),
+    ("===== 退出状态: {} =====", "===== Exit status: {} ====="),
+    ("[{}] 执行任务: {}", "[{}] Running job: {}"),
+    ("✓ 任务完成: {}", "✓ Job finished: {}"),
+    ("✗ 任务失败: {} (详见日志)", "✗ Job failed: {} (see log)"),
+    ("✗ 任务失败: {} - {}", "✗ Job failed: {} - {}"),
+    ("创建任务文件失败: {}", "Failed to create job file: {}"),
+    ("已创建任务文件模板: {}", "Created job file template: {}"),
+    (
+        "没有可执行的任务，请编辑任务文件: {}",
+        "No jobs to run; edit the job file: {}",
+    ),
+    ("{} 计划任务 {}", "{} Scheduled Jobs {}"),
+    ("任务文件: {}", "Job file: {}"),
+    ("日志目录: {}", "Log directory: {}"),
+    ("{}: {} (下次执行 {})", "{}: {} (next run {})"),
+    (
+        "所有任务均已没有后续执行时间，退出",
+        "No job has a next run time, exiting",
+    ),
+    // commands/secrets.rs
+    ("请输入密钥 {}:", "Enter secret {}:"),
+    ("读取输入失败", "Failed to read input"),
+    (
+        "已保存密钥: {}（引用方式: keyring:{}）",
+        "Saved secret: {} (reference it as keyring:{})",
+    ),
+    ("已删除密钥: {}", "Deleted secret: {}"),
+    // commands/split.rs
+    ("分块大小必须大于 0", "Chunk size must be greater than 0"),
+    ("文件不存在: {}", "File does not exist: {}"),
+    ("{} 文件分割工具 {}", "{} File Split {}"),
+    ("文件: {} ({})", "File: {} ({})"),
+    ("分块大小: {}", "Chunk size: {}"),
+    ("打开文件失败: {}", "Failed to open file: {}"),
+    ("创建分块失败: {}", "Failed to create chunk: {}"),
+    ("读取文件失败: {}", "Failed to read file: {}"),
+    ("写入分块失败: {}", "Failed to write chunk: {}"),
+    ("分块: {} ({})", "Chunk: {} ({})"),
+    ("操作成功完成！共 {} 个分块", "Done! {} chunk(s)"),
+    // commands/sync.rs
+    (
+        "不支持的地址类型 {}://，目前只支持本地路径",
+        "Unsupported location type {}://, only local paths are supported",
+    ),
+    (
+        "目标路径经由符号链接指向目标目录之外: {} -> {}",
+        "Target path escapes the target directory through a symlink: {} -> {}",
+    ),
+    ("删除符号链接失败: {}", "Failed to remove symlink: {}"),
+    ("复制文件失败: {}", "Failed to copy file: {}"),
+    (
+        "设置修改时间失败: {}",
+        "Failed to set modification time: {}",
+    ),
+    ("删除文件失败: {}", "Failed to delete file: {}"),
+    ("{} 目录同步工具 {}", "{} Directory Sync {}"),
+    (
+        "跳过符号链接: {} 个（使用 --follow-symlinks 或 --preserve-symlinks 处理链接）",
+        "Skipped symlinks: {} (use --follow-symlinks or --preserve-symlinks to handle them)",
+    ),
+    ("复制: {}", "Copy: {}"),
+    ("链接: {} -> {}", "Link: {} -> {}"),
+    ("复制文件: {} ({})", "Copied files: {} ({})"),
+    ("符号链接: {}", "Symlinks: {}"),
+    ("未变化: {}", "Unchanged: {}"),
+    ("移动到回收站: {}", "Moved to trash: {}"),
+    // commands/unused_files.rs
+    ("无法获取相对路径: {}", "Cannot get relative path: {}"),
+    ("路径包含无效的 UTF-8 字符", "Path contains invalid UTF-8"),
+    ("创建匹配器失败", "Failed to create matcher"),
+    ("遍历目录时出错", "Error while walking the directory"),
+    ("未使用", "unused"),
+    ("待定", "uncertain"),
+    ("{} 最大的文件 {}", "{} Largest Files {}"),
+    ("目录不存在: {}", "Directory does not exist: {}"),
+    (
+        "资源文件扩展名列表不能为空",
+        "Asset extension list must not be empty",
+    ),
+    (
+        "代码文件扩展名列表不能为空",
+        "Code extension list must not be empty",
+    ),
+    ("{}  未使用文件查找工具 {}", "{}  Unused File Finder {}"),
+    ("资源文件扩展名: {}", "Asset extensions: {}"),
+    (
+        "搜索范围: 所有不超过 {} 的文本文件",
+        "Search scope: all text files up to {}",
+    ),
+    ("代码文件扩展名: {}", "Code extensions: {}"),
+    ("未找到匹配的资源文件", "No matching asset files found"),
+    ("收集代码文件失败", "Failed to collect code files"),
+    (
+        "找到 {} 个资源文件需要检查",
+        "Found {} asset file(s) to check",
+    ),
+    ("找到 {} 个待搜索的文件\n", "Found {} file(s) to search\n"),
+    ("获取相对路径失败: {}", "Failed to get relative path: {}"),
+    ("检查文件失败: {}", "Failed to check file: {}"),
+    ("未使用的文件", "Unused Files"),
+    ("待定的文件", "Uncertain Files"),
+    ("已使用: {}", "Used: {}"),
+    ("未使用: {} ({})", "Unused: {} ({})"),
+    ("待定: {} ({})", "Uncertain: {} ({})"),
+    ("总计: {}", "Total: {}"),
+    (
+        "可回收空间: {} (含待定 {})",
+        "Reclaimable space: {} (including uncertain {})",
+    ),
+    // commands/video_thumbs.rs
+    ("ffmpeg 处理失败: {}", "ffmpeg failed: {}"),
+    ("封面: {}", "Cover: {}"),
+    ("预览: {}", "Preview: {}"),
+    ("{} 视频缩略图工具 {}", "{} Video Thumbnails {}"),
+    (
+        "截取方式: 均匀截取 {} 张",
+        "Capture: {} evenly spaced frame(s)",
+    ),
+    ("截取方式: 第 {} 秒", "Capture: at {} s"),
+    ("动态预览: {}", "Animated preview: {}"),
+    ("已启用", "enabled"),
+    ("未启用", "disabled"),
+    // commands/video_transcode.rs
+    ("等待 ffmpeg 完成 失败: {}", "Failed to wait for ffmpeg: {}"),
+    ("ffmpeg 转码失败: {}", "ffmpeg failed to transcode: {}"),
+    ("转码完成: {}", "Transcoded: {}"),
+    ("{} 视频转码工具 {}", "{} Video Transcode {}"),
+    ("编码器: {}", "Encoder: {}"),
+    ("编码质量: CRF={}", "Quality: CRF={}"),
+    // main.rs
+    ("警告: {}", "Warning: {}"),
+    (
+        "警告: {}，已忽略配置文件",
+        "Warning: {}, config file ignored",
+    ),
+    ("已中断", "Interrupted"),
+    ("已清理: {}", "Cleaned up: {}"),
+    // utils/compress.rs
+    (
+        "未找到 7z 可执行文件。请从 https://www.7-zip.org/ 安装 7-Zip",
+        "7z executable not found. Install 7-Zip from https://www.7-zip.org/",
+    ),
+    ("执行 7z 命令失败: {}", "Failed to run 7z: {}"),
+    (
+        "7z 压缩失败: {}，退出码: {}",
+        "7z compression failed: {}, exit code: {}",
+    ),
+    (
+        "7z 解压失败: {}，退出码: {}",
+        "7z extraction failed: {}, exit code: {}",
+    ),
+    // utils/config.rs
+    (
+        "无法确定用户主目录",
+        "Cannot determine the user home directory",
+    ),
+    ("解析配置文件失败: {}", "Failed to parse config file: {}"),
+    (
+        "不支持的配置值类型: {}",
+        "Unsupported config value type: {}",
+    ),
+    (
+        "配置项 [{}] 必须是表",
+        "Config section [{}] must be a table",
+    ),
+    (
+        "配置文件中未知的子命令: [{}]",
+        "Unknown subcommand in config file: [{}]",
+    ),
+    (
+        "配置项 [{}] 中未知的参数: {}",
+        "Unknown argument in config section [{}]: {}",
+    ),
+    (
+        "配置项 [{}] 参数 {} 无效",
+        "Config section [{}]: invalid value for argument {}",
+    ),
+    // utils/confirm.rs
+    ("{} 待确认操作 {}", "{} Confirmation Required {}"),
+    ("以下 {} 项将被{}:", "{} item(s) to {}:"),
+    ("已通过 --yes 确认", "Confirmed by --yes"),
+    ("确认{}以上 {} 项？", "Proceed to {} the {} item(s) above?"),
+    // utils/error.rs
+    ("✗ 失败: {} - {}", "✗ Failed: {} - {}"),
+    ("{} 失败项目 {}", "{} Failed Items {}"),
+    ("{} 个项目处理失败", "{} item(s) failed"),
+    // utils/filesystem.rs
+    ("无效的 glob 模式: {}", "Invalid glob pattern: {}"),
+    ("构建 glob 匹配器失败", "Failed to build glob matcher"),
+    ("复制文件失败: {} -> {}", "Failed to copy file: {} -> {}"),
+    ("删除源文件失败: {}", "Failed to delete source file: {}"),
+    ("创建符号链接失败: {}", "Failed to create symlink: {}"),
+    // utils/git.rs
+    (
+        "执行 git 命令失败，请确认已安装 git",
+        "Failed to run git; make sure git is installed",
+    ),
+    ("git {} 失败，退出码: {}", "git {} failed, exit code: {}"),
+    ("访问远程仓库失败", "Failed to access the remote repository"),
+    ("镜像克隆: {}", "Mirror clone: {}"),
+    ("镜像推送: {}", "Mirror push: {}"),
+    (
+        "镜像推送失败（{}），回退为逐个推送分支",
+        "Mirror push failed ({}), falling back to pushing branches one by one",
+    ),
+    ("拉取: {}", "Fetch: {}"),
+    ("推送分支 ({}/{}): {}", "Push branch ({}/{}): {}"),
+    ("推送标签: {}", "Push tags: {}"),
+    // utils/hash.rs
+    ("读取清单文件失败: {}", "Failed to read manifest file: {}"),
+    (
+        "清单格式错误: {} 第 {} 行",
+        "Invalid manifest format: {} line {}",
+    ),
+    // utils/history.rs
+    (
+        "警告: 写入执行历史失败: {}",
+        "Warning: failed to write command history: {}",
+    ),
+    ("打开历史文件失败: {}", "Failed to open history file: {}"),
+    ("写入历史文件失败: {}", "Failed to write history file: {}"),
+    ("读取历史文件失败: {}", "Failed to read history file: {}"),
+    // utils/media.rs
+    (
+        "编码器不可用: {}，请检查硬件驱动或 ffmpeg 构建",
+        "Encoder unavailable: {}; check the hardware drivers or the ffmpeg build",
+    ),
+    (
+        "暂不支持自动下载该平台的 ffmpeg: {}-{}",
+        "Automatic ffmpeg download is not supported on this platform: {}-{}",
+    ),
+    ("正在下载: {}", "Downloading: {}"),
+    (
+        "执行 curl 失败，请确保 curl 可用",
+        "Failed to run curl; make sure curl is available",
+    ),
+    ("下载失败: {}", "Download failed: {}"),
+    (
+        "校验文件中未找到 {} 的哈希值",
+        "No hash for {} in the checksum file",
+    ),
+    (
+        "ffmpeg 压缩包校验失败: 期望 {}，实际 {}",
+        "ffmpeg archive checksum mismatch: expected {}, got {}",
+    ),
+    ("SHA-256 校验通过: {}", "SHA-256 verified: {}"),
+    (
+        "执行 tar 失败，请确保 tar 可用",
+        "Failed to run tar; make sure tar is available",
+    ),
+    ("解压 ffmpeg 失败: {}", "Failed to extract ffmpeg: {}"),
+    (
+        "下载的压缩包中未找到 {}",
+        "{} not found in the downloaded archive",
+    ),
+    (
+        "设置可执行权限失败: {}",
+        "Failed to set executable permission: {}",
+    ),
+    ("ffmpeg 已下载到: {}", "ffmpeg downloaded to: {}"),
+    (
+        "ffmpeg 未安装，正在下载静态构建...",
+        "ffmpeg is not installed, downloading a static build...",
+    ),
+    (
+        "ffmpeg 未安装，正在使用 winget 安装...",
+        "ffmpeg is not installed, installing with winget...",
+    ),
+    (
+        "执行 winget 失败，请确保 winget 可用，或使用 --download-ffmpeg 下载静态构建",
+        "Failed to run winget; make sure winget is available, or use --download-ffmpeg to download a static build",
+    ),
+    ("winget 安装 ffmpeg 失败", "winget failed to install ffmpeg"),
+    ("ffmpeg 安装完成", "ffmpeg installed"),
+    ("执行 ffprobe 失败: {}", "Failed to run ffprobe: {}"),
+    (
+        "ffprobe 读取时长失败: {}",
+        "ffprobe failed to read duration: {}",
+    ),
+    ("无法解析媒体时长: {}", "Cannot parse media duration: {}"),
+    (
+        "ffprobe 读取媒体信息失败: {}",
+        "ffprobe failed to read media info: {}",
+    ),
+    (
+        "无法解析 ffprobe 输出: {}",
+        "Cannot parse ffprobe output: {}",
+    ),
+    // utils/plan.rs
+    ("{} 执行计划 (dry-run) {}", "{} Execution Plan (dry-run) {}"),
+    ("没有需要执行的操作", "Nothing to do"),
+    ("共 {} 个操作，涉及 {}", "{} operation(s), {} in total"),
+    ("压缩", "compress"),
+    ("复制", "copy"),
+    ("删除", "delete"),
+    ("移动", "move"),
+    ("回收", "trash"),
+    // utils/secrets.rs
+    ("无法访问密钥环条目: {}", "Cannot access keyring entry: {}"),
+    ("保存密钥失败: {}", "Failed to save secret: {}"),
+    ("读取密钥失败: {}", "Failed to read secret: {}"),
+    ("删除密钥失败: {}", "Failed to delete secret: {}"),
+    // utils/stats.rs
+    ("{} 运行统计 {}", "{} Run Statistics {}"),
+    (
+        "扫描: {}  处理: {}  删除: {}  失败: {}",
+        "Scanned: {}  Processed: {}  Deleted: {}  Failed: {}",
+    ),
+    ("读取: {}  写入: {}", "Read: {}  Written: {}"),
+    ("耗时: {}s", "Elapsed: {}s"),
+    ("写入统计文件失败: {}", "Failed to write stats file: {}"),
+    // utils/tempfiles.rs
+    (
+        "创建临时目录失败: {}",
+        "Failed to create temp directory: {}",
+    ),
+    ("无效的输出路径: {}", "Invalid output path: {}"),
+    (
+        "重命名为输出文件失败: {}",
+        "Failed to rename to output file: {}",
+    ),
+];
@@ -1,24 +1,379 @@
 //! # 媒体工具模块
 //!
-//! 提供媒体处理相关的工具函数，例如测试编码器可用性。
+//! 提供媒体处理相关的工具函数，例如测试编码器可用性、收集视频文件等。
 
+use crate::utils::error::{ErrorKind, ScriptError};
+use crate::utils::filesystem::get_file_extension;
+use crate::utils::i18n::tr;
+use crate::utils::tempfiles::TempPath;
 use anyhow::{Context, Result};
+use cached::proc_macro::cached;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::path::{Path, PathBuf};
 use std::process::{Command as StdCommand, Stdio};
 
+/// 支持的视频文件扩展名列表（小写，不带点）
+pub const VIDEO_EXTENSIONS: [&str; 12] = [
+    "mp4", "mkv", "avi", "mov", "wmv", "flv", "webm", "m4v", "3gp", "ts", "mts", "m2ts",
+];
+
+/// 支持的音频文件扩展名列表（小写，不带点）
+pub const AUDIO_EXTENSIONS: [&str; 9] = [
+    "mp3", "m4a", "aac", "opus", "ogg", "flac", "wav", "wma", "ape",
+];
+
+/// AV1 编码器选择
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum Av1Encoder {
+    /// 按优先级自动选择第一个可用的编码器
+    #[default]
+    Auto,
+    /// NVIDIA GPU (NVENC)
+    Nvenc,
+    /// Intel GPU (Quick Sync Video)
+    Qsv,
+    /// AMD GPU (AMF)
+    Amf,
+    /// SVT-AV1 (CPU)
+    Svt,
+}
+
+impl Av1Encoder {
+    /// 所有具体编码器（不含 `Auto`），按自动选择的优先级排序
+    pub const ALL: [Av1Encoder; 4] = [
+        Av1Encoder::Nvenc,
+        Av1Encoder::Qsv,
+        Av1Encoder::Amf,
+        Av1Encoder::Svt,
+    ];
+
+    /// 命令行中使用的名称，例如 "nvenc"
+    pub fn name(self) -> &'static str {
+        match self {
+            Av1Encoder::Auto => "auto",
+            Av1Encoder::Nvenc => "nvenc",
+            Av1Encoder::Qsv => "qsv",
+            Av1Encoder::Amf => "amf",
+            Av1Encoder::Svt => "svt",
+        }
+    }
+
+    /// 对应的 ffmpeg 编码器名称候选，按优先级排序
+    pub fn ffmpeg_names(self) -> &'static [&'static str] {
+        match self {
+            Av1Encoder::Auto => &["av1_nvenc", "av1_qsv", "av1_amf", "svt-av1", "libsvtav1"],
+            Av1Encoder::Nvenc => &["av1_nvenc"],
+            Av1Encoder::Qsv => &["av1_qsv"],
+            Av1Encoder::Amf => &["av1_amf"],
+            Av1Encoder::Svt => &["svt-av1", "libsvtav1"],
+        }
+    }
+
+    /// 解析为可用的 ffmpeg 编码器名称
+    ///
+    /// `Auto` 使用 [`detect_av1_encoder`] 的缓存结果；其他选项测试对应的编码器，
+    /// 不可用时返回错误而不是回退到其他编码器。
+    pub fn resolve(self) -> Result<String> {
+        if self == Av1Encoder::Auto {
+            return detect_av1_encoder();
+        }
+
+        self.ffmpeg_names()
+            .iter()
+            .find(|encoder| test_encoder(encoder))
+            .map(|encoder| encoder.to_string())
+            .ok_or_else(|| {
+                ScriptError::new(
+                    ErrorKind::Dependency,
+                    tr!(
+                        "编码器不可用: {}，请检查硬件驱动或 ffmpeg 构建",
+                        self.name()
+                    ),
+                )
+                .into()
+            })
+    }
+}
+
+/// 媒体文件信息
+///
+/// 由 [`probe_media`] 从 ffprobe 输出中提取，字段缺失时为 `None`。
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaInfo {
+    /// 文件路径
+    pub path: PathBuf,
+    /// 容器格式，例如 "matroska,webm"
+    pub container: String,
+    /// 时长（秒）
+    pub duration: Option<f64>,
+    /// 总码率（bit/s）
+    pub bit_rate: Option<u64>,
+    /// 第一条视频流的编码，例如 "h264"
+    pub video_codec: Option<String>,
+    /// 视频宽度（像素）
+    pub width: Option<u32>,
+    /// 视频高度（像素）
+    pub height: Option<u32>,
+    /// 第一条音频流的编码，例如 "aac"
+    pub audio_codec: Option<String>,
+}
+
+/// ffprobe `-show_format -show_streams` 的 JSON 输出（仅包含用到的字段）
+#[derive(Debug, Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+    format: ProbeFormat,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeFormat {
+    format_name: String,
+    duration: Option<String>,
+    bit_rate: Option<String>,
+}
+
+/// 获取自动下载的 ffmpeg 存放目录
+///
+/// 位于应用数据目录下（Windows 为 `%LOCALAPPDATA%\scripts\ffmpeg`）。
+pub fn ffmpeg_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(env::temp_dir)
+        .join("scripts")
+        .join("ffmpeg")
+}
+
+/// 获取可执行文件名（Windows 下追加 `.exe`）
+fn executable_name(name: &str) -> String {
+    format!("{}{}", name, env::consts::EXE_SUFFIX)
+}
+
+/// 查找 ffmpeg 可执行文件
+///
+/// 优先使用 [`ffmpeg_dir`] 中自动下载的版本，否则使用 PATH 中的 `ffmpeg`。
+pub fn find_ffmpeg() -> PathBuf {
+    let downloaded = ffmpeg_dir().join(executable_name("ffmpeg"));
+    if downloaded.is_file() {
+        downloaded
+    } else {
+        PathBuf::from("ffmpeg")
+    }
+}
+
+/// 查找 ffprobe 可执行文件
+///
+/// 优先使用 [`ffmpeg_dir`] 中自动下载的版本，否则使用 PATH 中的 `ffprobe`。
+pub fn find_ffprobe() -> PathBuf {
+    let downloaded = ffmpeg_dir().join(executable_name("ffprobe"));
+    if downloaded.is_file() {
+        downloaded
+    } else {
+        PathBuf::from("ffprobe")
+    }
+}
+
+/// BtbN/FFmpeg-Builds 发布页中各压缩包的 SHA-256 校验文件
+const STATIC_BUILD_CHECKSUMS_URL: &str =
+    "https://github.com/BtbN/FFmpeg-Builds/releases/download/latest/checksums.sha256";
+
+/// 获取当前平台的 ffmpeg 静态构建下载地址
+///
+/// 使用 BtbN/FFmpeg-Builds，仅支持发布了 SHA-256 校验文件的 Windows 和 Linux 构建。
+fn static_build_url() -> Result<&'static str> {
+    let url = match (env::consts::OS, env::consts::ARCH) {
+        ("windows", "x86_64") => {
+            "https://github.com/BtbN/FFmpeg-Builds/releases/download/latest/ffmpeg-master-latest-win64-gpl.zip"
+        }
+        ("linux", "x86_64") => {
+            "https://github.com/BtbN/FFmpeg-Builds/releases/download/latest/ffmpeg-master-latest-linux64-gpl.tar.xz"
+        }
+        ("linux", "aarch64") => {
+            "https://github.com/BtbN/FFmpeg-Builds/releases/download/latest/ffmpeg-master-latest-linuxarm64-gpl.tar.xz"
+        }
+        (os, arch) => {
+            return Err(ScriptError::new(
+                ErrorKind::Dependency,
+                tr!("暂不支持自动下载该平台的 ffmpeg: {}-{}", os, arch),
+            )
+            .into());
+        }
+    };
+    Ok(url)
+}
+
+/// 使用 curl 下载文件，失败时返回网络错误
+fn curl_download(url: &str, output: &Path) -> Result<()> {
+    let status = StdCommand::new("curl")
+        .arg("-L")
+        .arg("--fail")
+        .arg("-o")
+        .arg(output)
+        .arg(url)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|_| {
+            ScriptError::new(
+                ErrorKind::Dependency,
+                tr!("执行 curl 失败，请确保 curl 可用"),
+            )
+        })?;
+    if !status.success() {
+        return Err(ScriptError::new(ErrorKind::Network, tr!("下载失败: {}", url)).into());
+    }
+    Ok(())
+}
+
+/// 计算文件的 SHA-256 哈希值（小写十六进制）
+fn sha256_file(path: &Path) -> Result<String> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    let mut file =
+        std::fs::File::open(path).with_context(|| tr!("打开文件失败: {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .with_context(|| tr!("读取文件失败: {}", path.display()))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// 在 `sha256sum` 格式的校验文件中查找指定文件名对应的哈希值
+///
+/// 每行格式为 `<hex>  <name>` 或 `<hex> *<name>`。
+fn find_checksum(checksums: &str, file_name: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start().trim_start_matches('*');
+        (name == file_name).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// 下载 ffmpeg 静态构建到应用数据目录
+///
+/// 使用系统自带的 `curl` 下载压缩包和发布页的 SHA-256 校验文件，校验通过后
+/// 用 `tar` 解压（Windows 10+ 的 bsdtar 支持 zip），然后将 ffmpeg 和 ffprobe
+/// 复制到 [`ffmpeg_dir`]。
+///
+/// # 返回值
+///
+/// * `Ok(())` - 下载并安装成功
+/// * `Err(anyhow::Error)` - 平台不支持、下载失败、校验不通过或解压失败
+pub fn download_ffmpeg() -> Result<()> {
+    let url = static_build_url()?;
+    let install_dir = ffmpeg_dir();
+    std::fs::create_dir_all(&install_dir)
+        .with_context(|| tr!("创建目录失败: {}", install_dir.display()))?;
+    // 离开作用域时（包括出错返回）自动删除
+    let work_dir = TempPath::dir("ffmpeg-download")?;
+    let work_dir = work_dir.path();
+
+    println!("{}", tr!("正在下载: {}", url));
+    let archive = work_dir.join("archive");
+    curl_download(url, &archive)?;
+
+    // 解压前校验压缩包，避免执行被篡改或不完整的文件
+    let checksums_path = work_dir.join("checksums.sha256");
+    curl_download(STATIC_BUILD_CHECKSUMS_URL, &checksums_path)?;
+    let checksums = std::fs::read_to_string(&checksums_path)
+        .with_context(|| tr!("读取文件失败: {}", checksums_path.display()))?;
+    let asset_name = url.rsplit('/').next().unwrap_or(url);
+    let expected = find_checksum(&checksums, asset_name).ok_or_else(|| {
+        ScriptError::new(
+            ErrorKind::Verification,
+            tr!("校验文件中未找到 {} 的哈希值", asset_name),
+        )
+    })?;
+    let actual = sha256_file(&archive)?;
+    if actual != expected {
+        return Err(ScriptError::new(
+            ErrorKind::Verification,
+            tr!("ffmpeg 压缩包校验失败: 期望 {}，实际 {}", expected, actual),
+        )
+        .into());
+    }
+    println!("{}", tr!("SHA-256 校验通过: {}", actual));
+
+    let status = StdCommand::new("tar")
+        .arg("-xf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(work_dir)
+        .status()
+        .with_context(|| tr!("执行 tar 失败，请确保 tar 可用"))?;
+    if !status.success() {
+        anyhow::bail!(tr!("解压 ffmpeg 失败: {}", archive.display()));
+    }
+
+    // 在解压结果中查找可执行文件并复制到安装目录
+    for name in ["ffmpeg", "ffprobe"] {
+        let file_name = executable_name(name);
+        let found = walkdir::WalkDir::new(work_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .find(|e| e.file_type().is_file() && e.file_name() == file_name.as_str())
+            .with_context(|| tr!("下载的压缩包中未找到 {}", file_name))?;
+
+        // 复制到安装目录中的临时文件后再重命名，避免留下不完整的可执行文件
+        let target = install_dir.join(&file_name);
+        let temp = TempPath::beside(&target)?;
+        std::fs::copy(found.path(), temp.path())
+            .with_context(|| tr!("复制文件失败: {}", target.display()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(temp.path(), std::fs::Permissions::from_mode(0o755))
+                .with_context(|| tr!("设置可执行权限失败: {}", target.display()))?;
+        }
+        temp.persist(&target)?;
+    }
+
+    println!("{}", tr!("ffmpeg 已下载到: {}", install_dir.display()));
+    Ok(())
+}
+
 /// 确保 ffmpeg 可用
 ///
-/// 检测系统中是否安装了 ffmpeg，如果未安装则使用 winget 自动安装。
+/// 检测系统中是否安装了 ffmpeg，如果未安装则自动安装：
+/// `download` 为 `true` 时下载静态构建到应用数据目录，否则使用 winget 安装。
+///
+/// # 参数
+///
+/// * `download` - 未安装时是否下载静态构建（而非使用 winget）
 ///
 /// # 返回值
 ///
 /// * `Ok(())` - ffmpeg 可用或安装成功
-/// * `Err(anyhow::Error)` - 安装失败
+/// * `Err(anyhow::Error)` - 安装失败（[`ErrorKind::Dependency`]）
 ///
 /// # 技术细节
 ///
-/// - 通过执行 `ffmpeg -version` 检测 ffmpeg 是否可用
-/// - 若不可用，使用 `winget install ffmpeg` 进行安装
-/// - winget 安装时 stdout/stderr 继承到当前终端，方便用户查看安装进度
+/// - 通过执行 `ffmpeg -version` 检测 ffmpeg 是否可用（见 [`find_ffmpeg`]）
+/// - 若不可用且 `download` 为 `true`，调用 [`download_ffmpeg`]
+/// - 否则使用 `winget install ffmpeg` 进行安装
+/// - 安装时 stdout/stderr 继承到当前终端，方便用户查看安装进度
 ///
 /// # 示例
 ///
@@ -26,14 +381,14 @@ use std::process::{Command as StdCommand, Stdio};
 /// use scripts::utils::media::ensure_ffmpeg;
 ///
 /// fn main() -> anyhow::Result<()> {
-///     ensure_ffmpeg()?;
+///     ensure_ffmpeg(false)?;
 ///     println!("ffmpeg 已就绪");
 ///     Ok(())
 /// }
 /// ```
-pub fn ensure_ffmpeg() -> Result<()> {
+pub fn ensure_ffmpeg(download: bool) -> Result<()> {
     // 检测 ffmpeg 是否可用
-    let check = StdCommand::new("ffmpeg")
+    let check = StdCommand::new(find_ffmpeg())
         .arg("-version")
         .stdout(Stdio::null())
         .stderr(Stdio::null())
@@ -44,8 +399,13 @@ pub fn ensure_ffmpeg() -> Result<()> {
         _ => {}
     }
 
+    if download {
+        println!("{}", tr!("ffmpeg 未安装，正在下载静态构建..."));
+        return download_ffmpeg();
+    }
+
     // ffmpeg 不可用，使用 winget 安装
-    println!("ffmpeg 未安装，正在使用 winget 安装...");
+    println!("{}", tr!("ffmpeg 未安装，正在使用 winget 安装..."));
 
     let install_status = StdCommand::new("winget")
         .arg("install")
@@ -56,13 +416,18 @@ pub fn ensure_ffmpeg() -> Result<()> {
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .status()
-        .context("执行 winget 失败，请确保 winget 可用")?;
+        .map_err(|_| {
+            ScriptError::new(
+                ErrorKind::Dependency,
+                tr!("执行 winget 失败，请确保 winget 可用，或使用 --download-ffmpeg 下载静态构建"),
+            )
+        })?;
 
     if !install_status.success() {
-        anyhow::bail!("winget 安装 ffmpeg 失败");
+        return Err(ScriptError::new(ErrorKind::Dependency, tr!("winget 安装 ffmpeg 失败")).into());
     }
 
-    println!("ffmpeg 安装完成");
+    println!("{}", tr!("ffmpeg 安装完成"));
     Ok(())
 }
 
@@ -97,7 +462,7 @@ pub fn ensure_ffmpeg() -> Result<()> {
 /// }
 /// ```
 pub fn test_encoder(encoder: &str) -> bool {
-    let result = StdCommand::new("ffmpeg")
+    let result = StdCommand::new(find_ffmpeg())
         .arg("-f")
         .arg("lavfi")
         .arg("-i")
@@ -119,3 +484,235 @@ pub fn test_encoder(encoder: &str) -> bool {
         Err(_) => false,
     }
 }
+
+/// 收集指定目录下扩展名在给定列表中的文件
+fn collect_files_by_extension(
+    source_dir: &Path,
+    max_depth: usize,
+    extensions: &[&str],
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    // 递归遍历目录,按扩展名收集文件
+    for entry in walkdir::WalkDir::new(source_dir)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+
+        // 跳过非文件项
+        if !path.is_file() {
+            continue;
+        }
+
+        let ext = get_file_extension(path);
+        if !ext.is_empty() && extensions.contains(&ext.as_str()) {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    files
+}
+
+/// 收集指定目录下的所有视频文件
+///
+/// 递归遍历目录，按扩展名（见 [`VIDEO_EXTENSIONS`]）筛选视频文件。
+///
+/// # 参数
+///
+/// * `source_dir` - 源目录路径
+/// * `max_depth` - 最大扫描深度
+///
+/// # 返回值
+///
+/// 返回找到的所有视频文件路径列表
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::media::collect_video_files;
+/// use std::path::Path;
+///
+/// let videos = collect_video_files(Path::new("./videos"), 3);
+/// println!("找到 {} 个视频文件", videos.len());
+/// ```
+pub fn collect_video_files(source_dir: &Path, max_depth: usize) -> Vec<PathBuf> {
+    collect_files_by_extension(source_dir, max_depth, &VIDEO_EXTENSIONS)
+}
+
+/// 收集指定目录下的所有媒体文件（视频和音频）
+///
+/// 递归遍历目录，按扩展名（见 [`VIDEO_EXTENSIONS`] 和 [`AUDIO_EXTENSIONS`]）筛选媒体文件。
+///
+/// # 参数
+///
+/// * `source_dir` - 源目录路径
+/// * `max_depth` - 最大扫描深度
+///
+/// # 返回值
+///
+/// 返回找到的所有媒体文件路径列表
+pub fn collect_media_files(source_dir: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let extensions: Vec<&str> = VIDEO_EXTENSIONS
+        .iter()
+        .chain(AUDIO_EXTENSIONS.iter())
+        .copied()
+        .collect();
+    collect_files_by_extension(source_dir, max_depth, &extensions)
+}
+
+/// 获取媒体文件时长（秒）
+///
+/// 使用 ffprobe 读取容器级别的 `format=duration` 字段。
+///
+/// # 参数
+///
+/// * `path` - 媒体文件路径
+///
+/// # 返回值
+///
+/// * `Ok(f64)` - 媒体时长（秒）
+/// * `Err(anyhow::Error)` - ffprobe 执行失败或无法解析时长
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::media::probe_duration;
+/// use std::path::Path;
+///
+/// let seconds = probe_duration(Path::new("./video.mp4"))?;
+/// println!("时长: {:.1} 秒", seconds);
+/// ```
+pub fn probe_duration(path: &Path) -> Result<f64> {
+    let output = StdCommand::new(find_ffprobe())
+        .arg("-v")
+        .arg("error")
+        .arg("-show_entries")
+        .arg("format=duration")
+        .arg("-of")
+        .arg("default=noprint_wrappers=1:nokey=1")
+        .arg(path)
+        .output()
+        .with_context(|| tr!("执行 ffprobe 失败: {}", path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(tr!("ffprobe 读取时长失败: {}", path.display()));
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.trim()
+        .parse::<f64>()
+        .with_context(|| tr!("无法解析媒体时长: {}", path.display()))
+}
+
+/// 读取媒体文件的编码、分辨率、时长等信息
+///
+/// 使用 `ffprobe -print_format json -show_format -show_streams` 读取信息，
+/// 取第一条视频流和第一条音频流的编码。
+///
+/// # 参数
+///
+/// * `path` - 媒体文件路径
+///
+/// # 返回值
+///
+/// * `Ok(MediaInfo)` - 媒体信息
+/// * `Err(anyhow::Error)` - ffprobe 执行失败或输出无法解析
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::media::probe_media;
+/// use std::path::Path;
+///
+/// let info = probe_media(Path::new("./video.mp4"))?;
+/// println!("视频编码: {:?}", info.video_codec);
+/// ```
+pub fn probe_media(path: &Path) -> Result<MediaInfo> {
+    let output = StdCommand::new(find_ffprobe())
+        .arg("-v")
+        .arg("error")
+        .arg("-print_format")
+        .arg("json")
+        .arg("-show_format")
+        .arg("-show_streams")
+        .arg(path)
+        .output()
+        .with_context(|| tr!("执行 ffprobe 失败: {}", path.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(tr!("ffprobe 读取媒体信息失败: {}", path.display()));
+    }
+
+    let probe: ProbeOutput = serde_json::from_slice(&output.stdout)
+        .with_context(|| tr!("无法解析 ffprobe 输出: {}", path.display()))?;
+
+    let video = probe
+        .streams
+        .iter()
+        .find(|s| s.codec_type.as_deref() == Some("video"));
+    let audio = probe
+        .streams
+        .iter()
+        .find(|s| s.codec_type.as_deref() == Some("audio"));
+
+    Ok(MediaInfo {
+        path: path.to_path_buf(),
+        container: probe.format.format_name,
+        duration: probe.format.duration.and_then(|d| d.parse().ok()),
+        bit_rate: probe.format.bit_rate.and_then(|b| b.parse().ok()),
+        video_codec: video.and_then(|s| s.codec_name.clone()),
+        width: video.and_then(|s| s.width),
+        height: video.and_then(|s| s.height),
+        audio_codec: audio.and_then(|s| s.codec_name.clone()),
+    })
+}
+
+/// 获取可用的 AV1 编码器（带缓存）
+///
+/// 按优先级顺序检测系统中可用的 AV1 编码器，首次检测后缓存结果。
+///
+/// # 编码器优先级
+///
+/// 1. `av1_nvenc` - NVIDIA GPU (NVENC)
+/// 2. `av1_qsv` - Intel GPU (Quick Sync Video)
+/// 3. `av1_amf` - AMD GPU (AMF)
+/// 4. `svt-av1` - SVT-AV1 (Multi-thread)
+/// 5. `libsvtav1` - SVT-AV1 (libsvtav1)
+///
+/// # 返回值
+///
+/// * `Ok(String)` - 可用编码器名称
+/// * `Err(anyhow::Error)` - 未找到可用的 AV1 编码器
+///
+/// # 技术细节
+///
+/// - 使用 `cached` 宏缓存成功结果，避免重复检测
+/// - 按优先级顺序测试编码器，返回第一个可用的编码器
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::media::detect_av1_encoder;
+///
+/// match detect_av1_encoder() {
+///     Ok(encoder) => println!("使用编码器: {}", encoder),
+///     Err(e) => eprintln!("错误: {}", e),
+/// }
+/// ```
+#[cached(result = true)]
+pub fn detect_av1_encoder() -> Result<String> {
+    Av1Encoder::Auto
+        .ffmpeg_names()
+        .iter()
+        .find(|encoder| test_encoder(encoder))
+        .map(|encoder| encoder.to_string())
+        .ok_or_else(|| {
+            ScriptError::new(
+                ErrorKind::Dependency,
+                tr!("未找到可用的 AV1 编码器，请检查硬件驱动或安装支持 AV1 的 ffmpeg"),
+            )
+            .into()
+        })
+}
@@ -0,0 +1,122 @@
+//! # 操作计划模块
+//!
+//! 描述文件修改类命令将要执行的操作（压缩、复制、删除、移动到回收站等），
+//! 用于 `--dry-run` 模式下输出完整的执行计划而不实际执行。
+
+use crate::utils::filesystem::path_size;
+use crate::utils::i18n::{tr, translate};
+use bytesize::ByteSize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// 操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// 压缩为归档文件
+    Compress,
+    /// 复制文件
+    Copy,
+    /// 永久删除
+    Delete,
+    /// 移动文件
+    Move,
+    /// 移动到回收站
+    Trash,
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OperationKind::Compress => "压缩",
+            OperationKind::Copy => "复制",
+            OperationKind::Delete => "删除",
+            OperationKind::Move => "移动",
+            OperationKind::Trash => "回收",
+        };
+        write!(f, "{}", translate(name))
+    }
+}
+
+/// 单个计划操作
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// 操作类型
+    pub kind: OperationKind,
+    /// 源路径
+    pub source: PathBuf,
+    /// 目标路径（移动到回收站等操作没有目标）
+    pub target: Option<PathBuf>,
+    /// 源文件或目录大小（字节数）
+    pub size: u64,
+}
+
+impl Operation {
+    /// 创建计划操作，自动计算源路径大小
+    pub fn new(kind: OperationKind, source: &Path, target: Option<&Path>) -> Self {
+        Self {
+            kind,
+            source: source.to_path_buf(),
+            target: target.map(Path::to_path_buf),
+            size: path_size(source),
+        }
+    }
+}
+
+/// 输出操作计划
+///
+/// 逐条列出操作（类型、源、目标、大小），最后输出操作数和涉及的总大小。
+///
+/// # 参数
+///
+/// * `operations` - 计划操作列表
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::plan::{Operation, OperationKind, print_plan};
+/// use std::path::Path;
+///
+/// let ops = vec![Operation::new(OperationKind::Trash, Path::new("./old"), None)];
+/// print_plan(&ops);
+/// ```
+pub fn print_plan(operations: &[Operation]) {
+    println!(
+        "{}",
+        tr!("{} 执行计划 (dry-run) {}", "=".repeat(20), "=".repeat(20))
+    );
+
+    if operations.is_empty() {
+        println!("{}", tr!("没有需要执行的操作"));
+        return;
+    }
+
+    for op in operations {
+        match &op.target {
+            Some(target) => println!(
+                "[{}] {} -> {} ({})",
+                op.kind,
+                op.source.display(),
+                target.display(),
+                ByteSize(op.size)
+            ),
+            None => println!(
+                "[{}] {} ({})",
+                op.kind,
+                op.source.display(),
+                ByteSize(op.size)
+            ),
+        }
+    }
+
+    let total_size: u64 = operations.iter().map(|op| op.size).sum();
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "共 {} 个操作，涉及 {}",
+            operations.len(),
+            ByteSize(total_size)
+        )
+    );
+    println!("{}", tr!("dry-run 模式，未执行任何操作"));
+}
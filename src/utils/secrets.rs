@@ -0,0 +1,95 @@
+//! # 密钥管理模块
+//!
+//! 使用系统密钥环（Windows 凭据管理器、macOS 钥匙串、Linux keyutils）保存密码等敏感信息，
+//! 并支持在参数和配置文件中以 `keyring:<名称>` 的形式引用。
+
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use keyring::Entry;
+
+/// 密钥环中使用的服务名
+const SERVICE: &str = "scripts";
+
+/// 密钥引用前缀
+pub const KEYRING_PREFIX: &str = "keyring:";
+
+/// 获取指定名称的密钥环条目
+fn entry(name: &str) -> Result<Entry> {
+    Entry::new(SERVICE, name).with_context(|| tr!("无法访问密钥环条目: {}", name))
+}
+
+/// 保存密钥到系统密钥环
+///
+/// # 参数
+///
+/// * `name` - 密钥名称
+/// * `value` - 密钥内容
+///
+/// # 返回值
+///
+/// * `Ok(())` - 保存成功
+/// * `Err(anyhow::Error)` - 密钥环不可用或写入失败
+pub fn set_secret(name: &str, value: &str) -> Result<()> {
+    entry(name)?
+        .set_password(value)
+        .with_context(|| tr!("保存密钥失败: {}", name))
+}
+
+/// 从系统密钥环读取密钥
+///
+/// # 参数
+///
+/// * `name` - 密钥名称
+///
+/// # 返回值
+///
+/// * `Ok(String)` - 密钥内容
+/// * `Err(anyhow::Error)` - 密钥不存在或密钥环不可用
+pub fn get_secret(name: &str) -> Result<String> {
+    entry(name)?
+        .get_password()
+        .with_context(|| tr!("读取密钥失败: {}", name))
+}
+
+/// 从系统密钥环删除密钥
+///
+/// # 参数
+///
+/// * `name` - 密钥名称
+///
+/// # 返回值
+///
+/// * `Ok(())` - 删除成功
+/// * `Err(anyhow::Error)` - 密钥不存在或密钥环不可用
+pub fn delete_secret(name: &str) -> Result<()> {
+    entry(name)?
+        .delete_credential()
+        .with_context(|| tr!("删除密钥失败: {}", name))
+}
+
+/// 解析可能包含密钥引用的值
+///
+/// 以 `keyring:` 开头的值会被替换为密钥环中对应名称的密钥，其他值原样返回。
+///
+/// # 参数
+///
+/// * `value` - 原始值，例如 `keyring:backup` 或明文密码
+///
+/// # 返回值
+///
+/// * `Ok(String)` - 解析后的值
+/// * `Err(anyhow::Error)` - 引用的密钥不存在或密钥环不可用
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::secrets::resolve_secret;
+///
+/// let password = resolve_secret("keyring:backup")?;
+/// ```
+pub fn resolve_secret(value: &str) -> Result<String> {
+    match value.strip_prefix(KEYRING_PREFIX) {
+        Some(name) => get_secret(name),
+        None => Ok(value.to_string()),
+    }
+}
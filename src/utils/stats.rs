@@ -0,0 +1,151 @@
+//! # 运行统计模块
+//!
+//! 各命令在处理过程中通过 [`add`] 上报扫描、处理、删除、失败的文件数量和读写字节数。
+//! 主函数在命令结束时向标准错误输出统一格式的统计块（不干扰 `--json` 等标准输出），
+//! 同时写入执行历史，并可通过全局选项 `--stats-json <FILE>` 保存为 JSON 供仪表盘使用。
+
+use crate::utils::history::HistoryEntry;
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use bytesize::ByteSize;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// 本次运行的统计
+static STATS: Mutex<RunStats> = Mutex::new(RunStats {
+    scanned: 0,
+    processed: 0,
+    deleted: 0,
+    failed: 0,
+    bytes_in: 0,
+    bytes_out: 0,
+});
+
+/// 统计项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+    /// 扫描的文件或项目数量
+    Scanned,
+    /// 处理（复制、压缩、转码、移动等）成功的数量
+    Processed,
+    /// 删除或移动到回收站的数量
+    Deleted,
+    /// 处理失败的数量
+    Failed,
+    /// 读取的字节数
+    BytesIn,
+    /// 写入的字节数
+    BytesOut,
+}
+
+/// 运行统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStats {
+    /// 扫描的文件或项目数量
+    pub scanned: u64,
+    /// 处理成功的数量
+    pub processed: u64,
+    /// 删除或移动到回收站的数量
+    pub deleted: u64,
+    /// 处理失败的数量
+    pub failed: u64,
+    /// 读取的字节数
+    pub bytes_in: u64,
+    /// 写入的字节数
+    pub bytes_out: u64,
+}
+
+impl RunStats {
+    /// 命令是否上报过任何统计
+    pub fn is_empty(&self) -> bool {
+        *self == RunStats::default()
+    }
+}
+
+/// 累加统计项
+///
+/// # 参数
+///
+/// * `counter` - 统计项
+/// * `value` - 增加的数量或字节数
+///
+/// # 示例
+///
+/// ```rust
+/// use scripts::utils::stats::{Counter, add};
+///
+/// add(Counter::Scanned, files.len() as u64);
+/// add(Counter::Processed, 1);
+/// add(Counter::BytesOut, size);
+/// ```
+pub fn add(counter: Counter, value: u64) {
+    if let Ok(mut stats) = STATS.lock() {
+        let field = match counter {
+            Counter::Scanned => &mut stats.scanned,
+            Counter::Processed => &mut stats.processed,
+            Counter::Deleted => &mut stats.deleted,
+            Counter::Failed => &mut stats.failed,
+            Counter::BytesIn => &mut stats.bytes_in,
+            Counter::BytesOut => &mut stats.bytes_out,
+        };
+        *field += value;
+    }
+}
+
+/// 获取当前的统计
+pub fn snapshot() -> RunStats {
+    STATS.lock().map(|stats| *stats).unwrap_or_default()
+}
+
+/// 向标准错误输出运行统计块
+///
+/// 命令没有上报任何统计时不输出。
+///
+/// # 参数
+///
+/// * `entry` - 本次调用的执行记录
+pub fn print_stats(entry: &HistoryEntry) {
+    let stats = &entry.stats;
+    if stats.is_empty() {
+        return;
+    }
+
+    eprintln!();
+    eprintln!("{}", tr!("{} 运行统计 {}", "=".repeat(20), "=".repeat(20)));
+    eprintln!(
+        "{}",
+        tr!(
+            "扫描: {}  处理: {}  删除: {}  失败: {}",
+            stats.scanned,
+            stats.processed,
+            stats.deleted,
+            stats.failed
+        )
+    );
+    eprintln!(
+        "{}",
+        tr!(
+            "读取: {}  写入: {}",
+            ByteSize(stats.bytes_in),
+            ByteSize(stats.bytes_out)
+        )
+    );
+    eprintln!("{}", tr!("耗时: {}s", format!("{:.1}", entry.duration)));
+}
+
+/// 将执行记录（含运行统计）保存为 JSON 文件
+///
+/// # 参数
+///
+/// * `path` - 输出文件路径，已存在时覆盖
+/// * `entry` - 本次调用的执行记录
+///
+/// # 返回值
+///
+/// * `Ok(())` - 写入成功
+/// * `Err(anyhow::Error)` - 写入失败
+pub fn write_stats_json(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    let json = serde_json::to_string_pretty(entry)?;
+    std::fs::write(path, json).with_context(|| tr!("写入统计文件失败: {}", path.display()))
+}
@@ -0,0 +1,170 @@
+//! # 临时文件模块
+//!
+//! 所有命令的临时文件和临时目录统一创建在系统临时目录下的 `scripts` 子目录中，
+//! 离开作用域（包括出错返回和 panic）时自动删除，并在存在期间登记到中断清理。
+//! 异常退出（例如进程被强制结束）残留的文件可以通过 `scripts clean-temp` 清理。
+//!
+//! 生成最终输出文件时使用 [`TempPath::beside`] 在目标文件所在目录创建临时文件，
+//! 写入完成后通过 [`TempPath::persist`] 重命名为目标文件。重命名在同一文件系统内是原子操作，
+//! 中途失败或中断不会留下看起来完整的截断文件。异常退出时残留在输出目录中的临时文件
+//! 可以通过 `scripts clean-temp <目录>` 清理。
+
+use crate::utils::cancel::{CleanupGuard, register_cleanup};
+use crate::utils::i18n::tr;
+use anyhow::{Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// 临时文件名前缀
+pub const TEMP_PREFIX: &str = "scripts-";
+
+/// 获取本程序使用的临时目录：`<系统临时目录>/scripts`
+pub fn temp_root() -> PathBuf {
+    env::temp_dir().join("scripts")
+}
+
+/// 作用域临时路径
+///
+/// 丢弃时删除对应的文件或目录（不存在时忽略）。
+#[derive(Debug)]
+pub struct TempPath {
+    /// 临时路径
+    path: PathBuf,
+    /// 中断清理登记，在删除文件后取消
+    _cleanup: CleanupGuard,
+}
+
+impl TempPath {
+    /// 生成临时路径：`scripts-<用途>-<UUID>[.<扩展名>]`
+    fn new(purpose: &str, extension: Option<&str>) -> Result<Self> {
+        let root = temp_root();
+        std::fs::create_dir_all(&root)
+            .with_context(|| tr!("创建临时目录失败: {}", root.display()))?;
+
+        let mut name = format!("{}{}-{}", TEMP_PREFIX, purpose, Uuid::now_v7());
+        if let Some(extension) = extension {
+            name.push('.');
+            name.push_str(extension);
+        }
+        let path = root.join(name);
+        Ok(Self {
+            _cleanup: register_cleanup(&path),
+            path,
+        })
+    }
+
+    /// 创建临时文件路径（不创建文件本身）
+    ///
+    /// # 参数
+    ///
+    /// * `purpose` - 用途，出现在文件名中便于识别，例如 "transcode"
+    /// * `extension` - 扩展名（不带点），例如 "webm"
+    ///
+    /// # 返回值
+    ///
+    /// * `Ok(TempPath)` - 临时文件路径
+    /// * `Err(anyhow::Error)` - 无法创建临时目录
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use scripts::utils::tempfiles::TempPath;
+    ///
+    /// let temp = TempPath::file("transcode", "webm")?;
+    /// // 写入 temp.path() ...
+    /// // temp 离开作用域时自动删除
+    /// ```
+    pub fn file(purpose: &str, extension: &str) -> Result<Self> {
+        Self::new(purpose, Some(extension))
+    }
+
+    /// 创建临时目录
+    ///
+    /// # 参数
+    ///
+    /// * `purpose` - 用途，出现在目录名中便于识别，例如 "mirror"
+    ///
+    /// # 返回值
+    ///
+    /// * `Ok(TempPath)` - 已创建的临时目录
+    /// * `Err(anyhow::Error)` - 创建目录失败
+    pub fn dir(purpose: &str) -> Result<Self> {
+        let temp = Self::new(purpose, None)?;
+        std::fs::create_dir_all(&temp.path)
+            .with_context(|| tr!("创建临时目录失败: {}", temp.path.display()))?;
+        Ok(temp)
+    }
+
+    /// 在目标文件所在目录创建临时文件路径（不创建文件本身）
+    ///
+    /// 文件名为 `.scripts-<UUID>-<目标文件名>`，保留原扩展名，
+    /// 便于 ffmpeg 等根据扩展名选择输出格式的工具直接写入。
+    ///
+    /// # 参数
+    ///
+    /// * `target` - 最终输出文件路径
+    ///
+    /// # 返回值
+    ///
+    /// * `Ok(TempPath)` - 与目标文件位于同一目录的临时文件路径
+    /// * `Err(anyhow::Error)` - 目标路径没有文件名
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use scripts::utils::tempfiles::TempPath;
+    ///
+    /// let temp = TempPath::beside(&output_path)?;
+    /// // 写入 temp.path() ...
+    /// temp.persist(&output_path)?;
+    /// ```
+    pub fn beside(target: &Path) -> Result<Self> {
+        let file_name = target
+            .file_name()
+            .with_context(|| tr!("无效的输出路径: {}", target.display()))?;
+        let name = format!(
+            ".{}{}-{}",
+            TEMP_PREFIX,
+            Uuid::now_v7(),
+            file_name.to_string_lossy()
+        );
+        let path = target.with_file_name(name);
+        Ok(Self {
+            _cleanup: register_cleanup(&path),
+            path,
+        })
+    }
+
+    /// 将临时文件重命名为目标文件，覆盖已存在的目标文件
+    ///
+    /// 临时文件应由 [`TempPath::beside`] 创建，保证与目标位于同一文件系统。
+    ///
+    /// # 参数
+    ///
+    /// * `target` - 最终输出文件路径
+    ///
+    /// # 返回值
+    ///
+    /// * `Ok(())` - 重命名成功
+    /// * `Err(anyhow::Error)` - 重命名失败，临时文件随后被删除
+    pub fn persist(self, target: &Path) -> Result<()> {
+        std::fs::rename(&self.path, target)
+            .with_context(|| tr!("重命名为输出文件失败: {}", target.display()))
+    }
+
+    /// 临时路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if self.path.is_dir() {
+            let _ = std::fs::remove_dir_all(&self.path);
+        } else {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}